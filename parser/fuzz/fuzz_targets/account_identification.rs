@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use parser::{AccountIdentification, Bic, Bik, Iban, RussianAccount};
+
+fuzz_target!(|value: &str| {
+    let _ = AccountIdentification::try_from(value);
+    let _ = Iban::try_from(value);
+    let _ = Bic::try_from(value);
+    let _ = Bik::try_from(value);
+    let _ = RussianAccount::try_from(value);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use parser::Amount;
+
+fuzz_target!(|value: &str| {
+    let _ = Amount::try_from(value);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use parser::Balance;
+
+fuzz_target!(|value: &str| {
+    let _ = Balance::try_from(value);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use parser::CurrencyCode;
+
+fuzz_target!(|value: &str| {
+    let _ = CurrencyCode::try_from(value);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use parser::Date;
+
+fuzz_target!(|value: &str| {
+    let _ = Date::try_from(value);
+});
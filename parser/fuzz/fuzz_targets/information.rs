@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use parser::InformationFormat;
+use std::borrow::Cow;
+
+fuzz_target!(|value: &str| {
+    let information = value.lines().map(Cow::Borrowed).collect::<Vec<_>>();
+    for information_format in [
+        InformationFormat::Unstructured,
+        InformationFormat::SlashCodes,
+        InformationFormat::QuestionMarkSubfields,
+    ] {
+        let _ = information_format.subfields(&information);
+        let _ = information_format.counterparty(&information);
+    }
+});
@@ -0,0 +1,16 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use parser::{Mt940CustomerStatementMessage, Standard, detect_dialect, dialects};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = Mt940CustomerStatementMessage::parse_bytes(data, None);
+    let Ok(value) = std::str::from_utf8(data) else {
+        return;
+    };
+    let _ = Mt940CustomerStatementMessage::parse_with_dialect(value, &Standard);
+    let _ = Mt940CustomerStatementMessage::parse_with_dialect(value, detect_dialect(value));
+    for dialect in dialects() {
+        let _ = Mt940CustomerStatementMessage::parse_with_dialect(value, dialect);
+    }
+});
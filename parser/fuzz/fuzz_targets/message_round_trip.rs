@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use parser::{BalanceTimeline, Mt940CustomerStatementMessage, StatementAnonymizer};
+
+fuzz_target!(|message: Mt940CustomerStatementMessage<'static>| {
+    let timeline = BalanceTimeline::new(&message).unwrap();
+    assert_eq!(timeline.check(&message), Ok(()));
+
+    let text = StatementAnonymizer::new().anonymize(&message);
+    let anonymized = Mt940CustomerStatementMessage::try_from(text.as_str()).unwrap();
+    assert_eq!(
+        anonymized.statement_lines().len(),
+        message.statement_lines().len()
+    );
+    assert_eq!(anonymized.closing_balance(), message.closing_balance());
+});
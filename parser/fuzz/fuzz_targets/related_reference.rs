@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use parser::RelatedReference;
+
+fuzz_target!(|value: &str| {
+    let _ = RelatedReference::try_from(value);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use parser::StatementLine;
+
+fuzz_target!(|value: &str| {
+    let _ = StatementLine::try_from(value);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use parser::StatementSequenceNumber;
+
+fuzz_target!(|value: &str| {
+    let _ = StatementSequenceNumber::try_from(value);
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use parser::TextEncoding;
+
+fuzz_target!(|data: &[u8]| {
+    let encoding = TextEncoding::detect(data);
+    if let Ok(text) = encoding.decode(data) {
+        let _ = encoding.encode(&text);
+    }
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use parser::TransactionReferenceNumber;
+
+fuzz_target!(|value: &str| {
+    let _ = TransactionReferenceNumber::try_from(value);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use parser::TransactionTypeIdentificationCode;
+
+fuzz_target!(|value: &str| {
+    let _ = TransactionTypeIdentificationCode::try_from(value);
+});
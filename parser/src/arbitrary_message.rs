@@ -0,0 +1,237 @@
+use crate::money::*;
+use crate::mt_940_customer_statement_message::*;
+use arbitrary::{Arbitrary, Unstructured};
+use chrono::{Days, NaiveDate};
+use rust_decimal::Decimal;
+use std::fmt::Write;
+
+#[cfg(feature = "proptest")]
+use proptest::prelude::*;
+
+const ACCOUNTS: [&str; 5] = [
+    "NL91ABNA0417164300",
+    "DE89370400440532013000",
+    "DK5000400440116243",
+    "ABNANL2A/NL91ABNA0417164300",
+    "12-34-56/12345678",
+];
+const CURRENCIES: [(&str, u32); 5] = [("EUR", 2), ("USD", 2), ("GBP", 2), ("JPY", 0), ("KWD", 3)];
+const TRANSACTION_TYPES: [&str; 10] = [
+    "NTRF", "NCHK", "NMSC", "NCOM", "NDDT", "NSTO", "NINT", "FTRF", "S103", "S202",
+];
+const MARKS: [&str; 4] = ["C", "D", "RC", "RD"];
+const REFERENCE_CHARACTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789-.";
+const WORD_START_CHARACTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+const TEXT_CHARACTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789.,+-";
+const REFERENCE_MAX_LENGTH: usize = 16;
+const SUPPLEMENTARY_DETAILS_MAX_LENGTH: usize = 34;
+const INFORMATION_LINE_MAX_LENGTH: usize = 65;
+const INFORMATION_MAX_LINES: usize = 6;
+const STATEMENT_LINES_MAX: usize = 16;
+const STATEMENT_NUMBER_MAX: u16 = 9999;
+const AMOUNT_MAX_UNITS: i64 = 999_999_999;
+const MIN_YEAR: i32 = 2000;
+const MAX_YEAR: i32 = 2049;
+const VALUE_DATE_MAX_OFFSET: u64 = 3;
+const ENTRY_DATE_MAX_OFFSET: u64 = 2;
+const FORWARD_BALANCES_MAX: u64 = 3;
+const FORWARD_DATE_MAX_OFFSET: u64 = 5;
+#[cfg(feature = "proptest")]
+const PROPTEST_MAX_BYTES: usize = 4096;
+
+impl<'a> Arbitrary<'a> for Mt940CustomerStatementMessage<'static> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let text = arbitrary_message_text(u)?;
+        Mt940CustomerStatementMessage::try_from(text.as_str())
+            .map(Mt940CustomerStatementMessage::into_owned)
+            .map_err(|_| arbitrary::Error::IncorrectFormat)
+    }
+}
+
+#[cfg(feature = "proptest")]
+pub fn arbitrary_mt_940_customer_statement_message()
+-> impl Strategy<Value = Mt940CustomerStatementMessage<'static>> {
+    proptest::collection::vec(any::<u8>(), 0..PROPTEST_MAX_BYTES).prop_map(|bytes| {
+        Mt940CustomerStatementMessage::arbitrary(&mut Unstructured::new(&bytes))
+            .expect("generated message must be valid")
+    })
+}
+
+fn arbitrary_message_text(u: &mut Unstructured<'_>) -> arbitrary::Result<String> {
+    let (currency_code, minor_units) = *u.choose(&CURRENCIES)?;
+    let currency_code = CurrencyCode::try_from(currency_code)
+        .expect("generated currency code must be valid")
+        .into_owned();
+    let opening_date = date(u)?;
+
+    let mut text = format!(":20:{}\n", reference(u)?);
+    if u.arbitrary()? {
+        let _ = writeln!(text, ":21:{}", reference(u)?);
+    }
+    let _ = writeln!(text, ":25:{}", u.choose(&ACCOUNTS)?);
+    let _ = write!(text, ":28C:{}", u.int_in_range(1..=STATEMENT_NUMBER_MAX)?);
+    if u.arbitrary()? {
+        let _ = write!(text, "/{}", u.int_in_range(1..=STATEMENT_NUMBER_MAX)?);
+    }
+    text.push('\n');
+
+    let opening_balance = Money::new(amount(u, minor_units)?, currency_code.clone());
+    let opening_balance = if u.arbitrary()? {
+        -opening_balance
+    } else {
+        opening_balance
+    };
+    let _ = writeln!(text, ":60F:{}", balance(&opening_balance, opening_date));
+
+    let mut closing_balance = opening_balance;
+    let mut closing_date = opening_date;
+    let statement_lines = u.int_in_range(0..=STATEMENT_LINES_MAX)?;
+    for _ in 0..statement_lines {
+        let value_date = opening_date + Days::new(u.int_in_range(0..=VALUE_DATE_MAX_OFFSET)?);
+        closing_date = closing_date.max(value_date);
+        let _ = write!(text, ":61:{}", value_date.format("%y%m%d"));
+        if u.arbitrary()? {
+            let entry_date = value_date + Days::new(u.int_in_range(0..=ENTRY_DATE_MAX_OFFSET)?);
+            let _ = write!(text, "{}", entry_date.format("%m%d"));
+        }
+        let mark = *u.choose(&MARKS)?;
+        let amount = Money::new(amount(u, minor_units)?, currency_code.clone());
+        let _ = write!(text, "{}", mark);
+        if u.arbitrary()? {
+            text.extend(currency_code.as_str().chars().last());
+        }
+        let _ = write!(
+            text,
+            "{}{}{}",
+            amount.swift_amount(),
+            u.choose(&TRANSACTION_TYPES)?,
+            reference(u)?
+        );
+        if u.arbitrary()? {
+            let _ = write!(text, "//{}", reference(u)?);
+        }
+        text.push('\n');
+        if u.arbitrary()? {
+            let _ = writeln!(text, "{}", words(u, SUPPLEMENTARY_DETAILS_MAX_LENGTH)?);
+        }
+        if u.arbitrary()? {
+            let _ = writeln!(text, ":86:{}", information(u)?);
+        }
+        closing_balance = match mark {
+            "C" | "RD" => (closing_balance + amount).expect("currencies must match"),
+            _ => (closing_balance - amount).expect("currencies must match"),
+        };
+    }
+
+    let _ = writeln!(text, ":62F:{}", balance(&closing_balance, closing_date));
+    if u.arbitrary()? {
+        let _ = writeln!(text, ":64:{}", balance(&closing_balance, closing_date));
+    }
+    let mut forward_date = closing_date;
+    for _ in 0..u.int_in_range(0..=FORWARD_BALANCES_MAX)? {
+        forward_date = forward_date + Days::new(u.int_in_range(1..=FORWARD_DATE_MAX_OFFSET)?);
+        let _ = writeln!(text, ":65:{}", balance(&closing_balance, forward_date));
+    }
+    if u.arbitrary()? {
+        let _ = writeln!(text, ":86:{}", information(u)?);
+    }
+    text.push('-');
+    Ok(text)
+}
+
+fn date(u: &mut Unstructured<'_>) -> arbitrary::Result<NaiveDate> {
+    let first = NaiveDate::from_ymd_opt(MIN_YEAR, 1, 1).expect("first date must be valid");
+    let last = NaiveDate::from_ymd_opt(MAX_YEAR, 12, 31).expect("last date must be valid")
+        - Days::new(
+            VALUE_DATE_MAX_OFFSET
+                + ENTRY_DATE_MAX_OFFSET
+                + FORWARD_BALANCES_MAX * FORWARD_DATE_MAX_OFFSET,
+        );
+    let days = (last - first).num_days() as u64;
+    Ok(first + Days::new(u.int_in_range(0..=days)?))
+}
+
+fn amount(u: &mut Unstructured<'_>, minor_units: u32) -> arbitrary::Result<Decimal> {
+    let units = u.int_in_range(0..=AMOUNT_MAX_UNITS)?;
+    Ok(Decimal::new(units, minor_units))
+}
+
+fn balance(money: &Money, date: NaiveDate) -> String {
+    format!(
+        "{}{}{}{}",
+        money.credit_debit_mark().code(),
+        date.format("%y%m%d"),
+        money.currency_code(),
+        money.swift_amount()
+    )
+}
+
+fn reference(u: &mut Unstructured<'_>) -> arbitrary::Result<String> {
+    let length = u.int_in_range(1..=REFERENCE_MAX_LENGTH)?;
+    (0..length)
+        .map(|_| u.choose(REFERENCE_CHARACTERS).map(|&c| char::from(c)))
+        .collect()
+}
+
+fn words(u: &mut Unstructured<'_>, max_length: usize) -> arbitrary::Result<String> {
+    let mut text = String::new();
+    loop {
+        let length = u.int_in_range(1..=max_length - text.len())?;
+        text.push(char::from(*u.choose(WORD_START_CHARACTERS)?));
+        for _ in 1..length {
+            text.push(char::from(*u.choose(TEXT_CHARACTERS)?));
+        }
+        if text.len() + 2 > max_length || !u.arbitrary()? {
+            return Ok(text);
+        }
+        text.push(' ');
+    }
+}
+
+fn information(u: &mut Unstructured<'_>) -> arbitrary::Result<String> {
+    let lines = u.int_in_range(1..=INFORMATION_MAX_LINES)?;
+    let lines = (0..lines)
+        .map(|_| words(u, INFORMATION_LINE_MAX_LENGTH))
+        .collect::<arbitrary::Result<Vec<_>>>()?;
+    Ok(lines.join("\n"))
+}
+
+#[cfg(all(test, feature = "proptest"))]
+mod tests {
+    use super::*;
+    use crate::balance_timeline::*;
+    use crate::statement_anonymizer::*;
+    use crate::statement_diff::*;
+
+    proptest! {
+        #[test]
+        fn test_generated_balances_are_consistent(
+            message in arbitrary_mt_940_customer_statement_message()
+        ) {
+            let timeline = BalanceTimeline::new(&message).unwrap();
+            prop_assert_eq!(timeline.check(&message), Ok(()));
+            prop_assert!(message.opening_balance().check_currency().is_ok());
+        }
+
+        #[test]
+        fn test_anonymized_message_round_trip(
+            message in arbitrary_mt_940_customer_statement_message(),
+            seed in any::<u64>()
+        ) {
+            let text = StatementAnonymizer::new().with_amount_perturbation(seed).anonymize(&message);
+            let anonymized = Mt940CustomerStatementMessage::try_from(text.as_str()).unwrap();
+            prop_assert_eq!(anonymized.statement_lines().len(), message.statement_lines().len());
+            let timeline = BalanceTimeline::new(&anonymized).unwrap();
+            prop_assert_eq!(timeline.check(&anonymized), Ok(()));
+            prop_assert!(StatementDiff::new(&anonymized, &anonymized).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_arbitrary_message_from_empty_input() {
+        let message =
+            Mt940CustomerStatementMessage::arbitrary(&mut Unstructured::new(&[])).unwrap();
+        assert!(message.statement_lines().is_empty());
+        assert_eq!(message.opening_balance(), message.closing_balance());
+    }
+}
@@ -0,0 +1,368 @@
+use crate::money::*;
+use crate::mt_940_customer_statement_message::*;
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyBalance {
+    date: NaiveDate,
+    balance: Money,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BalanceTimeline {
+    opening_balance: Money,
+    running_balances: Vec<Money>,
+    ledger_balances: Vec<DailyBalance>,
+    value_dated_balances: Vec<DailyBalance>,
+}
+
+impl DailyBalance {
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn balance(&self) -> &Money {
+        &self.balance
+    }
+}
+
+impl BalanceTimeline {
+    pub fn new(message: &Mt940CustomerStatementMessage<'_>) -> Result<Self, MoneyError> {
+        let opening_balance = Money::from(message.opening_balance());
+        let currency_code = opening_balance.currency_code().clone();
+        let movements = message
+            .statement_lines()
+            .iter()
+            .map(|statement_line| Money::from_statement_line(statement_line, &currency_code))
+            .collect::<Vec<_>>();
+
+        let mut running_balances = Vec::with_capacity(movements.len());
+        let mut balance = opening_balance.clone();
+        for movement in &movements {
+            balance = (balance + movement.clone())?;
+            running_balances.push(balance.clone());
+        }
+
+        let ledger_balances = daily_balances(
+            &opening_balance,
+            message
+                .statement_lines()
+                .iter()
+                .map(|statement_line| statement_line.booking_date().value())
+                .zip(movements.iter().cloned()),
+        )?;
+        let value_dated_balances = daily_balances(
+            &opening_balance,
+            message
+                .statement_lines()
+                .iter()
+                .map(|statement_line| statement_line.value_date().value())
+                .zip(movements),
+        )?;
+
+        Ok(Self {
+            opening_balance,
+            running_balances,
+            ledger_balances,
+            value_dated_balances,
+        })
+    }
+
+    pub fn opening_balance(&self) -> &Money {
+        &self.opening_balance
+    }
+
+    pub fn running_balances(&self) -> &[Money] {
+        &self.running_balances
+    }
+
+    pub fn ledger_balances(&self) -> &[DailyBalance] {
+        &self.ledger_balances
+    }
+
+    pub fn value_dated_balances(&self) -> &[DailyBalance] {
+        &self.value_dated_balances
+    }
+
+    pub fn closing_balance(&self) -> &Money {
+        self.running_balances
+            .last()
+            .unwrap_or(&self.opening_balance)
+    }
+
+    pub fn value_dated_balance_at(&self, date: NaiveDate) -> &Money {
+        self.value_dated_balances
+            .iter()
+            .take_while(|daily_balance| daily_balance.date <= date)
+            .last()
+            .map(DailyBalance::balance)
+            .unwrap_or(&self.opening_balance)
+    }
+
+    pub fn check(
+        &self,
+        message: &Mt940CustomerStatementMessage<'_>,
+    ) -> Result<(), BalanceTimelineError> {
+        let closing_balance = Money::from(message.closing_balance());
+        if &closing_balance != self.closing_balance() {
+            return Err(BalanceTimelineError::ClosingBalanceMismatch(
+                closing_balance,
+                self.closing_balance().clone(),
+            ));
+        }
+        if let Some(balance) = message.closing_available_balance() {
+            let computed = self.value_dated_balance_at(balance.date().value());
+            if &Money::from(balance) != computed {
+                return Err(BalanceTimelineError::ClosingAvailableBalanceMismatch(
+                    Money::from(balance),
+                    computed.clone(),
+                ));
+            }
+        }
+        for balance in message.forward_available_balances() {
+            let computed = self.value_dated_balance_at(balance.date().value());
+            if &Money::from(balance) != computed {
+                return Err(BalanceTimelineError::ForwardAvailableBalanceMismatch(
+                    Money::from(balance),
+                    computed.clone(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn daily_balances(
+    opening_balance: &Money,
+    movements: impl Iterator<Item = (NaiveDate, Money)>,
+) -> Result<Vec<DailyBalance>, MoneyError> {
+    let mut movements_by_date = BTreeMap::<NaiveDate, Vec<Money>>::new();
+    for (date, movement) in movements {
+        movements_by_date.entry(date).or_default().push(movement);
+    }
+    let mut daily_balances = Vec::with_capacity(movements_by_date.len());
+    let mut balance = opening_balance.clone();
+    for (date, movements) in movements_by_date {
+        balance = movements
+            .into_iter()
+            .try_fold(balance, |balance, movement| balance + movement)?;
+        daily_balances.push(DailyBalance {
+            date,
+            balance: balance.clone(),
+        });
+    }
+    Ok(daily_balances)
+}
+
+impl Display for BalanceTimeline {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Opening balance: {}", self.opening_balance)?;
+        for daily_balance in &self.ledger_balances {
+            writeln!(
+                f,
+                "- Ledger balance {}: {}",
+                daily_balance.date, daily_balance.balance
+            )?;
+        }
+        for daily_balance in &self.value_dated_balances {
+            writeln!(
+                f,
+                "- Value-dated balance {}: {}",
+                daily_balance.date, daily_balance.balance
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum BalanceTimelineError {
+    ClosingBalanceMismatch(Money, Money),
+    ClosingAvailableBalanceMismatch(Money, Money),
+    ForwardAvailableBalanceMismatch(Money, Money),
+}
+
+impl Display for BalanceTimelineError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BalanceTimelineError::ClosingBalanceMismatch(expected, computed) => write!(
+                f,
+                ":62a: closing balance {} does not match computed balance {}",
+                expected, computed
+            ),
+            BalanceTimelineError::ClosingAvailableBalanceMismatch(expected, computed) => write!(
+                f,
+                ":64: closing available balance {} does not match computed balance {}",
+                expected, computed
+            ),
+            BalanceTimelineError::ForwardAvailableBalanceMismatch(expected, computed) => write!(
+                f,
+                ":65: forward available balance {} does not match computed balance {}",
+                expected, computed
+            ),
+        }
+    }
+}
+
+impl Error for BalanceTimelineError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    const MESSAGE: &str = ":20:STMT
+:25:DK5000400440116243
+:28C:1
+:60F:C230305DKK1000,00
+:61:2303060306D200,50NTRFINV-1001
+:61:2303080306C300,00NTRFINV-1002
+:61:2303070307D100,00NCHKNONREF
+:62F:C230307DKK999,50
+:64:C230307DKK699,50
+:65:C230308DKK999,50
+-";
+
+    fn money(value: &str) -> Money {
+        Money::new(
+            Decimal::from_str(value).unwrap(),
+            CurrencyCode::try_from("DKK").unwrap(),
+        )
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2023, 3, day).unwrap()
+    }
+
+    #[test]
+    fn test_balance_timeline() {
+        let message = Mt940CustomerStatementMessage::try_from(MESSAGE).unwrap();
+        let timeline = BalanceTimeline::new(&message).unwrap();
+        assert_eq!(
+            timeline.running_balances(),
+            [money("799.50"), money("1099.50"), money("999.50")]
+        );
+        assert_eq!(
+            timeline.ledger_balances(),
+            [
+                DailyBalance {
+                    date: date(6),
+                    balance: money("1099.50")
+                },
+                DailyBalance {
+                    date: date(7),
+                    balance: money("999.50")
+                },
+            ]
+        );
+        assert_eq!(
+            timeline.value_dated_balances(),
+            [
+                DailyBalance {
+                    date: date(6),
+                    balance: money("799.50")
+                },
+                DailyBalance {
+                    date: date(7),
+                    balance: money("699.50")
+                },
+                DailyBalance {
+                    date: date(8),
+                    balance: money("999.50")
+                },
+            ]
+        );
+        assert_eq!(timeline.value_dated_balance_at(date(5)), &money("1000"));
+        assert_eq!(timeline.closing_balance(), &money("999.50"));
+        assert_eq!(timeline.check(&message), Ok(()));
+        assert_eq!(
+            timeline.to_string(),
+            "Opening balance: DKK 1000,00
+- Ledger balance 2023-03-06: DKK 1099,50
+- Ledger balance 2023-03-07: DKK 999,50
+- Value-dated balance 2023-03-06: DKK 799,50
+- Value-dated balance 2023-03-07: DKK 699,50
+- Value-dated balance 2023-03-08: DKK 999,50
+"
+        );
+    }
+
+    #[test]
+    fn test_balance_timeline_unsorted_lines() {
+        let message = Mt940CustomerStatementMessage::try_from(
+            ":20:STMT
+:25:DK5000400440116243
+:28C:1
+:60F:C230305DKK100,00
+:61:230307D100,00NTRFNONREF
+:61:230306C50,00NTRFNONREF
+:62F:C230307DKK50,00
+-",
+        )
+        .unwrap();
+        let timeline = BalanceTimeline::new(&message).unwrap();
+        assert_eq!(timeline.running_balances(), [money("0"), money("50")]);
+        assert_eq!(
+            timeline.ledger_balances(),
+            [
+                DailyBalance {
+                    date: date(6),
+                    balance: money("150")
+                },
+                DailyBalance {
+                    date: date(7),
+                    balance: money("50")
+                },
+            ]
+        );
+        assert_eq!(
+            timeline.ledger_balances().last().unwrap().balance(),
+            timeline.closing_balance()
+        );
+        assert_eq!(timeline.check(&message), Ok(()));
+    }
+
+    #[test]
+    fn test_balance_timeline_mismatch() {
+        let message = MESSAGE.replace(":62F:C230307DKK999,50", ":62F:C230307DKK999,00");
+        let message = Mt940CustomerStatementMessage::try_from(message.as_str()).unwrap();
+        let result = BalanceTimeline::new(&message).unwrap().check(&message);
+        assert_eq!(
+            result,
+            Err(BalanceTimelineError::ClosingBalanceMismatch(
+                money("999.00"),
+                money("999.50")
+            ))
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            ":62a: closing balance DKK 999,00 does not match computed balance DKK 999,50"
+        );
+
+        let message = MESSAGE.replace(":64:C230307DKK699,50", ":64:C230307DKK999,50");
+        let message = Mt940CustomerStatementMessage::try_from(message.as_str()).unwrap();
+        let result = BalanceTimeline::new(&message).unwrap().check(&message);
+        assert_eq!(
+            result,
+            Err(BalanceTimelineError::ClosingAvailableBalanceMismatch(
+                money("999.50"),
+                money("699.50")
+            ))
+        );
+
+        let message = MESSAGE.replace(":65:C230308DKK999,50", ":65:C230308DKK699,50");
+        let message = Mt940CustomerStatementMessage::try_from(message.as_str()).unwrap();
+        let result = BalanceTimeline::new(&message).unwrap().check(&message);
+        assert_eq!(
+            result,
+            Err(BalanceTimelineError::ForwardAvailableBalanceMismatch(
+                money("699.50"),
+                money("999.50")
+            ))
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use chrono::NaiveDate;
+use parser::{CurrencyCode, StatementGenerator};
+use std::fs::File;
+use std::io::BufWriter;
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: generate_statements [--seed N] [--accounts N] [--days N] \
+[--transactions-per-day N] [--start-date YYYY-MM-DD] [--currency CODE] [--output PATH]";
+
+fn main() -> ExitCode {
+    match run(std::env::args().skip(1)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+    let mut generator = StatementGenerator::new();
+    let mut output = None;
+    while let Some(arg) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value for {}", arg))?;
+        generator = match arg.as_str() {
+            "--seed" => generator.with_seed(number(&value)?),
+            "--accounts" => generator.with_accounts(number(&value)?),
+            "--days" => generator.with_days(number(&value)?),
+            "--transactions-per-day" => generator.with_transactions_per_day(number(&value)?),
+            "--start-date" => generator.with_start_date(
+                NaiveDate::parse_from_str(&value, "%Y-%m-%d")
+                    .map_err(|_| format!("Invalid start date {}", value))?,
+            ),
+            "--currency" => generator.with_currency_code(
+                CurrencyCode::try_from(value.as_str())
+                    .map_err(|err| format!("Invalid currency {}: {}", value, err))?,
+            ),
+            "--output" => {
+                output = Some(value);
+                generator
+            }
+            _ => return Err(format!("Unknown argument {}", arg)),
+        };
+    }
+
+    let result = match output {
+        Some(path) => File::create(&path).and_then(|file| generator.write_to(BufWriter::new(file))),
+        None => generator.write_to(BufWriter::new(std::io::stdout().lock())),
+    };
+    result.map_err(|err| format!("Failed to write statements: {}", err))
+}
+
+fn number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid number {}", value))
+}
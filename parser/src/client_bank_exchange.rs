@@ -0,0 +1,111 @@
+mod client_bank_exchange_reader;
+mod client_bank_exchange_writer;
+
+pub use crate::client_bank_exchange::client_bank_exchange_reader::*;
+pub use crate::client_bank_exchange::client_bank_exchange_writer::*;
+use crate::mt_940_customer_statement_message::*;
+use crate::text_encoding::*;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+const FILE_HEADER: &str = "1CClientBankExchange";
+const FILE_END: &str = "КонецФайла";
+const FORMAT_VERSION: &str = "ВерсияФормата";
+const ENCODING: &str = "Кодировка";
+const SENDER: &str = "Отправитель";
+const CREATION_DATE: &str = "ДатаСоздания";
+const CREATION_TIME: &str = "ВремяСоздания";
+const START_DATE: &str = "ДатаНачала";
+const END_DATE: &str = "ДатаКонца";
+const ACCOUNT: &str = "РасчСчет";
+const ACCOUNT_SECTION_START: &str = "СекцияРасчСчет";
+const ACCOUNT_SECTION_END: &str = "КонецРасчСчет";
+const OPENING_BALANCE: &str = "НачальныйОстаток";
+const TOTAL_CREDITS: &str = "ВсегоПоступило";
+const TOTAL_DEBITS: &str = "ВсегоСписано";
+const CLOSING_BALANCE: &str = "КонечныйОстаток";
+const DOCUMENT_SECTION_START: &str = "СекцияДокумент";
+const DOCUMENT_SECTION_END: &str = "КонецДокумента";
+const DOCUMENT_NUMBER: &str = "Номер";
+const DOCUMENT_DATE: &str = "Дата";
+const DOCUMENT_AMOUNT: &str = "Сумма";
+const PAYER_ACCOUNT: &str = "ПлательщикСчет";
+const PAYER: &str = "Плательщик";
+const PAYER_INN: &str = "ПлательщикИНН";
+const DEBIT_DATE: &str = "ДатаСписано";
+const RECIPIENT_ACCOUNT: &str = "ПолучательСчет";
+const RECIPIENT: &str = "Получатель";
+const RECIPIENT_INN: &str = "ПолучательИНН";
+const CREDIT_DATE: &str = "ДатаПоступило";
+const PAYMENT_PURPOSE: &str = "НазначениеПлатежа";
+const WINDOWS_ENCODING: &str = "Windows";
+const DOS_ENCODING: &str = "DOS";
+const DATE_FORMAT: &str = "%d.%m.%Y";
+const TIME_FORMAT: &str = "%H:%M:%S";
+const PAYMENT_ORDER: &str = "Платежное поручение";
+const PAYMENT_REQUEST: &str = "Платежное требование";
+const COLLECTION_ORDER: &str = "Инкассовое поручение";
+const BANK_ORDER: &str = "Банковский ордер";
+
+fn document_type(
+    transaction_type_identification_code: &TransactionTypeIdentificationCode<'_>,
+) -> &'static str {
+    match transaction_type_identification_code.transaction_type() {
+        None | Some(TransactionType::Transfer) => PAYMENT_ORDER,
+        Some(TransactionType::DirectDebit) => PAYMENT_REQUEST,
+        Some(TransactionType::Collection) => COLLECTION_ORDER,
+        Some(_) => BANK_ORDER,
+    }
+}
+
+fn transaction_type_identification_code(document_type: &str) -> &'static str {
+    match document_type {
+        PAYMENT_ORDER | "Платежный ордер" => "NTRF",
+        PAYMENT_REQUEST => "NDDT",
+        COLLECTION_ORDER => "NCOL",
+        _ => "NMSC",
+    }
+}
+
+#[derive(Debug)]
+pub enum ClientBankExchangeError {
+    InvalidEncoding(TextEncodingError),
+    InvalidHeader,
+    UnexpectedEnd,
+    UnexpectedLine(String),
+    MissingKey(&'static str),
+    InvalidValue(&'static str, String),
+    UnmappableCharacter,
+    InvalidMessage(Mt940CustomerStatementMessageParseError),
+}
+
+impl Display for ClientBankExchangeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientBankExchangeError::InvalidEncoding(err) => {
+                write!(f, "File cannot be decoded: {}", err)
+            }
+            ClientBankExchangeError::InvalidHeader => {
+                write!(f, "File does not start with {}", FILE_HEADER)
+            }
+            ClientBankExchangeError::UnexpectedEnd => {
+                write!(f, "File ends without {}", FILE_END)
+            }
+            ClientBankExchangeError::UnexpectedLine(line) => {
+                write!(f, "Unexpected line {}", line)
+            }
+            ClientBankExchangeError::MissingKey(key) => write!(f, "Missing key {}", key),
+            ClientBankExchangeError::InvalidValue(key, value) => {
+                write!(f, "Invalid value {} for key {}", value, key)
+            }
+            ClientBankExchangeError::UnmappableCharacter => {
+                write!(f, "Text cannot be encoded in Windows-1251")
+            }
+            ClientBankExchangeError::InvalidMessage(err) => {
+                write!(f, "Statement cannot be converted: {}", err)
+            }
+        }
+    }
+}
+
+impl Error for ClientBankExchangeError {}
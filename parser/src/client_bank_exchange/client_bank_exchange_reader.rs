@@ -0,0 +1,451 @@
+use crate::client_bank_exchange::*;
+use crate::money::*;
+use crate::text_encoding::*;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::borrow::Cow;
+use std::fmt::Write;
+
+const TRANSACTION_REFERENCE_PREFIX: &str = "1C";
+const ACCOUNT_OWNER_REFERENCE_MAX_LENGTH: usize = 16;
+const INFORMATION_LINE_MAX_LENGTH: usize = 65;
+const NO_REFERENCE: &str = "NONREF";
+const DEFAULT_CURRENCY_CODE: &str = "RUB";
+const LEGACY_ROUBLE_CODE: &str = "810";
+
+type Section<'a> = Vec<(&'a str, &'a str)>;
+type DocumentSide = (bool, NaiveDate, [&'static str; 3]);
+
+struct ClientBankExchange<'a> {
+    account_sections: Vec<Section<'a>>,
+    documents: Vec<(&'a str, Section<'a>)>,
+}
+
+pub fn decode_client_bank_exchange(bytes: &[u8]) -> Result<Cow<'_, str>, ClientBankExchangeError> {
+    declared_encoding(bytes)
+        .unwrap_or_else(|| TextEncoding::detect(bytes))
+        .decode(bytes)
+        .map_err(ClientBankExchangeError::InvalidEncoding)
+}
+
+fn declared_encoding(bytes: &[u8]) -> Option<TextEncoding> {
+    let key = format!("{}=", ENCODING);
+    [TextEncoding::Windows1251, TextEncoding::Ibm866]
+        .into_iter()
+        .find_map(|encoding| {
+            let key = encoding.encode(&key).ok()?;
+            let start = bytes
+                .windows(key.len())
+                .position(|window| *window == *key)?
+                + key.len();
+            let value = bytes[start..]
+                .split(|byte| matches!(byte, b'\r' | b'\n'))
+                .next()?
+                .trim_ascii();
+            if value == WINDOWS_ENCODING.as_bytes() {
+                Some(TextEncoding::Windows1251)
+            } else if value == DOS_ENCODING.as_bytes() {
+                Some(TextEncoding::Ibm866)
+            } else {
+                None
+            }
+        })
+}
+
+pub fn read_client_bank_exchange(
+    bytes: &[u8],
+) -> Result<Vec<Mt940CustomerStatementMessage<'static>>, ClientBankExchangeError> {
+    let text = decode_client_bank_exchange(bytes)?;
+    let file = sections(&text)?;
+    file.account_sections
+        .iter()
+        .enumerate()
+        .map(|(index, section)| statement(index + 1, section, &file.documents))
+        .collect()
+}
+
+fn sections(text: &str) -> Result<ClientBankExchange<'_>, ClientBankExchangeError> {
+    let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+    if lines.next() != Some(FILE_HEADER) {
+        return Err(ClientBankExchangeError::InvalidHeader);
+    }
+
+    let mut file = ClientBankExchange {
+        account_sections: Vec::new(),
+        documents: Vec::new(),
+    };
+    let mut current: Option<(&str, Section)> = None;
+    for line in lines {
+        let (key, value) = line.split_once('=').unwrap_or((line, ""));
+        match (current.as_mut(), key) {
+            (None, FILE_END) => return Ok(file),
+            (None, ACCOUNT_SECTION_START) => current = Some((ACCOUNT_SECTION_START, Vec::new())),
+            (None, DOCUMENT_SECTION_START) => current = Some((value, Vec::new())),
+            (None, _) if line.contains('=') => {}
+            (Some((ACCOUNT_SECTION_START, _)), ACCOUNT_SECTION_END) => {
+                let (_, section) = current.take().unwrap_or_default();
+                file.account_sections.push(section);
+            }
+            (Some(_), DOCUMENT_SECTION_END) => {
+                file.documents.extend(current.take());
+            }
+            (Some((_, section)), _) if line.contains('=') => section.push((key, value.trim())),
+            _ => return Err(ClientBankExchangeError::UnexpectedLine(line.to_string())),
+        }
+    }
+    Err(ClientBankExchangeError::UnexpectedEnd)
+}
+
+fn statement(
+    number: usize,
+    section: &Section<'_>,
+    documents: &[(&str, Section<'_>)],
+) -> Result<Mt940CustomerStatementMessage<'static>, ClientBankExchangeError> {
+    let account = expect_value(section, ACCOUNT)?;
+    let start_date = date(section, START_DATE)?;
+    let end_date = optional_value(section, END_DATE)
+        .map(|_| date(section, END_DATE))
+        .transpose()?
+        .unwrap_or(start_date);
+    let currency_code = currency_code(account);
+    let opening_balance = amount(section, OPENING_BALANCE)?;
+    let closing_balance = amount(section, CLOSING_BALANCE)?;
+
+    let mut text = String::new();
+    let _ = writeln!(
+        text,
+        ":20:{}{}",
+        TRANSACTION_REFERENCE_PREFIX,
+        start_date.format("%y%m%d")
+    );
+    let _ = writeln!(text, ":25:{}", account);
+    let _ = writeln!(text, ":28C:{}", number);
+    let _ = writeln!(
+        text,
+        ":60F:{}",
+        balance(opening_balance, start_date, currency_code)
+    );
+    for (document_type, document) in documents {
+        let Some((debit, date, counterparty)) = side(document, account)? else {
+            continue;
+        };
+        if date < start_date || date > end_date {
+            continue;
+        }
+        let value = amount(document, DOCUMENT_AMOUNT)?;
+        let _ = writeln!(
+            text,
+            ":61:{}{}{}{}{}",
+            date.format("%y%m%d"),
+            if debit { "D" } else { "C" },
+            swift_amount(value, currency_code),
+            transaction_type_identification_code(document_type),
+            account_owner_ref(optional_value(document, DOCUMENT_NUMBER).unwrap_or_default())
+        );
+        let information = information(document, counterparty);
+        if !information.is_empty() {
+            let _ = writeln!(text, ":86:{}", information.join("\n"));
+        }
+    }
+    let _ = writeln!(
+        text,
+        ":62F:{}",
+        balance(closing_balance, end_date, currency_code)
+    );
+    text.push('-');
+
+    Mt940CustomerStatementMessage::try_from(text.as_str())
+        .map(Mt940CustomerStatementMessage::into_owned)
+        .map_err(ClientBankExchangeError::InvalidMessage)
+}
+
+fn side(
+    document: &Section<'_>,
+    account: &str,
+) -> Result<Option<DocumentSide>, ClientBankExchangeError> {
+    let (debit, date_key, counterparty) =
+        if optional_value(document, PAYER_ACCOUNT) == Some(account) {
+            (
+                true,
+                DEBIT_DATE,
+                [RECIPIENT, RECIPIENT_INN, RECIPIENT_ACCOUNT],
+            )
+        } else if optional_value(document, RECIPIENT_ACCOUNT) == Some(account) {
+            (false, CREDIT_DATE, [PAYER, PAYER_INN, PAYER_ACCOUNT])
+        } else {
+            return Ok(None);
+        };
+    let date_key = match optional_value(document, date_key) {
+        Some(_) => date_key,
+        None => DOCUMENT_DATE,
+    };
+    Ok(Some((debit, date(document, date_key)?, counterparty)))
+}
+
+fn information(document: &Section<'_>, counterparty: [&'static str; 3]) -> Vec<String> {
+    let [name, inn, account] = counterparty.map(|key| optional_value(document, key));
+    let mut lines = Vec::new();
+    lines.extend(name.map(str::to_string));
+    lines.extend(inn.map(|inn| format!("ИНН {}", inn)));
+    lines.extend(account.map(|account| format!("СЧ {}", account)));
+    if let Some(purpose) = optional_value(document, PAYMENT_PURPOSE) {
+        let chars = purpose.chars().collect::<Vec<_>>();
+        lines.extend(
+            chars
+                .chunks(INFORMATION_LINE_MAX_LENGTH)
+                .map(|chunk| chunk.iter().collect::<String>()),
+        );
+    }
+    lines
+        .into_iter()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty() && !line.starts_with([':', '-']))
+        .collect()
+}
+
+fn optional_value<'a>(section: &Section<'a>, key: &str) -> Option<&'a str> {
+    section
+        .iter()
+        .find(|(name, _)| *name == key)
+        .map(|(_, value)| *value)
+        .filter(|value| !value.is_empty())
+}
+
+fn expect_value<'a>(
+    section: &Section<'a>,
+    key: &'static str,
+) -> Result<&'a str, ClientBankExchangeError> {
+    optional_value(section, key).ok_or(ClientBankExchangeError::MissingKey(key))
+}
+
+fn date(section: &Section<'_>, key: &'static str) -> Result<NaiveDate, ClientBankExchangeError> {
+    let value = expect_value(section, key)?;
+    NaiveDate::parse_from_str(value, DATE_FORMAT)
+        .map_err(|_| ClientBankExchangeError::InvalidValue(key, value.to_string()))
+}
+
+fn amount(section: &Section<'_>, key: &'static str) -> Result<Decimal, ClientBankExchangeError> {
+    let value = expect_value(section, key)?;
+    value
+        .replace(',', ".")
+        .parse()
+        .map_err(|_| ClientBankExchangeError::InvalidValue(key, value.to_string()))
+}
+
+fn currency_code(account: &str) -> &'static str {
+    match account.get(5..8) {
+        Some(LEGACY_ROUBLE_CODE) => DEFAULT_CURRENCY_CODE,
+        Some(code) => code
+            .parse()
+            .ok()
+            .and_then(Iso4217Currency::find_by_numeric_code)
+            .map(Iso4217Currency::code)
+            .unwrap_or(DEFAULT_CURRENCY_CODE),
+        None => DEFAULT_CURRENCY_CODE,
+    }
+}
+
+fn swift_amount(value: Decimal, currency_code: &str) -> String {
+    let currency_code = CurrencyCode::try_from(currency_code).unwrap_or_else(|_| {
+        CurrencyCode::try_from(DEFAULT_CURRENCY_CODE).expect("default currency code is valid")
+    });
+    Money::new(value, currency_code).swift_amount()
+}
+
+fn balance(value: Decimal, date: NaiveDate, currency_code: &str) -> String {
+    let mark = if value.is_sign_negative() && !value.is_zero() {
+        "D"
+    } else {
+        "C"
+    };
+    format!(
+        "{}{}{}{}",
+        mark,
+        date.format("%y%m%d"),
+        currency_code,
+        swift_amount(value, currency_code)
+    )
+}
+
+fn account_owner_ref(number: &str) -> String {
+    let reference = number
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+        .take(ACCOUNT_OWNER_REFERENCE_MAX_LENGTH)
+        .collect::<String>();
+    if reference.is_empty() {
+        NO_REFERENCE.to_string()
+    } else {
+        reference
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIENT_BANK_EXCHANGE: &str = "1CClientBankExchange
+ВерсияФормата=1.03
+Кодировка=Windows
+Отправитель=Бухгалтерия предприятия
+Получатель=
+ДатаСоздания=07.03.2023
+ВремяСоздания=12:00:00
+ДатаНачала=06.03.2023
+ДатаКонца=06.03.2023
+РасчСчет=40702810938000012345
+СекцияРасчСчет
+ДатаНачала=06.03.2023
+ДатаКонца=06.03.2023
+РасчСчет=40702810938000012345
+НачальныйОстаток=1000.00
+ВсегоПоступило=50.00
+ВсегоСписано=200.50
+КонечныйОстаток=849.50
+КонецРасчСчет
+СекцияДокумент=Платежное поручение
+Номер=1001
+Дата=06.03.2023
+Сумма=200.50
+ПлательщикСчет=40702810938000012345
+ДатаСписано=06.03.2023
+Плательщик=ООО \"Ромашка\"
+ПолучательСчет=40702810400000000001
+Получатель=ООО \"Лютик\"
+ПолучательИНН=7701234567
+НазначениеПлатежа=Оплата по счету 1001 от 01.03.2023
+КонецДокумента
+СекцияДокумент=Банковский ордер
+Номер=17
+Дата=06.03.2023
+Сумма=50.00
+ПлательщикСчет=30101810400000000225
+Плательщик=ПАО Сбербанк
+ПолучательСчет=40702810938000012345
+ДатаПоступило=06.03.2023
+НазначениеПлатежа=Проценты на остаток
+КонецДокумента
+КонецФайла
+";
+
+    #[test]
+    fn test_read_client_bank_exchange() {
+        let bytes = TextEncoding::Windows1251
+            .encode(CLIENT_BANK_EXCHANGE)
+            .unwrap();
+        let messages = read_client_bank_exchange(&bytes).unwrap();
+        assert_eq!(messages.len(), 1);
+
+        let message = &messages[0];
+        assert_eq!(message.transaction_reference_number().as_str(), "1C230306");
+        assert_eq!(
+            message.account_identification().as_str(),
+            "40702810938000012345"
+        );
+        assert_eq!(
+            message.opening_balance(),
+            &Balance::try_from("C230306RUB1000,00").unwrap()
+        );
+        assert_eq!(
+            message.closing_balance(),
+            &Balance::try_from("C230306RUB849,50").unwrap()
+        );
+
+        let statement_lines = message.statement_lines();
+        assert_eq!(statement_lines.len(), 2);
+        assert_eq!(
+            statement_lines[0].debit_credit_mark(),
+            &StatementLineMark::Debit
+        );
+        assert_eq!(statement_lines[0].account_owner_ref(), "1001");
+        assert_eq!(
+            statement_lines[0].transaction_type_identification_code(),
+            &TransactionTypeIdentificationCode::NonSwift(TransactionType::Transfer)
+        );
+        assert_eq!(
+            statement_lines[0].information_to_account_owner(),
+            Some(
+                &[
+                    "ООО \"Лютик\"".into(),
+                    "ИНН 7701234567".into(),
+                    "СЧ 40702810400000000001".into(),
+                    "Оплата по счету 1001 от 01.03.2023".into(),
+                ][..]
+            )
+        );
+        assert_eq!(
+            statement_lines[1].debit_credit_mark(),
+            &StatementLineMark::Credit
+        );
+        assert_eq!(
+            statement_lines[1].transaction_type_identification_code(),
+            &TransactionTypeIdentificationCode::NonSwift(TransactionType::Miscellaneous)
+        );
+    }
+
+    #[test]
+    fn test_read_dos_client_bank_exchange() {
+        let text = CLIENT_BANK_EXCHANGE.replace("Кодировка=Windows", "Кодировка=DOS");
+        let bytes = TextEncoding::Ibm866.encode(&text).unwrap();
+        let messages = read_client_bank_exchange(&bytes).unwrap();
+        assert_eq!(messages[0].statement_lines().len(), 2);
+    }
+
+    #[test]
+    fn test_declared_client_bank_exchange_encoding() {
+        let bytes = TextEncoding::Windows1251
+            .encode(CLIENT_BANK_EXCHANGE)
+            .unwrap();
+        assert_eq!(declared_encoding(&bytes), Some(TextEncoding::Windows1251));
+
+        let text = CLIENT_BANK_EXCHANGE.replace("Кодировка=Windows", "Кодировка=DOS");
+        let bytes = TextEncoding::Ibm866.encode(&text).unwrap();
+        assert_eq!(declared_encoding(&bytes), Some(TextEncoding::Ibm866));
+
+        let text = "1CClientBankExchange\r\nКодировка=DOS\r\nОтправитель=Тест\r\n";
+        let bytes = TextEncoding::Ibm866.encode(text).unwrap();
+        assert_eq!(declared_encoding(&bytes), Some(TextEncoding::Ibm866));
+        assert_eq!(decode_client_bank_exchange(&bytes).unwrap(), text);
+
+        let text = CLIENT_BANK_EXCHANGE.replace("Кодировка=Windows\n", "");
+        let bytes = TextEncoding::Ibm866.encode(&text).unwrap();
+        assert_eq!(declared_encoding(&bytes), None);
+        let text = CLIENT_BANK_EXCHANGE.replace("Кодировка=Windows", "Кодировка=UTF-8");
+        let bytes = TextEncoding::Windows1251.encode(&text).unwrap();
+        assert_eq!(declared_encoding(&bytes), None);
+    }
+
+    #[test]
+    fn test_read_invalid_client_bank_exchange() {
+        let result = read_client_bank_exchange(b":20:STMT");
+        assert!(matches!(
+            result,
+            Err(ClientBankExchangeError::InvalidHeader)
+        ));
+
+        let text = CLIENT_BANK_EXCHANGE.replace("КонецФайла\n", "");
+        let result = read_client_bank_exchange(text.as_bytes());
+        assert!(matches!(
+            result,
+            Err(ClientBankExchangeError::UnexpectedEnd)
+        ));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "File ends without КонецФайла"
+        );
+
+        let text = CLIENT_BANK_EXCHANGE.replace("Сумма=50.00", "Сумма=пятьдесят");
+        let result = read_client_bank_exchange(text.as_bytes());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Invalid value пятьдесят for key Сумма"
+        );
+
+        let text = CLIENT_BANK_EXCHANGE.replace("НачальныйОстаток=1000.00\n", "");
+        let result = read_client_bank_exchange(text.as_bytes());
+        assert!(matches!(
+            result,
+            Err(ClientBankExchangeError::MissingKey(OPENING_BALANCE))
+        ));
+    }
+}
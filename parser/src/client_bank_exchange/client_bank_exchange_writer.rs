@@ -0,0 +1,261 @@
+use crate::client_bank_exchange::*;
+use crate::money::*;
+use crate::text_encoding::*;
+use chrono::{NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
+use std::fmt::{Display, Write};
+
+const FORMAT_VERSION_NUMBER: &str = "1.03";
+const LINE_END: &str = "\r\n";
+
+pub fn write_client_bank_exchange(
+    messages: &[Mt940CustomerStatementMessage<'_>],
+    sender: &str,
+    created: NaiveDateTime,
+) -> Result<Vec<u8>, ClientBankExchangeError> {
+    let text = client_bank_exchange_text(messages, sender, created);
+    TextEncoding::Windows1251
+        .encode(&text)
+        .map(|bytes| bytes.into_owned())
+        .map_err(|_| ClientBankExchangeError::UnmappableCharacter)
+}
+
+pub fn client_bank_exchange_text(
+    messages: &[Mt940CustomerStatementMessage<'_>],
+    sender: &str,
+    created: NaiveDateTime,
+) -> String {
+    let mut text = String::new();
+    marker(&mut text, FILE_HEADER);
+    let start_date = messages
+        .iter()
+        .map(|message| message.opening_balance().date().value())
+        .min();
+    let end_date = messages
+        .iter()
+        .map(|message| message.closing_balance().date().value())
+        .max();
+
+    key_value(&mut text, FORMAT_VERSION, &FORMAT_VERSION_NUMBER);
+    key_value(&mut text, ENCODING, &WINDOWS_ENCODING);
+    key_value(&mut text, SENDER, &sender);
+    key_value(&mut text, RECIPIENT, &"");
+    key_value(&mut text, CREATION_DATE, &created.format(DATE_FORMAT));
+    key_value(&mut text, CREATION_TIME, &created.format(TIME_FORMAT));
+    key_value(&mut text, START_DATE, &optional_date(start_date));
+    key_value(&mut text, END_DATE, &optional_date(end_date));
+    let mut accounts = messages.iter().map(account).collect::<Vec<_>>();
+    accounts.sort();
+    accounts.dedup();
+    for account in accounts {
+        key_value(&mut text, ACCOUNT, &account);
+    }
+
+    for message in messages {
+        write_account_section(&mut text, message);
+    }
+    for message in messages {
+        for statement_line in message.statement_lines() {
+            write_document(&mut text, message, statement_line);
+        }
+    }
+
+    marker(&mut text, FILE_END);
+    text
+}
+
+fn write_account_section(text: &mut String, message: &Mt940CustomerStatementMessage<'_>) {
+    let currency_code = message.opening_balance().currency_code();
+    let amounts = message
+        .statement_lines()
+        .iter()
+        .map(|statement_line| Money::from_statement_line(statement_line, currency_code).value());
+    let (credits, debits) = amounts.fold(
+        (Decimal::ZERO, Decimal::ZERO),
+        |(credits, debits), value| {
+            if value.is_sign_negative() {
+                (credits, debits - value)
+            } else {
+                (credits + value, debits)
+            }
+        },
+    );
+
+    marker(text, ACCOUNT_SECTION_START);
+    key_value(
+        text,
+        START_DATE,
+        &message.opening_balance().date().value().format(DATE_FORMAT),
+    );
+    key_value(
+        text,
+        END_DATE,
+        &message.closing_balance().date().value().format(DATE_FORMAT),
+    );
+    key_value(text, ACCOUNT, &account(message));
+    key_value(
+        text,
+        OPENING_BALANCE,
+        &amount(Money::from(message.opening_balance()).value()),
+    );
+    key_value(text, TOTAL_CREDITS, &amount(credits));
+    key_value(text, TOTAL_DEBITS, &amount(debits));
+    key_value(
+        text,
+        CLOSING_BALANCE,
+        &amount(Money::from(message.closing_balance()).value()),
+    );
+    marker(text, ACCOUNT_SECTION_END);
+}
+
+fn write_document(
+    text: &mut String,
+    message: &Mt940CustomerStatementMessage<'_>,
+    statement_line: &StatementLine<'_>,
+) {
+    let currency_code = message.opening_balance().currency_code();
+    let value = Money::from_statement_line(statement_line, currency_code).value();
+    let account = account(message);
+    let value_date = statement_line.value_date().value().format(DATE_FORMAT);
+    let purpose = statement_line
+        .information_to_account_owner()
+        .map(|information| information.join(" "))
+        .or_else(|| statement_line.supplementary_details().map(str::to_string))
+        .unwrap_or_default();
+
+    key_value(
+        text,
+        DOCUMENT_SECTION_START,
+        &document_type(statement_line.transaction_type_identification_code()),
+    );
+    key_value(text, DOCUMENT_NUMBER, &statement_line.account_owner_ref());
+    key_value(
+        text,
+        DOCUMENT_DATE,
+        &statement_line.booking_date().value().format(DATE_FORMAT),
+    );
+    key_value(text, DOCUMENT_AMOUNT, &amount(value.abs()));
+    if value.is_sign_negative() {
+        key_value(text, PAYER_ACCOUNT, &account);
+        key_value(text, DEBIT_DATE, &value_date);
+        key_value(text, RECIPIENT_ACCOUNT, &"");
+    } else {
+        key_value(text, PAYER_ACCOUNT, &"");
+        key_value(text, RECIPIENT_ACCOUNT, &account);
+        key_value(text, CREDIT_DATE, &value_date);
+    }
+    key_value(text, PAYMENT_PURPOSE, &purpose);
+    marker(text, DOCUMENT_SECTION_END);
+}
+
+fn key_value(text: &mut String, key: &str, value: &dyn Display) {
+    let _ = write!(text, "{}={}{}", key, value, LINE_END);
+}
+
+fn marker(text: &mut String, key: &str) {
+    text.push_str(key);
+    text.push_str(LINE_END);
+}
+
+fn account(message: &Mt940CustomerStatementMessage<'_>) -> String {
+    match message.account_identification().account_number() {
+        AccountNumber::RussianAccount(_, account) => account.as_str().to_string(),
+        _ => message.account_identification().as_str().to_string(),
+    }
+}
+
+fn amount(value: Decimal) -> String {
+    format!("{:.2}", value)
+}
+
+fn optional_date(date: Option<NaiveDate>) -> String {
+    date.map(|date| date.format(DATE_FORMAT).to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSAGE: &str = ":20:STMT
+:25:044525225/40702810938000012345
+:28C:1
+:60F:C230305RUB1000,00
+:61:230306D200,5NTRF1001
+:86:Оплата по счету 1001
+:61:230306C50,NMSCNONREF
+:62F:C230306RUB849,50
+-";
+
+    #[test]
+    fn test_write_client_bank_exchange() {
+        let message = Mt940CustomerStatementMessage::try_from(MESSAGE).unwrap();
+        let created = NaiveDate::from_ymd_opt(2023, 3, 7)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        let bytes = write_client_bank_exchange(&[message], "Банк", created).unwrap();
+        let text = TextEncoding::Windows1251.decode(&bytes).unwrap();
+        assert_eq!(
+            text,
+            "1CClientBankExchange\r
+ВерсияФормата=1.03\r
+Кодировка=Windows\r
+Отправитель=Банк\r
+Получатель=\r
+ДатаСоздания=07.03.2023\r
+ВремяСоздания=12:00:00\r
+ДатаНачала=05.03.2023\r
+ДатаКонца=06.03.2023\r
+РасчСчет=40702810938000012345\r
+СекцияРасчСчет\r
+ДатаНачала=05.03.2023\r
+ДатаКонца=06.03.2023\r
+РасчСчет=40702810938000012345\r
+НачальныйОстаток=1000.00\r
+ВсегоПоступило=50.00\r
+ВсегоСписано=200.50\r
+КонечныйОстаток=849.50\r
+КонецРасчСчет\r
+СекцияДокумент=Платежное поручение\r
+Номер=1001\r
+Дата=06.03.2023\r
+Сумма=200.50\r
+ПлательщикСчет=40702810938000012345\r
+ДатаСписано=06.03.2023\r
+ПолучательСчет=\r
+НазначениеПлатежа=Оплата по счету 1001\r
+КонецДокумента\r
+СекцияДокумент=Банковский ордер\r
+Номер=NONREF\r
+Дата=06.03.2023\r
+Сумма=50.00\r
+ПлательщикСчет=\r
+ПолучательСчет=40702810938000012345\r
+ДатаПоступило=06.03.2023\r
+НазначениеПлатежа=\r
+КонецДокумента\r
+КонецФайла\r
+"
+        );
+
+        let messages = read_client_bank_exchange(&bytes).unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].statement_lines().len(), 2);
+        assert_eq!(
+            messages[0].closing_balance(),
+            &Balance::try_from("C230306RUB849,50").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_write_unmappable_character() {
+        let message = Mt940CustomerStatementMessage::try_from(MESSAGE).unwrap();
+        let created = NaiveDateTime::default();
+        let result = write_client_bank_exchange(&[message], "銀行", created);
+        assert!(matches!(
+            result,
+            Err(ClientBankExchangeError::UnmappableCharacter)
+        ));
+    }
+}
@@ -0,0 +1,722 @@
+use crate::dialect::*;
+use crate::mt_940_customer_statement_message::*;
+use std::fmt::{Display, Formatter, Write};
+use std::ops::Range;
+
+const FIELD_ORDER: [&str; 9] = ["20", "21", "25", "28", "60", "61", "62", "64", "65"];
+const TRAILING_FIELDS: [&str; 3] = ["64", "65", "86"];
+const INFORMATION_TAG: &str = "86";
+const BALANCE_FORMAT: &str = "1!a6!n3!a15d";
+const DATE_HINT: &str = "Write dates as YYMMDD, e.g. 230305";
+const AMOUNT_HINT: &str = "Write amounts with a comma as the decimal separator, e.g. 1000,00";
+const RED: &str = "\x1b[1;31m";
+const BLUE: &str = "\x1b[1;34m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiagnosticStyle {
+    Plain,
+    Colored,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Diagnostic<'a> {
+    message: String,
+    tag: Option<String>,
+    snippet: Option<Snippet<'a>>,
+    hint: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Snippet<'a> {
+    line_number: usize,
+    line: &'a str,
+    columns: Range<usize>,
+}
+
+impl<'a> Diagnostic<'a> {
+    pub fn new(source: &'a str, error: &Mt940CustomerStatementMessageParseError) -> Self {
+        Self::with_dialect(source, error, &Standard)
+    }
+
+    pub fn with_dialect(
+        source: &'a str,
+        error: &Mt940CustomerStatementMessageParseError,
+        dialect: &dyn Dialect,
+    ) -> Self {
+        let fields = fields(source, dialect)
+            .map(Iterator::collect::<Vec<_>>)
+            .unwrap_or_default();
+        Self::from_fields(source, &fields, error)
+    }
+
+    pub fn for_recovered_error(source: &'a str, error: &RecoveredError<'_>) -> Self {
+        Self::from_fields(source, &[(error.tag(), error.value())], error.error())
+    }
+
+    fn from_fields(
+        source: &'a str,
+        fields: &[Field<'_>],
+        error: &Mt940CustomerStatementMessageParseError,
+    ) -> Self {
+        let (tag, snippet) = match locate(source, fields, error) {
+            Some((tag, span)) => (tag, Some(Snippet::new(source, span))),
+            None => (None, None),
+        };
+        let tag = match error {
+            Mt940CustomerStatementMessageParseError::MissingField(tag) => Some(tag.to_string()),
+            _ => tag.or_else(|| error_tag(error).map(str::to_string)),
+        };
+
+        Self {
+            message: error.to_string(),
+            tag,
+            snippet,
+            hint: hint(error),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn tag(&self) -> Option<&str> {
+        self.tag.as_deref()
+    }
+
+    pub fn field_name(&self) -> Option<&'static str> {
+        self.tag
+            .as_deref()
+            .and_then(field_description)
+            .map(|(name, _)| name)
+    }
+
+    pub fn expected_format(&self) -> Option<&'static str> {
+        self.tag
+            .as_deref()
+            .and_then(field_description)
+            .map(|(_, format)| format)
+    }
+
+    pub fn line_number(&self) -> Option<usize> {
+        self.snippet.as_ref().map(|snippet| snippet.line_number)
+    }
+
+    pub fn column(&self) -> Option<usize> {
+        self.snippet
+            .as_ref()
+            .map(|snippet| snippet.columns.start + 1)
+    }
+
+    pub fn hint(&self) -> Option<&str> {
+        self.hint.as_deref()
+    }
+
+    pub fn render(&self, style: DiagnosticStyle) -> String {
+        let mut text = String::new();
+        let _ = self.write(&mut text, style);
+        text
+    }
+
+    fn write(&self, f: &mut impl Write, style: DiagnosticStyle) -> std::fmt::Result {
+        let (red, blue, bold, reset) = match style {
+            DiagnosticStyle::Plain => ("", "", "", ""),
+            DiagnosticStyle::Colored => (RED, BLUE, BOLD, RESET),
+        };
+        writeln!(
+            f,
+            "{}error{}{}: {}{}",
+            red, reset, bold, self.message, reset
+        )?;
+
+        let gutter = self
+            .snippet
+            .as_ref()
+            .map_or(1, |snippet| snippet.line_number.to_string().len());
+        let padding = " ".repeat(gutter);
+        if let Some(snippet) = &self.snippet {
+            writeln!(
+                f,
+                "{}{}-->{} line {}, column {}",
+                padding,
+                blue,
+                reset,
+                snippet.line_number,
+                snippet.columns.start + 1
+            )?;
+            writeln!(f, "{} {}|{}", padding, blue, reset)?;
+            writeln!(
+                f,
+                "{}{} |{} {}",
+                blue, snippet.line_number, reset, snippet.line
+            )?;
+            writeln!(
+                f,
+                "{} {}|{} {}{}{}{}",
+                padding,
+                blue,
+                reset,
+                " ".repeat(snippet.columns.start),
+                red,
+                "^".repeat(snippet.columns.len().max(1)),
+                reset
+            )?;
+        }
+
+        let mut note = |label: &str, value: &str| {
+            writeln!(
+                f,
+                "{} {}={} {}{}{}: {}",
+                padding, blue, reset, bold, label, reset, value
+            )
+        };
+        if let Some(tag) = &self.tag {
+            match self.field_name() {
+                Some(name) => note("field", &format!(":{}: {}", tag, name))?,
+                None => note("field", &format!(":{}:", tag))?,
+            }
+        }
+        if let Some(format) = self.expected_format() {
+            note("expected", format)?;
+        }
+        if let Some(hint) = &self.hint {
+            note("hint", hint)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Snippet<'a> {
+    fn new(source: &'a str, span: Range<usize>) -> Self {
+        let start = source.floor_char_boundary(span.start);
+        let line_start = source[..start].rfind('\n').map_or(0, |index| index + 1);
+        let line_end = source[start..]
+            .find('\n')
+            .map_or(source.len(), |index| start + index);
+        let line = source[line_start..line_end].trim_end_matches('\r');
+        let end = source
+            .floor_char_boundary(span.end)
+            .clamp(start, (line_start + line.len()).max(start));
+        let column = source[line_start..start].chars().count();
+
+        Self {
+            line_number: source[..line_start].matches('\n').count() + 1,
+            line,
+            columns: column..column + source[start..end].chars().count(),
+        }
+    }
+}
+
+impl Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.write(f, DiagnosticStyle::Plain)
+    }
+}
+
+type Field<'a> = (&'a str, &'a str);
+
+fn locate(
+    source: &str,
+    fields: &[Field<'_>],
+    error: &Mt940CustomerStatementMessageParseError,
+) -> Option<(Option<String>, Range<usize>)> {
+    match error {
+        Mt940CustomerStatementMessageParseError::Empty
+        | Mt940CustomerStatementMessageParseError::InvalidEncoding(_) => None,
+        Mt940CustomerStatementMessageParseError::MissingField(tag) => {
+            let missing = rank(tag)?;
+            let next = fields.iter().find(|(tag, _)| {
+                *tag != INFORMATION_TAG && rank(tag).is_none_or(|rank| rank > missing)
+            });
+            match next {
+                Some(next) => tag_span(source, next).map(|span| (None, span)),
+                None => {
+                    let (_, value) = fields.last()?;
+                    let end = offset(source, value)? + value.len();
+                    Some((None, end..end))
+                }
+            }
+        }
+        Mt940CustomerStatementMessageParseError::UnexpectedField(unexpected) => {
+            match unexpected_field(fields, unexpected) {
+                Some(field) => {
+                    tag_span(source, field).map(|span| (Some(field.0.to_string()), span))
+                }
+                None => line_span(source, unexpected).map(|span| (None, span)),
+            }
+        }
+        Mt940CustomerStatementMessageParseError::TransactionReferenceNumber(_) => {
+            field_span(source, fields, |tag, _| tag == "20", trimmed)
+        }
+        Mt940CustomerStatementMessageParseError::RelatedReference(_) => {
+            field_span(source, fields, |tag, _| tag == "21", trimmed)
+        }
+        Mt940CustomerStatementMessageParseError::AccountIdentification(_) => {
+            field_span(source, fields, |tag, _| tag == "25", trimmed)
+        }
+        Mt940CustomerStatementMessageParseError::StatementSequenceNumber(err) => field_span(
+            source,
+            fields,
+            |tag, _| tag == "28C",
+            |value| statement_sequence_number_span(value, err),
+        ),
+        Mt940CustomerStatementMessageParseError::OpeningBalance(err) => field_span(
+            source,
+            fields,
+            |tag, _| tag.starts_with("60"),
+            |value| balance_error_span(value, err),
+        ),
+        Mt940CustomerStatementMessageParseError::StatementLine(err) => field_span(
+            source,
+            fields,
+            |tag, value| tag == "61" && StatementLine::try_from(value).is_err(),
+            |value| statement_line_error_span(value, err),
+        ),
+        Mt940CustomerStatementMessageParseError::ClosingBalance(err) => field_span(
+            source,
+            fields,
+            |tag, _| tag.starts_with("62"),
+            |value| balance_error_span(value, err),
+        ),
+        Mt940CustomerStatementMessageParseError::ClosingAvailableBalance(err) => field_span(
+            source,
+            fields,
+            |tag, _| tag == "64",
+            |value| balance_error_span(value, err),
+        ),
+        Mt940CustomerStatementMessageParseError::ForwardAvailableBalance(err) => field_span(
+            source,
+            fields,
+            |tag, value| tag == "65" && Balance::try_from(value).is_err(),
+            |value| balance_error_span(value, err),
+        ),
+        Mt940CustomerStatementMessageParseError::ForwardAvailableBalanceCurrencyMismatch(
+            expected,
+            _,
+        ) => field_span(
+            source,
+            fields,
+            |tag, value| {
+                tag == "65"
+                    && Balance::try_from(value)
+                        .is_ok_and(|balance| balance.currency_code().as_str() != expected)
+            },
+            |value| {
+                balance_error_span(
+                    value,
+                    &BalanceParseError::from(CurrencyCodeParseError::InvalidFormat),
+                )
+            },
+        ),
+        Mt940CustomerStatementMessageParseError::UnorderedForwardAvailableBalance(_) => {
+            let mut previous = None;
+            field_span(
+                source,
+                fields,
+                |tag, value| {
+                    let date = match Balance::try_from(value) {
+                        Ok(balance) if tag == "65" => balance.date().value(),
+                        _ => return false,
+                    };
+                    previous
+                        .replace(date)
+                        .is_some_and(|previous| previous >= date)
+                },
+                |value| {
+                    balance_error_span(
+                        value,
+                        &BalanceParseError::from(DateParseError::InvalidValue),
+                    )
+                },
+            )
+        }
+    }
+}
+
+fn field_span(
+    source: &str,
+    fields: &[Field<'_>],
+    mut predicate: impl FnMut(&str, &str) -> bool,
+    span: impl Fn(&str) -> Range<usize>,
+) -> Option<(Option<String>, Range<usize>)> {
+    let (tag, value) = fields.iter().find(|(tag, value)| predicate(tag, value))?;
+    let start = offset(source, value)?;
+    let span = span(value);
+    Some((Some(tag.to_string()), start + span.start..start + span.end))
+}
+
+fn unexpected_field<'f, 'a>(fields: &'f [Field<'a>], tag: &str) -> Option<&'f Field<'a>> {
+    let closing_balance = fields
+        .iter()
+        .position(|(tag, _)| tag.starts_with("62"))
+        .unwrap_or(fields.len());
+    let mut rest = fields.iter().skip(closing_balance + 1).peekable();
+    for trailing in TRAILING_FIELDS {
+        rest.next_if(|(tag, _)| *tag == trailing);
+    }
+    rest.next()
+        .filter(|(next, _)| *next == tag)
+        .or_else(|| fields.iter().rfind(|(next, _)| *next == tag))
+}
+
+fn rank(tag: &str) -> Option<usize> {
+    FIELD_ORDER
+        .iter()
+        .position(|field| tag.get(..2) == Some(field))
+}
+
+fn offset(source: &str, value: &str) -> Option<usize> {
+    let offset = (value.as_ptr() as usize).checked_sub(source.as_ptr() as usize)?;
+    (offset + value.len() <= source.len()).then_some(offset)
+}
+
+fn tag_span(source: &str, (tag, value): &Field<'_>) -> Option<Range<usize>> {
+    let start = offset(source, value)?.checked_sub(tag.len() + 2)?;
+    Some(start..start + tag.len() + 2)
+}
+
+fn line_span(source: &str, content: &str) -> Option<Range<usize>> {
+    let mut offset = 0;
+    for line in source.split_inclusive('\n') {
+        if line.trim() == content {
+            let start = offset + line.len() - line.trim_start().len();
+            return Some(start..start + content.len());
+        }
+        offset += line.len();
+    }
+    None
+}
+
+fn trimmed(value: &str) -> Range<usize> {
+    let start = value.len() - value.trim_start().len();
+    start..start + value.trim().len()
+}
+
+fn statement_sequence_number_span(
+    value: &str,
+    error: &StatementSequenceNumberParseError,
+) -> Range<usize> {
+    let span = trimmed(value);
+    let separator = value[span.clone()]
+        .find('/')
+        .map(|index| span.start + index);
+    match (error, separator) {
+        (
+            StatementSequenceNumberParseError::StatementNumberTooLong
+            | StatementSequenceNumberParseError::InvalidStatementNumberFormat,
+            Some(separator),
+        ) => span.start..separator,
+        (
+            StatementSequenceNumberParseError::SequenceNumberTooLong
+            | StatementSequenceNumberParseError::InvalidSequenceNumberFormat,
+            Some(separator),
+        ) => separator + 1..span.end,
+        _ => span,
+    }
+}
+
+fn error_tag(error: &Mt940CustomerStatementMessageParseError) -> Option<&'static str> {
+    match error {
+        Mt940CustomerStatementMessageParseError::Empty
+        | Mt940CustomerStatementMessageParseError::InvalidEncoding(_)
+        | Mt940CustomerStatementMessageParseError::UnexpectedField(_) => None,
+        Mt940CustomerStatementMessageParseError::MissingField(tag) => Some(tag),
+        Mt940CustomerStatementMessageParseError::TransactionReferenceNumber(_) => Some("20"),
+        Mt940CustomerStatementMessageParseError::RelatedReference(_) => Some("21"),
+        Mt940CustomerStatementMessageParseError::AccountIdentification(_) => Some("25"),
+        Mt940CustomerStatementMessageParseError::StatementSequenceNumber(_) => Some("28C"),
+        Mt940CustomerStatementMessageParseError::OpeningBalance(_) => Some("60a"),
+        Mt940CustomerStatementMessageParseError::StatementLine(_) => Some("61"),
+        Mt940CustomerStatementMessageParseError::ClosingBalance(_) => Some("62a"),
+        Mt940CustomerStatementMessageParseError::ClosingAvailableBalance(_) => Some("64"),
+        Mt940CustomerStatementMessageParseError::ForwardAvailableBalance(_)
+        | Mt940CustomerStatementMessageParseError::ForwardAvailableBalanceCurrencyMismatch(..)
+        | Mt940CustomerStatementMessageParseError::UnorderedForwardAvailableBalance(_) => {
+            Some("65")
+        }
+    }
+}
+
+fn field_description(tag: &str) -> Option<(&'static str, &'static str)> {
+    match tag.get(..2)? {
+        "20" => Some(("transaction reference number", "16x")),
+        "21" => Some(("related reference", "16x")),
+        "25" => Some(("account identification", "35x")),
+        "28" => Some(("statement number/sequence number", "5n[/5n]")),
+        "60" => Some(("opening balance", BALANCE_FORMAT)),
+        "61" => Some(("statement line", "6!n[4!n]2a[1!a]15d1!a3!c16x[//16x][34x]")),
+        "62" => Some(("closing balance", BALANCE_FORMAT)),
+        "64" => Some(("closing available balance", BALANCE_FORMAT)),
+        "65" => Some(("forward available balance", BALANCE_FORMAT)),
+        "86" => Some(("information to account owner", "6*65x")),
+        _ => None,
+    }
+}
+
+fn hint(error: &Mt940CustomerStatementMessageParseError) -> Option<String> {
+    let hint = match error {
+        Mt940CustomerStatementMessageParseError::Empty => {
+            "Add at least the :20:, :25:, :60a: and :62a: fields"
+        }
+        Mt940CustomerStatementMessageParseError::InvalidEncoding(_) => {
+            "Pass the text encoding explicitly instead of relying on detection"
+        }
+        Mt940CustomerStatementMessageParseError::MissingField(tag) => {
+            return Some(format!("Add the mandatory :{}: field here", tag));
+        }
+        Mt940CustomerStatementMessageParseError::UnexpectedField(_) => {
+            "Remove the field or move it to its expected position"
+        }
+        Mt940CustomerStatementMessageParseError::TransactionReferenceNumber(err) => match err {
+            TransactionReferenceNumberParseError::TooLong(max_length) => {
+                return Some(too_long_hint(*max_length));
+            }
+            _ => "Use a reference that does not start or end with '/' and has no '//'",
+        },
+        Mt940CustomerStatementMessageParseError::RelatedReference(err) => match err {
+            RelatedReferenceParseError::TooLong(max_length) => {
+                return Some(too_long_hint(*max_length));
+            }
+            _ => "Use a reference that does not start or end with '/' and has no '//', or NONREF",
+        },
+        Mt940CustomerStatementMessageParseError::AccountIdentification(err) => match err {
+            AccountIdentificationParseError::InvalidIban(_) => {
+                "Check the country code and check digits of the IBAN"
+            }
+            AccountIdentificationParseError::InvalidBic(_) => {
+                "Write the account as BIC/account number, e.g. ABNANL2A/NL91ABNA0417164300"
+            }
+            AccountIdentificationParseError::InvalidRussianAccount(_) => {
+                "Check the BIK and the control key of the account number"
+            }
+            _ => "Use an account number of up to 35 characters",
+        },
+        Mt940CustomerStatementMessageParseError::StatementSequenceNumber(_) => {
+            "Write the statement number and the optional sequence number as digits, e.g. 45/1"
+        }
+        Mt940CustomerStatementMessageParseError::OpeningBalance(err)
+        | Mt940CustomerStatementMessageParseError::ClosingBalance(err)
+        | Mt940CustomerStatementMessageParseError::ClosingAvailableBalance(err)
+        | Mt940CustomerStatementMessageParseError::ForwardAvailableBalance(err) => {
+            return Some(balance_hint(err));
+        }
+        Mt940CustomerStatementMessageParseError::StatementLine(err) => {
+            return Some(statement_line_hint(err));
+        }
+        Mt940CustomerStatementMessageParseError::ForwardAvailableBalanceCurrencyMismatch(
+            expected,
+            _,
+        ) => {
+            return Some(format!(
+                "Use the statement currency {} for every forward available balance",
+                expected
+            ));
+        }
+        Mt940CustomerStatementMessageParseError::UnorderedForwardAvailableBalance(_) => {
+            "List forward available balances in ascending date order"
+        }
+    };
+    Some(hint.to_string())
+}
+
+fn balance_hint(error: &BalanceParseError) -> String {
+    let hint = match error {
+        BalanceParseError::TooLong(max_length) => return too_long_hint(*max_length),
+        BalanceParseError::InvalidFormat(Some(err)) if err.is::<CreditDebitMarkParseError>() => {
+            "Use C for credit or D for debit"
+        }
+        BalanceParseError::InvalidFormat(Some(err)) if err.is::<DateParseError>() => DATE_HINT,
+        BalanceParseError::InvalidFormat(Some(err)) if err.is::<CurrencyCodeParseError>() => {
+            "Use a three-letter ISO 4217 currency code, e.g. EUR"
+        }
+        BalanceParseError::InvalidFormat(Some(err)) if err.is::<AmountParseError>() => AMOUNT_HINT,
+        _ => "Write the mark, date, currency and amount without separators, e.g. C230305EUR1000,00",
+    };
+    hint.to_string()
+}
+
+fn statement_line_hint(error: &StatementLineParseError) -> String {
+    let hint = match error {
+        StatementLineParseError::Empty => {
+            "Write at least the value date, mark, amount, transaction type and reference, \
+             e.g. 230306C100,00NTRFNONREF"
+        }
+        StatementLineParseError::InvalidFormat => "Move additional lines to the :86: field",
+        StatementLineParseError::InvalidValueDate(_) => DATE_HINT,
+        StatementLineParseError::InvalidEntryDate(_) => "Write the entry date as MMDD, e.g. 0306",
+        StatementLineParseError::InvalidMark(_) => "Use C, D, RC or RD",
+        StatementLineParseError::InvalidAmount(_) => AMOUNT_HINT,
+        StatementLineParseError::InvalidTransactionTypeIdentificationCode(_) => {
+            "Use S, N or F followed by a three-character code, e.g. NTRF"
+        }
+        StatementLineParseError::InvalidAccountOwnerReference => {
+            "Use NONREF when there is no reference for the account owner"
+        }
+        StatementLineParseError::BankReferenceTooLong => {
+            "Shorten the reference after the // separator"
+        }
+        StatementLineParseError::SupplementaryDetailsTooLong => {
+            "Move longer details to the :86: field"
+        }
+    };
+    hint.to_string()
+}
+
+fn too_long_hint(max_length: usize) -> String {
+    format!("Shorten the value to at most {} characters", max_length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSAGE: &str = ":20:STMT
+:25:DK5000400440116243
+:28C:45/1
+:60F:C230305DKK1000X00
+:61:230306C50,NCHKNONREF
+:62F:C230306DKK849,50
+-";
+
+    fn parse_diagnostic(source: &str) -> Diagnostic<'_> {
+        let error = Mt940CustomerStatementMessage::try_from(source).unwrap_err();
+        Diagnostic::new(source, &error)
+    }
+
+    #[test]
+    fn test_balance_diagnostic() {
+        let diagnostic = parse_diagnostic(MESSAGE);
+        assert_eq!(diagnostic.tag(), Some("60F"));
+        assert_eq!(diagnostic.field_name(), Some("opening balance"));
+        assert_eq!(diagnostic.expected_format(), Some("1!a6!n3!a15d"));
+        assert_eq!(diagnostic.line_number(), Some(4));
+        assert_eq!(diagnostic.column(), Some(16));
+        assert_eq!(diagnostic.hint(), Some(AMOUNT_HINT));
+        assert_eq!(
+            diagnostic.to_string(),
+            "error: :60a: Opening balance has invalid format: Invalid amount format
+ --> line 4, column 16
+  |
+4 | :60F:C230305DKK1000X00
+  |                ^^^^^^^
+  = field: :60F: opening balance
+  = expected: 1!a6!n3!a15d
+  = hint: Write amounts with a comma as the decimal separator, e.g. 1000,00
+"
+        );
+    }
+
+    #[test]
+    fn test_colored_diagnostic() {
+        let diagnostic = parse_diagnostic(MESSAGE);
+        let colored = diagnostic.render(DiagnosticStyle::Colored);
+        assert!(colored.starts_with("\x1b[1;31merror\x1b[0m\x1b[1m: :60a:"));
+        assert!(colored.contains("\x1b[1;31m^^^^^^^\x1b[0m"));
+        assert_eq!(
+            diagnostic.render(DiagnosticStyle::Plain),
+            diagnostic.to_string()
+        );
+        assert!(!diagnostic.to_string().contains('\x1b'));
+    }
+
+    #[test]
+    fn test_statement_line_diagnostic() {
+        let source = MESSAGE.replace("1000X00", "1000,00").replace(
+            ":61:230306C50,NCHKNONREF",
+            ":61:230306C50,NCHKREF\n:61:2303060306X50,NCHKREF",
+        );
+        let diagnostic = parse_diagnostic(&source);
+        assert_eq!(diagnostic.tag(), Some("61"));
+        assert_eq!(diagnostic.line_number(), Some(6));
+        assert_eq!(diagnostic.column(), Some(15));
+        assert_eq!(diagnostic.hint(), Some("Use C, D, RC or RD"));
+        assert!(diagnostic.to_string().contains(
+            "6 | :61:2303060306X50,NCHKREF
+  |               ^
+"
+        ));
+        assert!(
+            diagnostic
+                .to_string()
+                .contains("= expected: 6!n[4!n]2a[1!a]15d1!a3!c16x[//16x][34x]")
+        );
+
+        let source = source.replace("2303060306X50,NCHKREF", "230306C50,NCHKREF\nTOO\nMANY");
+        let diagnostic = parse_diagnostic(&source);
+        assert_eq!(diagnostic.line_number(), Some(8));
+        assert_eq!(diagnostic.column(), Some(1));
+    }
+
+    #[test]
+    fn test_statement_sequence_number_diagnostic() {
+        let source = MESSAGE.replace("45/1", "45/X");
+        let diagnostic = parse_diagnostic(&source);
+        assert_eq!(diagnostic.tag(), Some("28C"));
+        assert_eq!(diagnostic.line_number(), Some(3));
+        assert_eq!(diagnostic.column(), Some(9));
+        assert_eq!(diagnostic.expected_format(), Some("5n[/5n]"));
+    }
+
+    #[test]
+    fn test_missing_field_diagnostic() {
+        let diagnostic = parse_diagnostic(":20:STMT\n:28C:1\n-");
+        assert_eq!(diagnostic.tag(), Some("25"));
+        assert_eq!(diagnostic.expected_format(), Some("35x"));
+        assert_eq!(diagnostic.line_number(), Some(2));
+        assert_eq!(diagnostic.column(), Some(1));
+        assert!(diagnostic.to_string().contains("2 | :28C:1\n  | ^^^^^\n"));
+        assert_eq!(diagnostic.hint(), Some("Add the mandatory :25: field here"));
+    }
+
+    #[test]
+    fn test_unexpected_field_diagnostic() {
+        let source = MESSAGE
+            .replace("1000X00", "1000,00")
+            .replace("-", ":64:C230306DKK849,50\n:64:C230306DKK849,50\n-");
+        let diagnostic = parse_diagnostic(&source);
+        assert_eq!(diagnostic.tag(), Some("64"));
+        assert_eq!(diagnostic.line_number(), Some(8));
+
+        let diagnostic = parse_diagnostic("GARBAGE\n:20:STMT\n-");
+        assert_eq!(diagnostic.tag(), None);
+        assert_eq!(diagnostic.expected_format(), None);
+        assert_eq!(diagnostic.line_number(), Some(1));
+        assert!(
+            diagnostic
+                .to_string()
+                .contains("1 | GARBAGE\n  | ^^^^^^^\n")
+        );
+    }
+
+    #[test]
+    fn test_recovered_error_diagnostic() {
+        let source = MESSAGE.replace("1000X00", "1000,00").replace(
+            ":61:230306C50,NCHKNONREF",
+            ":61:230306X50,NCHKNONREF\n:61:230306C50,NCHKNONREF\n:61:230306C50,XCHKNONREF",
+        );
+        let recovered =
+            Mt940CustomerStatementMessage::parse_recovering(&source, &Standard).unwrap();
+        let diagnostics = recovered
+            .errors()
+            .iter()
+            .map(|error| Diagnostic::for_recovered_error(&source, error))
+            .collect::<Vec<_>>();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].line_number(), Some(5));
+        assert_eq!(diagnostics[0].column(), Some(11));
+        assert_eq!(diagnostics[1].line_number(), Some(7));
+        assert_eq!(diagnostics[1].column(), Some(15));
+        assert_eq!(diagnostics[1].tag(), Some("61"));
+    }
+
+    #[test]
+    fn test_diagnostic_without_location() {
+        let diagnostic = parse_diagnostic("");
+        assert_eq!(diagnostic.line_number(), None);
+        assert_eq!(
+            diagnostic.to_string(),
+            "error: Message is empty\n  = hint: Add at least the :20:, :25:, :60a: and :62a: fields\n"
+        );
+    }
+}
@@ -0,0 +1,127 @@
+mod bank_profiles;
+mod information_format;
+
+pub use crate::dialect::bank_profiles::*;
+pub use crate::dialect::information_format::*;
+use crate::mt_940_customer_statement_message::*;
+
+const MESSAGE_END: &str = "-";
+const BASIC_HEADER_START: &str = "{1:";
+const APPLICATION_HEADER_START: &str = "{2:";
+const BASIC_HEADER_ADDRESS_START: usize = 3;
+const OUTPUT_HEADER_ADDRESS_START: usize = 14;
+const BIC_LENGTH: usize = 8;
+const BANK_CODE_LENGTH: usize = 4;
+
+pub trait Dialect {
+    fn name(&self) -> &'static str;
+
+    fn bank_codes(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn transaction_reference_number_max_length(&self) -> usize {
+        TRANSACTION_REFERENCE_NUMBER_MAX_LENGTH
+    }
+
+    fn related_reference_max_length(&self) -> usize {
+        RELATED_REFERENCE_MAX_LENGTH
+    }
+
+    fn balance_max_length(&self) -> usize {
+        BALANCE_MAX_LENGTH
+    }
+
+    fn information_format(&self) -> InformationFormat {
+        InformationFormat::Unstructured
+    }
+
+    fn requires_statement_sequence_number(&self) -> bool {
+        true
+    }
+
+    fn ignored_tags(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn message_terminators(&self) -> &'static [&'static str] {
+        &[MESSAGE_END]
+    }
+}
+
+pub fn dialects() -> [&'static dyn Dialect; 7] {
+    [
+        &Ing,
+        &Rabobank,
+        &AbnAmro,
+        &DeutscheBank,
+        &Sberbank,
+        &Tinkoff,
+        &Raiffeisen,
+    ]
+}
+
+pub fn detect_dialect(message: &str) -> &'static dyn Dialect {
+    let Some(bic) = sender_bic(message) else {
+        return &Standard;
+    };
+    let bank_code = &bic[..BANK_CODE_LENGTH];
+    dialects()
+        .into_iter()
+        .find(|dialect| dialect.bank_codes().contains(&bank_code))
+        .unwrap_or(&Standard)
+}
+
+pub fn sender_bic(message: &str) -> Option<&str> {
+    let application_header = header(message, APPLICATION_HEADER_START);
+    let bic = match application_header {
+        Some(header) if header.starts_with('O') => {
+            header.get(OUTPUT_HEADER_ADDRESS_START..OUTPUT_HEADER_ADDRESS_START + BIC_LENGTH)
+        }
+        _ => header(message, BASIC_HEADER_START)?
+            .get(BASIC_HEADER_ADDRESS_START..BASIC_HEADER_ADDRESS_START + BIC_LENGTH),
+    }?;
+    Bic::is_bic_like(bic).then_some(bic)
+}
+
+fn header<'a>(message: &'a str, start: &str) -> Option<&'a str> {
+    let header = &message[message.find(start)? + start.len()..];
+    header.find('}').map(|end| &header[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sender_bic() {
+        assert_eq!(
+            sender_bic(
+                "{1:F01OWNBDEFFAXXX0000000000}{2:O9401200230307DEUTDEFFAXXX00000000002303071200N}{4:"
+            ),
+            Some("DEUTDEFF")
+        );
+        assert_eq!(
+            sender_bic("{1:F01RABONL2UAXXX0000000000}{2:I940OWNBNL2AXXXXN}{4:"),
+            Some("RABONL2U")
+        );
+        assert_eq!(sender_bic(":20:REFERENCE"), None);
+    }
+
+    #[test]
+    fn test_detect_dialect() {
+        let dialect = detect_dialect(
+            "{1:F01OWNBDEFFAXXX0000000000}{2:O9401200230307DEUTDEFFAXXX00000000002303071200N}{4:",
+        );
+        assert_eq!(dialect.name(), "Deutsche Bank");
+
+        let dialect = detect_dialect("{1:F01SABRRUMMAXXX0000000000}{4:");
+        assert_eq!(dialect.name(), "Sberbank");
+
+        let dialect = detect_dialect("{1:F01BANKDEFFAXXX0000000000}{4:");
+        assert_eq!(dialect.name(), "Standard");
+
+        let dialect = detect_dialect(":20:REFERENCE");
+        assert_eq!(dialect.name(), "Standard");
+    }
+}
@@ -0,0 +1,168 @@
+use crate::dialect::*;
+
+const EXTENDED_REFERENCE_MAX_LENGTH: usize = 35;
+const EXTENDED_BALANCE_MAX_LENGTH: usize = 35;
+
+pub struct Standard;
+
+impl Dialect for Standard {
+    fn name(&self) -> &'static str {
+        "Standard"
+    }
+}
+
+pub struct Ing;
+
+impl Dialect for Ing {
+    fn name(&self) -> &'static str {
+        "ING"
+    }
+
+    fn bank_codes(&self) -> &'static [&'static str] {
+        &["INGB"]
+    }
+
+    fn information_format(&self) -> InformationFormat {
+        InformationFormat::SlashCodes
+    }
+}
+
+pub struct Rabobank;
+
+impl Dialect for Rabobank {
+    fn name(&self) -> &'static str {
+        "Rabobank"
+    }
+
+    fn bank_codes(&self) -> &'static [&'static str] {
+        &["RABO"]
+    }
+
+    fn related_reference_max_length(&self) -> usize {
+        EXTENDED_REFERENCE_MAX_LENGTH
+    }
+
+    fn information_format(&self) -> InformationFormat {
+        InformationFormat::SlashCodes
+    }
+}
+
+pub struct AbnAmro;
+
+impl Dialect for AbnAmro {
+    fn name(&self) -> &'static str {
+        "ABN AMRO"
+    }
+
+    fn bank_codes(&self) -> &'static [&'static str] {
+        &["ABNA"]
+    }
+
+    fn information_format(&self) -> InformationFormat {
+        InformationFormat::SlashCodes
+    }
+}
+
+pub struct DeutscheBank;
+
+impl Dialect for DeutscheBank {
+    fn name(&self) -> &'static str {
+        "Deutsche Bank"
+    }
+
+    fn bank_codes(&self) -> &'static [&'static str] {
+        &["DEUT"]
+    }
+
+    fn information_format(&self) -> InformationFormat {
+        InformationFormat::QuestionMarkSubfields
+    }
+
+    fn ignored_tags(&self) -> &'static [&'static str] {
+        &["13D", "90C", "90D"]
+    }
+}
+
+pub struct Sberbank;
+
+impl Dialect for Sberbank {
+    fn name(&self) -> &'static str {
+        "Sberbank"
+    }
+
+    fn bank_codes(&self) -> &'static [&'static str] {
+        &["SABR"]
+    }
+
+    fn transaction_reference_number_max_length(&self) -> usize {
+        EXTENDED_REFERENCE_MAX_LENGTH
+    }
+
+    fn related_reference_max_length(&self) -> usize {
+        EXTENDED_REFERENCE_MAX_LENGTH
+    }
+
+    fn balance_max_length(&self) -> usize {
+        EXTENDED_BALANCE_MAX_LENGTH
+    }
+
+    fn requires_statement_sequence_number(&self) -> bool {
+        false
+    }
+
+    fn message_terminators(&self) -> &'static [&'static str] {
+        &["-", "}"]
+    }
+}
+
+pub struct Tinkoff;
+
+impl Dialect for Tinkoff {
+    fn name(&self) -> &'static str {
+        "Tinkoff"
+    }
+
+    fn bank_codes(&self) -> &'static [&'static str] {
+        &["TICS"]
+    }
+
+    fn transaction_reference_number_max_length(&self) -> usize {
+        EXTENDED_REFERENCE_MAX_LENGTH
+    }
+
+    fn related_reference_max_length(&self) -> usize {
+        EXTENDED_REFERENCE_MAX_LENGTH
+    }
+
+    fn requires_statement_sequence_number(&self) -> bool {
+        false
+    }
+}
+
+pub struct Raiffeisen;
+
+impl Dialect for Raiffeisen {
+    fn name(&self) -> &'static str {
+        "Raiffeisen"
+    }
+
+    fn bank_codes(&self) -> &'static [&'static str] {
+        &["RZBA", "RZBM"]
+    }
+
+    fn related_reference_max_length(&self) -> usize {
+        EXTENDED_REFERENCE_MAX_LENGTH
+    }
+
+    fn balance_max_length(&self) -> usize {
+        EXTENDED_BALANCE_MAX_LENGTH
+    }
+
+    fn information_format(&self) -> InformationFormat {
+        InformationFormat::QuestionMarkSubfields
+    }
+
+    fn ignored_tags(&self) -> &'static [&'static str] {
+        &["13D", "90C", "90D"]
+    }
+}
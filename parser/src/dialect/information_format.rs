@@ -0,0 +1,215 @@
+use std::borrow::Cow;
+
+const SLASH_CODES: [&str; 24] = [
+    "ADDR",
+    "BENM",
+    "BIC",
+    "CDTRREF",
+    "CDTRREFTP",
+    "CHGS",
+    "CNTP",
+    "CSID",
+    "EREF",
+    "EXCH",
+    "FX",
+    "IBAN",
+    "ID",
+    "ISDT",
+    "MARF",
+    "NAME",
+    "ORDP",
+    "PURP",
+    "REMI",
+    "RTRN",
+    "SVCL",
+    "TRTP",
+    "ULTB",
+    "ULTD",
+];
+const SLASH_CODE_COUNTERPARTY_NAME: &str = "NAME";
+const QUESTION_MARK_COUNTERPARTY_NAMES: [&str; 2] = ["32", "33"];
+const SUBFIELD_SEPARATOR: char = '?';
+const SUBFIELD_CODE_LENGTH: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InformationFormat {
+    Unstructured,
+    SlashCodes,
+    QuestionMarkSubfields,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InformationSubfield {
+    code: String,
+    value: String,
+}
+
+impl InformationSubfield {
+    pub fn new(code: &str, value: &str) -> Self {
+        Self {
+            code: code.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl InformationFormat {
+    pub fn subfields(&self, information: &[Cow<'_, str>]) -> Vec<InformationSubfield> {
+        match self {
+            InformationFormat::Unstructured => {
+                vec![InformationSubfield::new("", &information.join(" "))]
+            }
+            InformationFormat::SlashCodes => slash_codes(&information.concat()),
+            InformationFormat::QuestionMarkSubfields => {
+                question_mark_subfields(&information.concat())
+            }
+        }
+    }
+
+    pub fn counterparty(&self, information: &[Cow<'_, str>]) -> Option<String> {
+        let codes: &[&str] = match self {
+            InformationFormat::Unstructured => return None,
+            InformationFormat::SlashCodes => &[SLASH_CODE_COUNTERPARTY_NAME],
+            InformationFormat::QuestionMarkSubfields => &QUESTION_MARK_COUNTERPARTY_NAMES,
+        };
+        let name = self
+            .subfields(information)
+            .iter()
+            .filter(|subfield| codes.contains(&subfield.code()))
+            .map(InformationSubfield::value)
+            .collect::<String>();
+        let name = name.trim();
+        (!name.is_empty()).then(|| name.to_string())
+    }
+}
+
+fn slash_codes(text: &str) -> Vec<InformationSubfield> {
+    let mut subfields = Vec::new();
+    let mut parts = text.strip_prefix('/').unwrap_or(text).split('/').peekable();
+    while let Some(code) = parts.next() {
+        let mut value = Vec::new();
+        while let Some(part) = parts.next_if(|part| !SLASH_CODES.contains(part)) {
+            value.push(part);
+        }
+        let value = value.join("/");
+        if SLASH_CODES.contains(&code) {
+            subfields.push(InformationSubfield::new(code, value.trim_end_matches('/')));
+        } else if value.is_empty() {
+            subfields.extend((!code.is_empty()).then(|| InformationSubfield::new("", code)));
+        } else {
+            subfields.push(InformationSubfield::new("", &format!("{}/{}", code, value)));
+        }
+    }
+    subfields
+}
+
+fn question_mark_subfields(text: &str) -> Vec<InformationSubfield> {
+    let mut parts = text.split(SUBFIELD_SEPARATOR);
+    let mut subfields = parts
+        .next()
+        .filter(|prefix| !prefix.is_empty())
+        .map(|prefix| InformationSubfield::new("", prefix))
+        .into_iter()
+        .collect::<Vec<_>>();
+    for part in parts {
+        let (code, value) = part
+            .split_at_checked(SUBFIELD_CODE_LENGTH)
+            .unwrap_or((part, ""));
+        subfields.push(InformationSubfield::new(code, value));
+    }
+    subfields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn information(lines: &[&'static str]) -> Vec<Cow<'static, str>> {
+        lines.iter().map(|line| Cow::Borrowed(*line)).collect()
+    }
+
+    #[test]
+    fn test_unstructured_information() {
+        let subfields =
+            InformationFormat::Unstructured.subfields(&information(&["INVOICE 1001", "ACME LTD"]));
+        assert_eq!(
+            subfields,
+            vec![InformationSubfield::new("", "INVOICE 1001 ACME LTD")]
+        );
+    }
+
+    #[test]
+    fn test_slash_codes_information() {
+        let subfields = InformationFormat::SlashCodes.subfields(&information(&[
+            "/TRTP/SEPA OVERBOEKING/IBAN/NL91ABNA0417164300/BIC/ABNANL2A/NAME/ACM",
+            "E LTD/REMI/USTD//INVOICE 1001/EREF/NOTPROVIDED",
+        ]));
+        assert_eq!(
+            subfields,
+            vec![
+                InformationSubfield::new("TRTP", "SEPA OVERBOEKING"),
+                InformationSubfield::new("IBAN", "NL91ABNA0417164300"),
+                InformationSubfield::new("BIC", "ABNANL2A"),
+                InformationSubfield::new("NAME", "ACME LTD"),
+                InformationSubfield::new("REMI", "USTD//INVOICE 1001"),
+                InformationSubfield::new("EREF", "NOTPROVIDED"),
+            ]
+        );
+
+        let subfields = InformationFormat::SlashCodes.subfields(&information(&["INVOICE 1001"]));
+        assert_eq!(
+            subfields,
+            vec![InformationSubfield::new("", "INVOICE 1001")]
+        );
+    }
+
+    #[test]
+    fn test_question_mark_subfields_information() {
+        let subfields = InformationFormat::QuestionMarkSubfields.subfields(&information(&[
+            "166?00GUTSCHRIFT?109310?20EREF+INVOICE 1001",
+            "?30DEUTDEFF?31DE89370400440532013000?32ACME LTD",
+        ]));
+        assert_eq!(
+            subfields,
+            vec![
+                InformationSubfield::new("", "166"),
+                InformationSubfield::new("00", "GUTSCHRIFT"),
+                InformationSubfield::new("10", "9310"),
+                InformationSubfield::new("20", "EREF+INVOICE 1001"),
+                InformationSubfield::new("30", "DEUTDEFF"),
+                InformationSubfield::new("31", "DE89370400440532013000"),
+                InformationSubfield::new("32", "ACME LTD"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_counterparty() {
+        let counterparty = InformationFormat::SlashCodes.counterparty(&information(&[
+            "/TRTP/SEPA OVERBOEKING/NAME/ACM",
+            "E LTD/REMI/INVOICE 1001",
+        ]));
+        assert_eq!(counterparty, Some("ACME LTD".to_string()));
+
+        let counterparty = InformationFormat::QuestionMarkSubfields.counterparty(&information(&[
+            "166?00GUTSCHRIFT?32ACME TRADING GMBH UND C",
+            "?33O KG",
+        ]));
+        assert_eq!(
+            counterparty,
+            Some("ACME TRADING GMBH UND CO KG".to_string())
+        );
+
+        let counterparty =
+            InformationFormat::Unstructured.counterparty(&information(&["ACME LTD"]));
+        assert_eq!(counterparty, None);
+    }
+}
@@ -1 +1,41 @@
+#[cfg(feature = "arbitrary")]
+mod arbitrary_message;
+mod balance_timeline;
+mod client_bank_exchange;
+mod diagnostic;
+mod dialect;
+mod message_reader;
+mod money;
 mod mt_940_customer_statement_message;
+mod network_validation;
+mod reconciliation;
+mod statement_anonymizer;
+mod statement_diff;
+mod statement_generator;
+mod statement_line_deduplicator;
+mod statement_line_filter;
+mod statement_report;
+#[cfg(feature = "sqlite")]
+mod statement_store;
+mod text_encoding;
+
+#[cfg(feature = "proptest")]
+pub use crate::arbitrary_message::*;
+pub use crate::balance_timeline::*;
+pub use crate::client_bank_exchange::*;
+pub use crate::diagnostic::*;
+pub use crate::dialect::*;
+pub use crate::message_reader::*;
+pub use crate::money::*;
+pub use crate::mt_940_customer_statement_message::*;
+pub use crate::network_validation::*;
+pub use crate::reconciliation::*;
+pub use crate::statement_anonymizer::*;
+pub use crate::statement_diff::*;
+pub use crate::statement_generator::*;
+pub use crate::statement_line_deduplicator::*;
+pub use crate::statement_line_filter::*;
+pub use crate::statement_report::*;
+#[cfg(feature = "sqlite")]
+pub use crate::statement_store::*;
+pub use crate::text_encoding::*;
@@ -0,0 +1,478 @@
+#[cfg(feature = "tokio")]
+mod async_message_reader;
+mod message_framer;
+
+use crate::dialect::*;
+#[cfg(feature = "tokio")]
+pub use crate::message_reader::async_message_reader::*;
+use crate::message_reader::message_framer::*;
+use crate::mt_940_customer_statement_message::*;
+use crate::text_encoding::*;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+
+pub struct MessageReader<R> {
+    reader: R,
+    dialect: &'static dyn Dialect,
+    encoding: Option<TextEncoding>,
+    framer: MessageFramer,
+    finished: bool,
+}
+
+impl<R: BufRead> MessageReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            dialect: &Standard,
+            encoding: None,
+            framer: MessageFramer::new(&Standard, None),
+            finished: false,
+        }
+    }
+
+    pub fn with_dialect(mut self, dialect: &'static dyn Dialect) -> Self {
+        self.dialect = dialect;
+        self.framer = MessageFramer::new(dialect, self.encoding);
+        self
+    }
+
+    pub fn with_encoding(mut self, encoding: Option<TextEncoding>) -> Self {
+        self.encoding = encoding;
+        self.framer = MessageFramer::new(self.dialect, encoding);
+        self
+    }
+
+    pub fn recovering(self) -> RecoveringMessageReader<R> {
+        RecoveringMessageReader { reader: self }
+    }
+
+    fn read_next<T>(&mut self, parse: FrameParser<T>) -> Option<Result<T, MessageReaderError>> {
+        if self.finished {
+            return None;
+        }
+        match self.next_frame() {
+            Ok(Some(frame)) => Some(parse(frame, self.dialect, self.encoding)),
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(err) => {
+                self.finished = true;
+                Some(Err(MessageReaderError::Io(err)))
+            }
+        }
+    }
+
+    fn next_frame(&mut self) -> Result<Option<Frame>, std::io::Error> {
+        loop {
+            let available = match self.reader.fill_buf() {
+                Ok(available) => available,
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            if available.is_empty() {
+                return Ok(self.framer.finish());
+            }
+            let (consumed, frame) = self.framer.feed(available);
+            self.reader.consume(consumed);
+            if frame.is_some() {
+                return Ok(frame);
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for MessageReader<R> {
+    type Item = Result<(u64, Mt940CustomerStatementMessage<'static>), MessageReaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_next(parse_frame)
+    }
+}
+
+pub struct RecoveringMessageReader<R> {
+    reader: MessageReader<R>,
+}
+
+impl<R: BufRead> Iterator for RecoveringMessageReader<R> {
+    type Item = Result<(u64, RecoveredMessage<'static>), MessageReaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.reader.read_next(parse_recovering_frame)
+    }
+}
+
+pub(crate) type FrameParser<T> =
+    fn(Frame, &dyn Dialect, Option<TextEncoding>) -> Result<T, MessageReaderError>;
+
+pub(crate) fn parse_frame(
+    frame: Frame,
+    dialect: &dyn Dialect,
+    encoding: Option<TextEncoding>,
+) -> Result<(u64, Mt940CustomerStatementMessage<'static>), MessageReaderError> {
+    decode_frame(frame, encoding, |text| {
+        Mt940CustomerStatementMessage::parse_with_dialect(text, dialect)
+            .map(Mt940CustomerStatementMessage::into_owned)
+    })
+}
+
+pub(crate) fn parse_recovering_frame(
+    frame: Frame,
+    dialect: &dyn Dialect,
+    encoding: Option<TextEncoding>,
+) -> Result<(u64, RecoveredMessage<'static>), MessageReaderError> {
+    decode_frame(frame, encoding, |text| {
+        Mt940CustomerStatementMessage::parse_recovering(text, dialect)
+            .map(RecoveredMessage::into_owned)
+    })
+}
+
+fn decode_frame<T>(
+    frame: Frame,
+    encoding: Option<TextEncoding>,
+    parse: impl FnOnce(&str) -> Result<T, Mt940CustomerStatementMessageParseError>,
+) -> Result<(u64, T), MessageReaderError> {
+    match frame {
+        Frame::TooLong(offset) => Err(MessageReaderError::MessageTooLong(offset)),
+        Frame::Message(offset, bytes) => {
+            let encoding = encoding.unwrap_or_else(|| TextEncoding::detect(&bytes));
+            let text = encoding.decode(&bytes).map_err(|err| {
+                MessageReaderError::InvalidEncoding(
+                    offset,
+                    encoding,
+                    stream_encoding_error(err, offset),
+                )
+            })?;
+            parse(&text)
+                .map(|message| (offset, message))
+                .map_err(|err| MessageReaderError::InvalidMessage(offset, err))
+        }
+    }
+}
+
+fn stream_encoding_error(err: TextEncodingError, offset: u64) -> TextEncodingError {
+    match err {
+        TextEncodingError::UndecodableBytes(offsets) => TextEncodingError::UndecodableBytes(
+            offsets
+                .into_iter()
+                .map(|position| position + offset as usize)
+                .collect(),
+        ),
+        err => err,
+    }
+}
+
+pub struct BorrowedMessageReader<'a> {
+    text: &'a str,
+    dialect: &'static dyn Dialect,
+    position: usize,
+}
+
+impl<'a> BorrowedMessageReader<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            dialect: &Standard,
+            position: 0,
+        }
+    }
+
+    pub fn with_dialect(self, dialect: &'static dyn Dialect) -> Self {
+        Self { dialect, ..self }
+    }
+}
+
+impl<'a> Iterator for BorrowedMessageReader<'a> {
+    type Item = Result<(u64, Mt940CustomerStatementMessage<'a>), MessageReaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut start = None;
+        let mut end = self.position;
+        for line in self.text[self.position..].split_inclusive('\n') {
+            let line_start = end;
+            end += line.len();
+            let content = line.trim().as_bytes();
+            match start {
+                None if is_between_messages(content) => continue,
+                None => start = Some(line_start),
+                Some(_) if is_message_start(content) => {
+                    end = line_start;
+                    break;
+                }
+                Some(_) => {}
+            }
+            if is_message_end(content, self.dialect.message_terminators()) {
+                break;
+            }
+        }
+        self.position = end;
+
+        let offset = start? as u64;
+        let text = &self.text[offset as usize..end];
+        Some(
+            Mt940CustomerStatementMessage::parse_with_dialect(text, self.dialect)
+                .map(|message| (offset, message))
+                .map_err(|err| MessageReaderError::InvalidMessage(offset, err)),
+        )
+    }
+}
+
+#[derive(Debug)]
+pub enum MessageReaderError {
+    Io(std::io::Error),
+    MessageTooLong(u64),
+    InvalidEncoding(u64, TextEncoding, TextEncodingError),
+    InvalidMessage(u64, Mt940CustomerStatementMessageParseError),
+}
+
+impl MessageReaderError {
+    pub fn offset(&self) -> Option<u64> {
+        match self {
+            MessageReaderError::Io(_) => None,
+            MessageReaderError::MessageTooLong(offset)
+            | MessageReaderError::InvalidEncoding(offset, _, _)
+            | MessageReaderError::InvalidMessage(offset, _) => Some(*offset),
+        }
+    }
+}
+
+impl Display for MessageReaderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MessageReaderError::Io(err) => write!(f, "Failed to read messages: {}", err),
+            MessageReaderError::MessageTooLong(offset) => {
+                write!(f, "Message at byte {} exceeds maximum length", offset)
+            }
+            MessageReaderError::InvalidEncoding(offset, encoding, err) => write!(
+                f,
+                "Message at byte {} is not valid {}: {}",
+                offset, encoding, err
+            ),
+            MessageReaderError::InvalidMessage(offset, err) => {
+                write!(f, "Message at byte {} is invalid: {}", offset, err)
+            }
+        }
+    }
+}
+
+impl Error for MessageReaderError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufReader, Cursor};
+
+    const MESSAGES: &str = ":20:FIRST
+:25:123456
+:28C:1
+:60F:C230305EUR100,00
+:61:230306D10,00NTRFREF1
+:62F:C230306EUR90,00
+-
+:20:BROKEN
+:25:123456
+:28C:2
+:60F:X230306EUR90,00
+:62F:C230307EUR90,00
+-
+{1:F01BANKDEFFAXXX0000000000}{4:
+:20:THIRD
+:25:123456
+:28C:3
+:60F:C230307EUR90,00
+:62F:C230307EUR90,00
+-}
+";
+
+    #[test]
+    fn test_read_messages() {
+        let reader = MessageReader::new(BufReader::with_capacity(7, Cursor::new(MESSAGES)));
+        let results = reader.collect::<Vec<_>>();
+        assert_eq!(results.len(), 3);
+
+        let (offset, message) = results[0].as_ref().unwrap();
+        assert_eq!(*offset, 0);
+        assert_eq!(message.transaction_reference_number().as_str(), "FIRST");
+        assert_eq!(message.statement_lines().len(), 1);
+
+        let err = results[1].as_ref().unwrap_err();
+        let offset = MESSAGES.find(":20:BROKEN").unwrap() as u64;
+        assert_eq!(err.offset(), Some(offset));
+        assert!(matches!(
+            err,
+            MessageReaderError::InvalidMessage(
+                _,
+                Mt940CustomerStatementMessageParseError::OpeningBalance(_)
+            )
+        ));
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Message at byte {} is invalid: :60a: Opening balance has invalid format: Invalid credit/debit mark",
+                offset
+            )
+        );
+
+        let (offset, message) = results[2].as_ref().unwrap();
+        assert_eq!(*offset, MESSAGES.find("{1:").unwrap() as u64);
+        assert_eq!(message.transaction_reference_number().as_str(), "THIRD");
+    }
+
+    #[test]
+    fn test_read_recovered_messages() {
+        let text = MESSAGES.replace(":28C:2\n:60F:X", ":28C:2/X\n:60F:C");
+        let reader = MessageReader::new(BufReader::with_capacity(7, Cursor::new(&text)));
+        let results = reader.recovering().collect::<Vec<_>>();
+        assert_eq!(results.len(), 3);
+
+        let (offset, recovered) = results[0].as_ref().unwrap();
+        assert_eq!(*offset, 0);
+        assert!(recovered.is_complete());
+
+        let (offset, recovered) = results[1].as_ref().unwrap();
+        assert_eq!(*offset, text.find(":20:BROKEN").unwrap() as u64);
+        assert_eq!(
+            recovered.message().transaction_reference_number().as_str(),
+            "BROKEN"
+        );
+        assert_eq!(recovered.errors().len(), 1);
+        assert_eq!(recovered.errors()[0].tag(), "28C");
+        assert_eq!(recovered.errors()[0].value(), "2/X");
+
+        let (_, recovered) = results[2].as_ref().unwrap();
+        assert!(recovered.is_complete());
+    }
+
+    #[test]
+    fn test_read_invalid_encoding() {
+        let mut input = MESSAGES.as_bytes().to_vec();
+        input.extend_from_slice(b":20:\xFF\n:25:1\xFE\n-\n");
+        let mut reader =
+            MessageReader::new(Cursor::new(&input)).with_encoding(Some(TextEncoding::Utf8));
+
+        assert_eq!(reader.by_ref().take(3).count(), 3);
+        let err = reader.next().unwrap().unwrap_err();
+        let offset = MESSAGES.len();
+        assert_eq!(err.offset(), Some(offset as u64));
+        let MessageReaderError::InvalidEncoding(_, TextEncoding::Utf8, encoding_error) = &err
+        else {
+            panic!("unexpected error {:?}", err);
+        };
+        assert_eq!(
+            encoding_error,
+            &TextEncodingError::UndecodableBytes(vec![offset + 4, offset + 11])
+        );
+        assert_eq!(input[offset + 4], 0xFF);
+        assert_eq!(input[offset + 11], 0xFE);
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Message at byte {} is not valid UTF-8: Undecodable bytes at offsets {}, {}",
+                offset,
+                offset + 4,
+                offset + 11
+            )
+        );
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_read_encoded_messages() {
+        let text = MESSAGES.replace("REF1", "ОПЛАТА");
+        let bytes = TextEncoding::Windows1251.encode(&text).unwrap();
+        let results = MessageReader::new(Cursor::new(bytes)).collect::<Vec<_>>();
+        assert_eq!(results.len(), 3);
+        let (_, message) = results[0].as_ref().unwrap();
+        assert_eq!(message.statement_lines()[0].account_owner_ref(), "ОПЛАТА");
+
+        let bytes = TextEncoding::Ebcdic.encode(MESSAGES).unwrap();
+        let results = MessageReader::new(Cursor::new(bytes))
+            .with_encoding(Some(TextEncoding::Ebcdic))
+            .collect::<Vec<_>>();
+        assert_eq!(results.len(), 3);
+        let (_, message) = results[2].as_ref().unwrap();
+        assert_eq!(message.transaction_reference_number().as_str(), "THIRD");
+    }
+
+    #[test]
+    fn test_read_detected_ebcdic_messages() {
+        let bytes = TextEncoding::Ebcdic.encode(MESSAGES).unwrap();
+        assert_eq!(TextEncoding::detect(&bytes), TextEncoding::Ebcdic);
+        for capacity in [7, 8192] {
+            let results = MessageReader::new(BufReader::with_capacity(capacity, &bytes[..]))
+                .collect::<Vec<_>>();
+            assert_eq!(results.len(), 3);
+            let (_, message) = results[0].as_ref().unwrap();
+            assert_eq!(message.transaction_reference_number().as_str(), "FIRST");
+            assert!(results[1].is_err());
+            let (offset, message) = results[2].as_ref().unwrap();
+            assert_eq!(*offset, MESSAGES.find("{1:").unwrap() as u64);
+            assert_eq!(message.transaction_reference_number().as_str(), "THIRD");
+        }
+    }
+
+    #[test]
+    fn test_read_borrowed_messages() {
+        let results = BorrowedMessageReader::new(MESSAGES).collect::<Vec<_>>();
+        assert_eq!(results.len(), 3);
+
+        let (offset, message) = results[0].as_ref().unwrap();
+        assert_eq!(*offset, 0);
+        let account_owner_ref = message.statement_lines()[0].account_owner_ref();
+        assert_eq!(account_owner_ref, "REF1");
+        assert!(
+            MESSAGES
+                .as_bytes()
+                .as_ptr_range()
+                .contains(&account_owner_ref.as_ptr())
+        );
+
+        let err = results[1].as_ref().unwrap_err();
+        assert_eq!(
+            err.offset(),
+            Some(MESSAGES.find(":20:BROKEN").unwrap() as u64)
+        );
+
+        let (offset, message) = results[2].as_ref().unwrap();
+        assert_eq!(*offset, MESSAGES.find("{1:").unwrap() as u64);
+        assert_eq!(message.transaction_reference_number().as_str(), "THIRD");
+    }
+
+    #[test]
+    fn test_read_messages_with_dialect() {
+        let text = MESSAGES.replace("\n-\n", "\n}\n");
+        let reader = MessageReader::new(Cursor::new(text.as_str())).with_dialect(&Sberbank);
+        let results = reader.collect::<Vec<_>>();
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+        assert!(results[2].is_ok());
+
+        let results = BorrowedMessageReader::new(&text)
+            .with_dialect(&Sberbank)
+            .collect::<Vec<_>>();
+        assert_eq!(results.len(), 3);
+        assert_eq!(
+            results[1].as_ref().unwrap_err().offset(),
+            Some(text.find(":20:BROKEN").unwrap() as u64)
+        );
+        assert_eq!(
+            results[2]
+                .as_ref()
+                .unwrap()
+                .1
+                .transaction_reference_number()
+                .as_str(),
+            "THIRD"
+        );
+
+        assert_eq!(MessageReader::new(Cursor::new(text.as_str())).count(), 2);
+    }
+
+    #[test]
+    fn test_read_empty_input() {
+        let mut reader = MessageReader::new(Cursor::new("\n\r\n  \n"));
+        assert!(reader.next().is_none());
+    }
+}
@@ -0,0 +1,186 @@
+use crate::dialect::*;
+use crate::message_reader::message_framer::*;
+use crate::message_reader::*;
+use crate::mt_940_customer_statement_message::*;
+use crate::text_encoding::*;
+use futures_core::Stream;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+use tokio::io::AsyncBufRead;
+
+pub struct AsyncMessageReader<R> {
+    reader: R,
+    dialect: &'static dyn Dialect,
+    encoding: Option<TextEncoding>,
+    framer: MessageFramer,
+    finished: bool,
+}
+
+impl<R: AsyncBufRead + Unpin> AsyncMessageReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            dialect: &Standard,
+            encoding: None,
+            framer: MessageFramer::new(&Standard, None),
+            finished: false,
+        }
+    }
+
+    pub fn with_dialect(mut self, dialect: &'static dyn Dialect) -> Self {
+        self.dialect = dialect;
+        self.framer = MessageFramer::new(dialect, self.encoding);
+        self
+    }
+
+    pub fn with_encoding(mut self, encoding: Option<TextEncoding>) -> Self {
+        self.encoding = encoding;
+        self.framer = MessageFramer::new(self.dialect, encoding);
+        self
+    }
+
+    pub async fn next_message(
+        &mut self,
+    ) -> Option<Result<(u64, Mt940CustomerStatementMessage<'static>), MessageReaderError>> {
+        std::future::poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+
+    pub async fn next_recovered_message(
+        &mut self,
+    ) -> Option<Result<(u64, RecoveredMessage<'static>), MessageReaderError>> {
+        std::future::poll_fn(|cx| self.poll_read_next(cx, parse_recovering_frame)).await
+    }
+
+    fn poll_read_next<T>(
+        &mut self,
+        cx: &mut Context<'_>,
+        parse: FrameParser<T>,
+    ) -> Poll<Option<Result<T, MessageReaderError>>> {
+        if self.finished {
+            return Poll::Ready(None);
+        }
+        loop {
+            let available = match ready!(Pin::new(&mut self.reader).poll_fill_buf(cx)) {
+                Ok(available) => available,
+                Err(err) => {
+                    self.finished = true;
+                    return Poll::Ready(Some(Err(MessageReaderError::Io(err))));
+                }
+            };
+            if available.is_empty() {
+                let frame = self.framer.finish();
+                self.finished = frame.is_none();
+                return Poll::Ready(frame.map(|frame| parse(frame, self.dialect, self.encoding)));
+            }
+            let (consumed, frame) = self.framer.feed(available);
+            Pin::new(&mut self.reader).consume(consumed);
+            if let Some(frame) = frame {
+                return Poll::Ready(Some(parse(frame, self.dialect, self.encoding)));
+            }
+        }
+    }
+}
+
+impl<R: AsyncBufRead + Unpin> Stream for AsyncMessageReader<R> {
+    type Item = Result<(u64, Mt940CustomerStatementMessage<'static>), MessageReaderError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_read_next(cx, parse_frame)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::BufReader;
+
+    const MESSAGES: &[u8] = b":20:FIRST
+:25:123456
+:28C:1
+:60F:C230305EUR100,00
+:62F:C230305EUR100,00
+-
+:20:BROKEN
+-
+:20:THIRD
+:25:123456
+:28C:2
+:60F:C230305EUR100,00
+:62F:C230305EUR100,00";
+
+    #[tokio::test]
+    async fn test_read_messages_async() {
+        let mut reader = AsyncMessageReader::new(BufReader::with_capacity(5, MESSAGES));
+
+        let (offset, message) = reader.next_message().await.unwrap().unwrap();
+        assert_eq!(offset, 0);
+        assert_eq!(message.transaction_reference_number().as_str(), "FIRST");
+
+        let err = reader.next_message().await.unwrap().unwrap_err();
+        assert!(matches!(
+            err,
+            MessageReaderError::InvalidMessage(
+                _,
+                Mt940CustomerStatementMessageParseError::MissingField("25")
+            )
+        ));
+        assert_eq!(err.offset(), Some(74));
+
+        let (offset, message) = reader.next_message().await.unwrap().unwrap();
+        assert_eq!(offset, 87);
+        assert_eq!(message.transaction_reference_number().as_str(), "THIRD");
+
+        assert!(reader.next_message().await.is_none());
+        assert!(reader.next_message().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_read_messages_async_with_dialect() {
+        let text = String::from_utf8(MESSAGES.to_vec())
+            .unwrap()
+            .replace("\n-\n", "\n}\n");
+        let mut reader =
+            AsyncMessageReader::new(BufReader::new(text.as_bytes())).with_dialect(&Sberbank);
+
+        let (_, message) = reader.next_message().await.unwrap().unwrap();
+        assert_eq!(message.transaction_reference_number().as_str(), "FIRST");
+        assert!(reader.next_message().await.unwrap().is_err());
+        let (_, message) = reader.next_message().await.unwrap().unwrap();
+        assert_eq!(message.transaction_reference_number().as_str(), "THIRD");
+        assert!(reader.next_message().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_read_messages_async_with_encoding() {
+        let text = String::from_utf8(MESSAGES.to_vec())
+            .unwrap()
+            .replace("FIRST", "ПЕРВЫЙ");
+        let bytes = TextEncoding::Koi8R.encode(&text).unwrap();
+        let mut reader = AsyncMessageReader::new(BufReader::new(&bytes[..]))
+            .with_encoding(Some(TextEncoding::Koi8R));
+
+        let (_, message) = reader.next_message().await.unwrap().unwrap();
+        assert_eq!(message.transaction_reference_number().as_str(), "ПЕРВЫЙ");
+    }
+
+    #[tokio::test]
+    async fn test_read_recovered_messages_async() {
+        let text =
+            String::from_utf8(MESSAGES.to_vec())
+                .unwrap()
+                .replacen(":28C:1\n", ":28C:1/X\n", 1);
+        let mut reader = AsyncMessageReader::new(BufReader::with_capacity(5, text.as_bytes()));
+
+        let (_, recovered) = reader.next_recovered_message().await.unwrap().unwrap();
+        assert_eq!(
+            recovered.message().transaction_reference_number().as_str(),
+            "FIRST"
+        );
+        assert_eq!(recovered.errors().len(), 1);
+        assert_eq!(recovered.errors()[0].tag(), "28C");
+        assert!(reader.next_recovered_message().await.unwrap().is_err());
+        let (_, recovered) = reader.next_recovered_message().await.unwrap().unwrap();
+        assert!(recovered.is_complete());
+        assert!(reader.next_recovered_message().await.is_none());
+    }
+}
@@ -0,0 +1,303 @@
+use crate::dialect::*;
+use crate::text_encoding::*;
+use std::borrow::Cow;
+
+const MESSAGE_MAX_LENGTH: usize = 1 << 20;
+const ENCODING_DETECTION_LENGTH: usize = 4096;
+const MESSAGE_START: &[u8] = b"{1:";
+const TEXT_BLOCK_END: &[u8] = b"-}";
+const TRAILER_BLOCKS: [&[u8]; 2] = [b"{5:", b"{S:"];
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Frame {
+    Message(u64, Vec<u8>),
+    TooLong(u64),
+}
+
+#[derive(Debug)]
+pub(crate) struct MessageFramer {
+    terminators: &'static [&'static str],
+    encoding: Option<TextEncoding>,
+    pending: Vec<u8>,
+    position: u64,
+    line: Vec<u8>,
+    line_offset: u64,
+    message: Vec<u8>,
+    message_offset: Option<u64>,
+    overflowed: bool,
+}
+
+impl MessageFramer {
+    pub(crate) fn new(dialect: &dyn Dialect, encoding: Option<TextEncoding>) -> Self {
+        Self {
+            terminators: dialect.message_terminators(),
+            encoding,
+            pending: Vec::new(),
+            position: 0,
+            line: Vec::new(),
+            line_offset: 0,
+            message: Vec::new(),
+            message_offset: None,
+            overflowed: false,
+        }
+    }
+
+    pub(crate) fn feed(&mut self, available: &[u8]) -> (usize, Option<Frame>) {
+        if self.encoding.is_none() {
+            let length = available
+                .len()
+                .min(ENCODING_DETECTION_LENGTH - self.pending.len());
+            self.pending.extend_from_slice(&available[..length]);
+            if self.pending.len() < ENCODING_DETECTION_LENGTH {
+                return (length, None);
+            }
+            self.detect_encoding();
+            return (length, self.feed_pending());
+        }
+        if !self.pending.is_empty() {
+            return (0, self.feed_pending());
+        }
+        self.feed_line(available)
+    }
+
+    pub(crate) fn finish(&mut self) -> Option<Frame> {
+        if self.encoding.is_none() {
+            self.detect_encoding();
+        }
+        if let Some(frame) = self.feed_pending() {
+            return Some(frame);
+        }
+        if !self.line.is_empty()
+            && let Some(frame) = self.end_line()
+        {
+            return Some(frame);
+        }
+        self.take_frame()
+    }
+
+    fn detect_encoding(&mut self) {
+        self.encoding = Some(TextEncoding::detect(&self.pending));
+    }
+
+    fn feed_pending(&mut self) -> Option<Frame> {
+        let mut pending = std::mem::take(&mut self.pending);
+        let mut consumed = 0;
+        let mut frame = None;
+        while frame.is_none() && consumed < pending.len() {
+            let (length, line_frame) = self.feed_line(&pending[consumed..]);
+            consumed += length;
+            frame = line_frame;
+        }
+        pending.drain(..consumed);
+        self.pending = pending;
+        frame
+    }
+
+    fn text_encoding(&self) -> TextEncoding {
+        self.encoding.unwrap_or(TextEncoding::Utf8)
+    }
+
+    fn feed_line(&mut self, available: &[u8]) -> (usize, Option<Frame>) {
+        let encoding = self.text_encoding();
+        let newline = available
+            .iter()
+            .position(|&b| encoding.ascii_byte(b) == b'\n');
+        let (chunk, line_complete) = match newline {
+            Some(end) => (&available[..=end], true),
+            None => (available, false),
+        };
+        if self.line.is_empty() {
+            self.line_offset = self.position;
+        }
+        let capacity = (MESSAGE_MAX_LENGTH + 1).saturating_sub(self.line.len());
+        self.line
+            .extend_from_slice(&chunk[..chunk.len().min(capacity)]);
+        self.position += chunk.len() as u64;
+
+        let frame = if line_complete { self.end_line() } else { None };
+        (chunk.len(), frame)
+    }
+
+    fn end_line(&mut self) -> Option<Frame> {
+        let line = std::mem::take(&mut self.line);
+        let (is_between, is_start, is_end) = {
+            let encoding = self.text_encoding();
+            let ascii = match encoding {
+                TextEncoding::Ebcdic => {
+                    Cow::Owned(line.iter().map(|byte| encoding.ascii_byte(*byte)).collect())
+                }
+                _ => Cow::Borrowed(&line[..]),
+            };
+            let content = ascii.trim_ascii();
+            (
+                is_between_messages(content),
+                is_message_start(content),
+                is_message_end(content, self.terminators),
+            )
+        };
+
+        if self.message_offset.is_none() {
+            if is_between {
+                return None;
+            }
+        } else if is_start {
+            let frame = self.take_frame();
+            self.push_line(line);
+            return frame;
+        }
+
+        self.push_line(line);
+        if is_end { self.take_frame() } else { None }
+    }
+
+    fn push_line(&mut self, line: Vec<u8>) {
+        if self.message_offset.is_none() {
+            self.message_offset = Some(self.line_offset);
+        }
+        if self.overflowed || self.message.len() + line.len() > MESSAGE_MAX_LENGTH {
+            self.overflowed = true;
+            self.message = Vec::new();
+        } else {
+            self.message.extend_from_slice(&line);
+        }
+    }
+
+    fn take_frame(&mut self) -> Option<Frame> {
+        let offset = self.message_offset.take()?;
+        let message = std::mem::take(&mut self.message);
+        if std::mem::take(&mut self.overflowed) {
+            Some(Frame::TooLong(offset))
+        } else {
+            Some(Frame::Message(offset, message))
+        }
+    }
+}
+
+pub(crate) fn is_between_messages(content: &[u8]) -> bool {
+    content.is_empty() || TRAILER_BLOCKS.iter().any(|b| content.starts_with(b))
+}
+
+pub(crate) fn is_message_start(content: &[u8]) -> bool {
+    content.starts_with(MESSAGE_START)
+}
+
+pub(crate) fn is_message_end(content: &[u8], terminators: &[&str]) -> bool {
+    terminators
+        .iter()
+        .any(|terminator| content == terminator.as_bytes())
+        || content.starts_with(TEXT_BLOCK_END)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames(input: &[u8], chunk_size: usize) -> Vec<Frame> {
+        dialect_frames(input, chunk_size, &Standard)
+    }
+
+    fn dialect_frames(input: &[u8], chunk_size: usize, dialect: &dyn Dialect) -> Vec<Frame> {
+        let mut framer = MessageFramer::new(dialect, None);
+        let mut frames = Vec::new();
+        let mut input = input;
+        while !input.is_empty() {
+            let (consumed, frame) = framer.feed(&input[..chunk_size.min(input.len())]);
+            input = &input[consumed..];
+            frames.extend(frame);
+        }
+        while let Some(frame) = framer.finish() {
+            frames.push(frame);
+        }
+        frames
+    }
+
+    #[test]
+    fn test_frames_split_on_terminators() {
+        let input = b"\r\n{1:A}{4:\r\n:20:X\r\n-}{5:{CHK:1}}\r\n{5:{CHK:2}}\n:20:Y\n-\n\n";
+        for chunk_size in [1, 3, input.len()] {
+            assert_eq!(
+                frames(input, chunk_size),
+                vec![
+                    Frame::Message(2, b"{1:A}{4:\r\n:20:X\r\n-}{5:{CHK:1}}\r\n".to_vec()),
+                    Frame::Message(46, b":20:Y\n-\n".to_vec()),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_frames_split_on_dialect_terminators() {
+        let input = b":20:X\n}\n:20:Y\n-\n";
+        assert_eq!(
+            dialect_frames(input, 3, &Sberbank),
+            vec![
+                Frame::Message(0, b":20:X\n}\n".to_vec()),
+                Frame::Message(8, b":20:Y\n-\n".to_vec()),
+            ]
+        );
+        assert_eq!(frames(input, 3), vec![Frame::Message(0, input.to_vec())]);
+    }
+
+    #[test]
+    fn test_frames_ebcdic() {
+        let input = TextEncoding::Ebcdic.encode(":20:X\n-\n:20:Y\n-\n").unwrap();
+        let mut framer = MessageFramer::new(&Standard, Some(TextEncoding::Ebcdic));
+        let mut frames = Vec::new();
+        let mut rest = &input[..];
+        while !rest.is_empty() {
+            let (consumed, frame) = framer.feed(rest);
+            rest = &rest[consumed..];
+            frames.extend(frame);
+        }
+        assert_eq!(
+            frames,
+            vec![
+                Frame::Message(0, input[..8].to_vec()),
+                Frame::Message(8, input[8..].to_vec()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_frames_detected_ebcdic() {
+        let input = TextEncoding::Ebcdic
+            .encode(":20:X\n-\n:20:Y\n-\n:20:Z\n-\n")
+            .unwrap();
+        for chunk_size in [1, 5, input.len()] {
+            assert_eq!(
+                frames(&input, chunk_size),
+                vec![
+                    Frame::Message(0, input[..8].to_vec()),
+                    Frame::Message(8, input[8..16].to_vec()),
+                    Frame::Message(16, input[16..].to_vec()),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_frames_new_message_without_terminator() {
+        let input = b"{1:A}{4:\n:20:X\n{1:B}{4:\n:20:Y";
+        assert_eq!(
+            frames(input, 4),
+            vec![
+                Frame::Message(0, b"{1:A}{4:\n:20:X\n".to_vec()),
+                Frame::Message(15, b"{1:B}{4:\n:20:Y".to_vec()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_frames_message_too_long() {
+        let mut input = b":20:X\n:86:".to_vec();
+        input.extend(std::iter::repeat_n(b'A', MESSAGE_MAX_LENGTH));
+        input.extend(b"\n-\n:20:Y\n-\n");
+        assert_eq!(
+            frames(&input, 4096),
+            vec![
+                Frame::TooLong(0),
+                Frame::Message(input.len() as u64 - 8, b":20:Y\n-\n".to_vec()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,290 @@
+use crate::mt_940_customer_statement_message::*;
+use rust_decimal::Decimal;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Neg, Sub};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Money {
+    value: Decimal,
+    currency_code: CurrencyCode<'static>,
+}
+
+impl Money {
+    pub fn new(value: Decimal, currency_code: CurrencyCode<'_>) -> Self {
+        Self {
+            value,
+            currency_code: currency_code.into_owned(),
+        }
+    }
+
+    pub fn zero(currency_code: CurrencyCode<'_>) -> Self {
+        Self::new(Decimal::ZERO, currency_code)
+    }
+
+    pub fn sum_in<M: Borrow<Money>>(
+        currency_code: CurrencyCode<'_>,
+        amounts: impl IntoIterator<Item = M>,
+    ) -> Result<Money, MoneyError> {
+        amounts
+            .into_iter()
+            .try_fold(Self::zero(currency_code), |total, money| {
+                total + money.borrow().clone()
+            })
+    }
+
+    pub fn from_statement_line(
+        statement_line: &StatementLine<'_>,
+        currency_code: &CurrencyCode<'_>,
+    ) -> Self {
+        let value = statement_line.amount().value();
+        let value = match statement_line.debit_credit_mark() {
+            StatementLineMark::Credit | StatementLineMark::ReversalOfDebit => value,
+            StatementLineMark::Debit | StatementLineMark::ReversalOfCredit => -value,
+        };
+        Self::new(value, currency_code.clone())
+    }
+
+    pub fn value(&self) -> Decimal {
+        self.value
+    }
+
+    pub fn currency_code(&self) -> &CurrencyCode<'static> {
+        &self.currency_code
+    }
+
+    pub fn credit_debit_mark(&self) -> CreditDebitMark {
+        if self.value.is_sign_negative() && !self.value.is_zero() {
+            CreditDebitMark::Debit
+        } else {
+            CreditDebitMark::Credit
+        }
+    }
+
+    pub fn swift_amount(&self) -> String {
+        let value = self.value.abs().to_string();
+        match value.split_once('.') {
+            Some((integer, fraction)) => format!("{},{}", integer, fraction),
+            None => format!("{},", value),
+        }
+    }
+
+    fn check_currency(&self, other: &Money) -> Result<(), MoneyError> {
+        if self.currency_code == other.currency_code {
+            Ok(())
+        } else {
+            Err(MoneyError::CurrencyMismatch(
+                self.currency_code.to_string(),
+                other.currency_code.to_string(),
+            ))
+        }
+    }
+}
+
+impl From<&Balance<'_>> for Money {
+    fn from(balance: &Balance<'_>) -> Self {
+        let value = balance.amount().value();
+        let value = match balance.debit_credit_mark() {
+            CreditDebitMark::Credit => value,
+            CreditDebitMark::Debit => -value,
+        };
+        Self::new(value, balance.currency_code().clone())
+    }
+}
+
+impl Add for Money {
+    type Output = Result<Money, MoneyError>;
+
+    fn add(self, rhs: Money) -> Self::Output {
+        self.check_currency(&rhs)?;
+        let value = self
+            .value
+            .checked_add(rhs.value)
+            .ok_or(MoneyError::Overflow)?;
+        Ok(Self { value, ..self })
+    }
+}
+
+impl Sub for Money {
+    type Output = Result<Money, MoneyError>;
+
+    fn sub(self, rhs: Money) -> Self::Output {
+        self.check_currency(&rhs)?;
+        let value = self
+            .value
+            .checked_sub(rhs.value)
+            .ok_or(MoneyError::Overflow)?;
+        Ok(Self { value, ..self })
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            value: -self.value,
+            ..self
+        }
+    }
+}
+
+impl PartialOrd for Money {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.currency_code == other.currency_code {
+            self.value.partial_cmp(&other.value)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Money {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.credit_debit_mark() == CreditDebitMark::Debit {
+            "-"
+        } else {
+            ""
+        };
+        write!(f, "{} {}{}", self.currency_code, sign, self.swift_amount())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum MoneyError {
+    CurrencyMismatch(String, String),
+    Overflow,
+}
+
+impl Display for MoneyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MoneyError::CurrencyMismatch(left, right) => {
+                write!(f, "Cannot combine amounts in {} and {}", left, right)
+            }
+            MoneyError::Overflow => write!(f, "Amount overflow"),
+        }
+    }
+}
+
+impl Error for MoneyError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn money(value: &str, currency_code: &str) -> Money {
+        Money::new(
+            value.parse().unwrap(),
+            CurrencyCode::try_from(currency_code).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_money_from_balance() {
+        let balance = Balance::try_from("D230306DKK985623,04").unwrap();
+        assert_eq!(Money::from(&balance), money("-985623.04", "DKK"));
+
+        let balance = Balance::try_from("C230306DKK985623,04").unwrap();
+        assert_eq!(Money::from(&balance), money("985623.04", "DKK"));
+    }
+
+    #[test]
+    fn test_money_from_statement_line() {
+        let eur = CurrencyCode::try_from("EUR").unwrap();
+        let cases = [
+            ("230306C10,5NTRFREF", "10.5"),
+            ("230306D10,5NTRFREF", "-10.5"),
+            ("230306RC10,5NTRFREF", "-10.5"),
+            ("230306RD10,5NTRFREF", "10.5"),
+        ];
+        for (statement_line, expected) in cases {
+            let statement_line = StatementLine::try_from(statement_line).unwrap();
+            assert_eq!(
+                Money::from_statement_line(&statement_line, &eur),
+                money(expected, "EUR")
+            );
+        }
+    }
+
+    #[test]
+    fn test_money_arithmetic() {
+        assert_eq!(
+            money("10.50", "EUR") + money("1.25", "EUR"),
+            Ok(money("11.75", "EUR"))
+        );
+        assert_eq!(
+            money("10.50", "EUR") - money("11.25", "EUR"),
+            Ok(money("-0.75", "EUR"))
+        );
+        assert_eq!(-money("10.50", "EUR"), money("-10.50", "EUR"));
+
+        let eur = CurrencyCode::try_from("EUR").unwrap();
+        let total = Money::sum_in(eur, &[money("1", "EUR"), money("2.5", "EUR")]);
+        assert_eq!(total, Ok(money("3.5", "EUR")));
+    }
+
+    #[test]
+    fn test_money_currency_mismatch() {
+        let result = money("10", "EUR") + money("10", "DKK");
+        assert_eq!(
+            result,
+            Err(MoneyError::CurrencyMismatch(
+                "EUR".to_string(),
+                "DKK".to_string()
+            ))
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Cannot combine amounts in EUR and DKK"
+        );
+
+        let total = Money::sum_in(
+            CurrencyCode::try_from("EUR").unwrap(),
+            vec![money("1", "EUR"), money("2", "DKK")],
+        );
+        assert!(matches!(total, Err(MoneyError::CurrencyMismatch(_, _))));
+
+        assert_eq!(money("10", "EUR").partial_cmp(&money("10", "DKK")), None);
+    }
+
+    #[test]
+    fn test_money_empty_sum() {
+        let total = Money::sum_in(CurrencyCode::try_from("EUR").unwrap(), Vec::<Money>::new());
+        assert_eq!(total, Ok(money("0", "EUR")));
+        assert_eq!(
+            Money::zero(CurrencyCode::try_from("DKK").unwrap()).to_string(),
+            "DKK 0,"
+        );
+    }
+
+    #[test]
+    fn test_money_overflow() {
+        let result = money(&Decimal::MAX.to_string(), "EUR") + money("1", "EUR");
+        assert_eq!(result, Err(MoneyError::Overflow));
+    }
+
+    #[test]
+    fn test_money_comparison() {
+        assert!(money("10", "EUR") > money("9.99", "EUR"));
+        assert!(money("-10", "EUR") < money("0", "EUR"));
+    }
+
+    #[test]
+    fn test_money_format() {
+        assert_eq!(money("-1234.56", "EUR").swift_amount(), "1234,56");
+        assert_eq!(money("1234", "JPY").swift_amount(), "1234,");
+        assert_eq!(money("-1234.56", "EUR").to_string(), "EUR -1234,56");
+        assert_eq!(money("0.00", "EUR").to_string(), "EUR 0,00");
+        assert_eq!(
+            money("-1", "EUR").credit_debit_mark(),
+            CreditDebitMark::Debit
+        );
+        assert_eq!(
+            money("0", "EUR").credit_debit_mark(),
+            CreditDebitMark::Credit
+        );
+    }
+}
@@ -2,47 +2,1056 @@ mod account_identification;
 mod amount;
 mod balance;
 mod date;
+mod iso_4217;
 mod related_reference;
+mod statement_line;
 mod statement_sequence_number;
 mod transaction_reference_number;
 
-use crate::mt_940_customer_statement_message::account_identification::*;
-use crate::mt_940_customer_statement_message::amount::*;
-use crate::mt_940_customer_statement_message::balance::*;
-use crate::mt_940_customer_statement_message::date::*;
-use crate::mt_940_customer_statement_message::related_reference::*;
-use crate::mt_940_customer_statement_message::statement_sequence_number::*;
-use crate::mt_940_customer_statement_message::transaction_reference_number::*;
-
-struct Mt940CustomerStatementMessage {
-    transaction_reference_number: TransactionReferenceNumber,
-    related_reference: Option<RelatedReference>,
-    account_identification: AccountIdentification,
-    statement_sequence_no: StatementSequenceNumber,
-    opening_balance: Balance,
-    statement_lines: Option<Vec<StatementLine>>,
-    closing_balance: Balance,
-    closing_available_balance: Option<Balance>,
-    forward_available_balance: Option<Balance>,
-    information_to_account_owner: Option<Vec<String>>,
-}
-
-struct StatementLine {
-    value_date: Date,
-    entry_date: Option<Date>,
-    debit_credit_mark: StatementLineMark,
-    funds_code: Option<char>,
-    amount: Amount,
-    transaction_type_identification_code: String,
-    account_owner_ref: String,
-    bank_ref: Option<String>,
-    supplementary_details: Option<String>,
-    information_to_account_owner: Option<Vec<String>>,
-}
-
-enum StatementLineMark {
-    Credit,
-    Debit,
-    ReversalOfCredit,
-    ReversalOfDebit,
+use crate::dialect::*;
+pub use crate::mt_940_customer_statement_message::account_identification::*;
+pub use crate::mt_940_customer_statement_message::amount::*;
+pub use crate::mt_940_customer_statement_message::balance::*;
+pub use crate::mt_940_customer_statement_message::date::*;
+pub use crate::mt_940_customer_statement_message::iso_4217::*;
+pub use crate::mt_940_customer_statement_message::related_reference::*;
+pub use crate::mt_940_customer_statement_message::statement_line::*;
+pub use crate::mt_940_customer_statement_message::statement_sequence_number::*;
+pub use crate::mt_940_customer_statement_message::transaction_reference_number::*;
+use crate::text_encoding::*;
+use chrono::NaiveDate;
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::iter::Peekable;
+
+const TEXT_BLOCK_START: &str = "{4:";
+const TEXT_BLOCK_END: &str = "-}";
+
+#[derive(Debug, PartialEq)]
+pub struct Mt940CustomerStatementMessage<'a> {
+    transaction_reference_number: TransactionReferenceNumber<'a>,
+    related_reference: Option<RelatedReference<'a>>,
+    account_identification: AccountIdentification<'a>,
+    statement_sequence_no: Option<StatementSequenceNumber>,
+    opening_balance: Balance<'a>,
+    statement_lines: Option<Vec<StatementLine<'a>>>,
+    closing_balance: Balance<'a>,
+    closing_available_balance: Option<Balance<'a>>,
+    forward_available_balances: Option<Vec<Balance<'a>>>,
+    information_to_account_owner: Option<Vec<Cow<'a, str>>>,
+    information_format: InformationFormat,
+}
+
+impl<'a> Mt940CustomerStatementMessage<'a> {
+    pub fn transaction_reference_number(&self) -> &TransactionReferenceNumber<'a> {
+        &self.transaction_reference_number
+    }
+
+    pub fn related_reference(&self) -> Option<&RelatedReference<'a>> {
+        self.related_reference.as_ref()
+    }
+
+    pub fn account_identification(&self) -> &AccountIdentification<'a> {
+        &self.account_identification
+    }
+
+    pub fn statement_sequence_no(&self) -> Option<&StatementSequenceNumber> {
+        self.statement_sequence_no.as_ref()
+    }
+
+    pub fn opening_balance(&self) -> &Balance<'a> {
+        &self.opening_balance
+    }
+
+    pub fn statement_lines(&self) -> &[StatementLine<'a>] {
+        self.statement_lines.as_deref().unwrap_or_default()
+    }
+
+    pub fn closing_balance(&self) -> &Balance<'a> {
+        &self.closing_balance
+    }
+
+    pub fn closing_available_balance(&self) -> Option<&Balance<'a>> {
+        self.closing_available_balance.as_ref()
+    }
+
+    pub fn forward_available_balances(&self) -> &[Balance<'a>] {
+        self.forward_available_balances
+            .as_deref()
+            .unwrap_or_default()
+    }
+
+    pub fn projected_available_balance(&self, date: NaiveDate) -> Option<&Balance<'a>> {
+        self.forward_available_balances()
+            .iter()
+            .take_while(|balance| balance.date().value() <= date)
+            .last()
+            .or(self
+                .closing_available_balance
+                .as_ref()
+                .filter(|balance| balance.date().value() <= date))
+    }
+
+    pub fn information_to_account_owner(&self) -> Option<&[Cow<'a, str>]> {
+        self.information_to_account_owner.as_deref()
+    }
+
+    pub fn information_format(&self) -> InformationFormat {
+        self.information_format
+    }
+
+    pub fn into_owned(self) -> Mt940CustomerStatementMessage<'static> {
+        Mt940CustomerStatementMessage {
+            transaction_reference_number: self.transaction_reference_number.into_owned(),
+            related_reference: self.related_reference.map(RelatedReference::into_owned),
+            account_identification: self.account_identification.into_owned(),
+            statement_sequence_no: self.statement_sequence_no,
+            opening_balance: self.opening_balance.into_owned(),
+            statement_lines: self.statement_lines.map(|statement_lines| {
+                statement_lines
+                    .into_iter()
+                    .map(StatementLine::into_owned)
+                    .collect()
+            }),
+            closing_balance: self.closing_balance.into_owned(),
+            closing_available_balance: self.closing_available_balance.map(Balance::into_owned),
+            forward_available_balances: self
+                .forward_available_balances
+                .map(|balances| balances.into_iter().map(Balance::into_owned).collect()),
+            information_to_account_owner: self.information_to_account_owner.map(|information| {
+                information
+                    .into_iter()
+                    .map(|line| Cow::Owned(line.into_owned()))
+                    .collect()
+            }),
+            information_format: self.information_format,
+        }
+    }
+
+    pub fn parse_bytes(
+        bytes: &[u8],
+        encoding: Option<TextEncoding>,
+    ) -> Result<Mt940CustomerStatementMessage<'static>, Mt940CustomerStatementMessageParseError>
+    {
+        let encoding = encoding.unwrap_or_else(|| TextEncoding::detect(bytes));
+        let value = encoding
+            .decode(bytes)
+            .map_err(Mt940CustomerStatementMessageParseError::InvalidEncoding)?;
+        Mt940CustomerStatementMessage::try_from(value.as_ref())
+            .map(Mt940CustomerStatementMessage::into_owned)
+    }
+
+    pub fn parse_with_dialect(
+        value: &'a str,
+        dialect: &dyn Dialect,
+    ) -> Result<Self, Mt940CustomerStatementMessageParseError> {
+        Self::parse(value, dialect, None)
+    }
+
+    pub fn parse_recovering(
+        value: &'a str,
+        dialect: &dyn Dialect,
+    ) -> Result<RecoveredMessage<'a>, Mt940CustomerStatementMessageParseError> {
+        let mut errors = Vec::new();
+        let message = Self::parse(value, dialect, Some(&mut errors))?;
+        Ok(RecoveredMessage { message, errors })
+    }
+
+    fn parse(
+        value: &'a str,
+        dialect: &dyn Dialect,
+        mut errors: Option<&mut Vec<RecoveredError<'a>>>,
+    ) -> Result<Self, Mt940CustomerStatementMessageParseError> {
+        let mut fields = fields(value, dialect)?;
+        if fields.peek().is_none() {
+            return Err(Mt940CustomerStatementMessageParseError::Empty);
+        }
+
+        let transaction_reference_number = TransactionReferenceNumber::parse(
+            expect_field(&mut fields, "20")?,
+            dialect.transaction_reference_number_max_length(),
+        )
+        .map_err(Mt940CustomerStatementMessageParseError::TransactionReferenceNumber)?;
+        let related_reference = match optional_field(&mut fields, &["21"]) {
+            Some(value) => recover(
+                errors.as_deref_mut(),
+                "21",
+                value,
+                RelatedReference::parse(value, dialect.related_reference_max_length())
+                    .map_err(Mt940CustomerStatementMessageParseError::RelatedReference),
+            )?,
+            None => None,
+        };
+        let account_identification =
+            AccountIdentification::try_from(expect_field(&mut fields, "25")?)
+                .map_err(Mt940CustomerStatementMessageParseError::AccountIdentification)?;
+        let statement_sequence_no = match optional_field(&mut fields, &["28C"]) {
+            None if dialect.requires_statement_sequence_number() => {
+                return Err(Mt940CustomerStatementMessageParseError::MissingField("28C"));
+            }
+            None => None,
+            Some(value) => recover(
+                errors.as_deref_mut(),
+                "28C",
+                value,
+                StatementSequenceNumber::try_from(value)
+                    .map_err(Mt940CustomerStatementMessageParseError::StatementSequenceNumber),
+            )?,
+        };
+        let balance_max_length = dialect.balance_max_length();
+        let opening_balance =
+            Balance::parse(expect_balance(&mut fields, "60")?, balance_max_length)
+                .map_err(Mt940CustomerStatementMessageParseError::OpeningBalance)?;
+
+        let mut statement_lines = Vec::new();
+        loop {
+            if let Some(value) = optional_field(&mut fields, &["61"]) {
+                let statement_line = recover(
+                    errors.as_deref_mut(),
+                    "61",
+                    value,
+                    StatementLine::try_from(value)
+                        .map_err(Mt940CustomerStatementMessageParseError::StatementLine),
+                )?;
+                let information = optional_field(&mut fields, &["86"]);
+                match (statement_line, errors.as_deref_mut()) {
+                    (Some(mut statement_line), _) => {
+                        if let Some(information) = information {
+                            statement_line
+                                .set_information_to_account_owner(information_lines(information));
+                        }
+                        statement_lines.push(statement_line);
+                    }
+                    (None, Some(errors)) => {
+                        if let Some(error) = errors.last_mut() {
+                            error.information = information.map(Cow::Borrowed);
+                        }
+                    }
+                    (None, None) => {}
+                }
+                continue;
+            }
+            match fields.next_if(|(tag, _)| errors.is_some() && !tag.starts_with("62")) {
+                Some((tag, value)) => {
+                    recover::<()>(
+                        errors.as_deref_mut(),
+                        tag,
+                        value,
+                        Err(Mt940CustomerStatementMessageParseError::UnexpectedField(
+                            tag.to_string(),
+                        )),
+                    )?;
+                }
+                None => break,
+            }
+        }
+
+        let closing_balance =
+            Balance::parse(expect_balance(&mut fields, "62")?, balance_max_length)
+                .map_err(Mt940CustomerStatementMessageParseError::ClosingBalance)?;
+        for statement_line in &mut statement_lines {
+            statement_line
+                .resolve_entry_date_within(opening_balance.date(), closing_balance.date());
+        }
+        let closing_available_balance = match optional_field(&mut fields, &["64"]) {
+            Some(value) => recover(
+                errors.as_deref_mut(),
+                "64",
+                value,
+                Balance::parse(value, balance_max_length)
+                    .map_err(Mt940CustomerStatementMessageParseError::ClosingAvailableBalance),
+            )?,
+            None => None,
+        };
+        let mut forward_available_balances: Vec<Balance<'a>> = Vec::new();
+        while let Some(value) = optional_field(&mut fields, &["65"]) {
+            let balance = Balance::parse(value, balance_max_length)
+                .map_err(Mt940CustomerStatementMessageParseError::ForwardAvailableBalance)
+                .and_then(|balance| {
+                    check_forward_available_balance(
+                        &balance,
+                        opening_balance.currency_code(),
+                        forward_available_balances.last(),
+                    )
+                    .map(|_| balance)
+                });
+            forward_available_balances.extend(recover(
+                errors.as_deref_mut(),
+                "65",
+                value,
+                balance,
+            )?);
+        }
+        let information_to_account_owner =
+            optional_field(&mut fields, &["86"]).map(information_lines);
+
+        for (tag, value) in fields {
+            recover::<()>(
+                errors.as_deref_mut(),
+                tag,
+                value,
+                Err(Mt940CustomerStatementMessageParseError::UnexpectedField(
+                    tag.to_string(),
+                )),
+            )?;
+        }
+
+        Ok(Self {
+            transaction_reference_number,
+            related_reference,
+            account_identification,
+            statement_sequence_no,
+            opening_balance,
+            statement_lines: (!statement_lines.is_empty()).then_some(statement_lines),
+            closing_balance,
+            closing_available_balance,
+            forward_available_balances: (!forward_available_balances.is_empty())
+                .then_some(forward_available_balances),
+            information_to_account_owner,
+            information_format: dialect.information_format(),
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Mt940CustomerStatementMessage<'a> {
+    type Error = Mt940CustomerStatementMessageParseError;
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        Self::parse_with_dialect(value, &Standard)
+    }
+}
+
+fn check_forward_available_balance(
+    balance: &Balance<'_>,
+    currency_code: &CurrencyCode<'_>,
+    previous: Option<&Balance<'_>>,
+) -> Result<(), Mt940CustomerStatementMessageParseError> {
+    if balance.currency_code() != currency_code {
+        return Err(
+            Mt940CustomerStatementMessageParseError::ForwardAvailableBalanceCurrencyMismatch(
+                currency_code.to_string(),
+                balance.currency_code().to_string(),
+            ),
+        );
+    }
+    if previous.is_some_and(|previous| previous.date().value() >= balance.date().value()) {
+        return Err(
+            Mt940CustomerStatementMessageParseError::UnorderedForwardAvailableBalance(
+                *balance.date(),
+            ),
+        );
+    }
+    Ok(())
+}
+
+fn recover<'a, T>(
+    errors: Option<&mut Vec<RecoveredError<'a>>>,
+    tag: &'a str,
+    value: &'a str,
+    result: Result<T, Mt940CustomerStatementMessageParseError>,
+) -> Result<Option<T>, Mt940CustomerStatementMessageParseError> {
+    match (result, errors) {
+        (Ok(result), _) => Ok(Some(result)),
+        (Err(error), Some(errors)) => {
+            errors.push(RecoveredError {
+                tag: Cow::Borrowed(tag),
+                value: Cow::Borrowed(value),
+                information: None,
+                error,
+            });
+            Ok(None)
+        }
+        (Err(error), None) => Err(error),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RecoveredMessage<'a> {
+    message: Mt940CustomerStatementMessage<'a>,
+    errors: Vec<RecoveredError<'a>>,
+}
+
+impl<'a> RecoveredMessage<'a> {
+    pub fn message(&self) -> &Mt940CustomerStatementMessage<'a> {
+        &self.message
+    }
+
+    pub fn errors(&self) -> &[RecoveredError<'a>] {
+        &self.errors
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn into_message(self) -> Mt940CustomerStatementMessage<'a> {
+        self.message
+    }
+
+    pub fn into_owned(self) -> RecoveredMessage<'static> {
+        RecoveredMessage {
+            message: self.message.into_owned(),
+            errors: self
+                .errors
+                .into_iter()
+                .map(RecoveredError::into_owned)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RecoveredError<'a> {
+    tag: Cow<'a, str>,
+    value: Cow<'a, str>,
+    information: Option<Cow<'a, str>>,
+    error: Mt940CustomerStatementMessageParseError,
+}
+
+impl RecoveredError<'_> {
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn information(&self) -> Option<&str> {
+        self.information.as_deref()
+    }
+
+    pub fn error(&self) -> &Mt940CustomerStatementMessageParseError {
+        &self.error
+    }
+
+    pub fn into_owned(self) -> RecoveredError<'static> {
+        RecoveredError {
+            tag: Cow::Owned(self.tag.into_owned()),
+            value: Cow::Owned(self.value.into_owned()),
+            information: self
+                .information
+                .map(|information| Cow::Owned(information.into_owned())),
+            error: self.error,
+        }
+    }
+}
+
+impl Display for RecoveredError<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+pub(crate) type Fields<'a> = Peekable<std::vec::IntoIter<(&'a str, &'a str)>>;
+
+pub(crate) fn fields<'a>(
+    value: &'a str,
+    dialect: &dyn Dialect,
+) -> Result<Fields<'a>, Mt940CustomerStatementMessageParseError> {
+    let text = match value.find(TEXT_BLOCK_START) {
+        Some(start) => &value[start + TEXT_BLOCK_START.len()..],
+        None => value,
+    };
+    let mut fields: Vec<(&str, &str)> = Vec::new();
+    let mut current: Option<(&str, usize, usize)> = None;
+    let mut offset = value.len() - text.len();
+
+    for line in text.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        let content = line.trim_end_matches(['\r', '\n']);
+        if dialect.message_terminators().contains(&content.trim_end())
+            || content.starts_with(TEXT_BLOCK_END)
+        {
+            break;
+        }
+        match tag(content) {
+            Some(tag) => {
+                fields.extend(
+                    current
+                        .take()
+                        .map(|(tag, start, end)| (tag, &value[start..end])),
+                );
+                current = Some((tag, line_start + tag.len() + 2, line_start + content.len()));
+            }
+            None => match current.as_mut() {
+                Some((_, _, end)) => *end = line_start + content.len(),
+                None if content.trim().is_empty() => {}
+                None => {
+                    return Err(Mt940CustomerStatementMessageParseError::UnexpectedField(
+                        content.trim().to_string(),
+                    ));
+                }
+            },
+        }
+    }
+    fields.extend(current.map(|(tag, start, end)| (tag, &value[start..end])));
+    fields.retain(|(tag, _)| !dialect.ignored_tags().contains(tag));
+
+    Ok(fields.into_iter().peekable())
+}
+
+fn tag(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix(':')?;
+    let end = rest.find(':')?;
+    let tag = &rest[..end];
+    let bytes = tag.as_bytes();
+    let valid = matches!(bytes.len(), 2 | 3)
+        && bytes[..2].iter().all(u8::is_ascii_digit)
+        && bytes[2..].iter().all(u8::is_ascii_uppercase);
+    valid.then_some(tag)
+}
+
+fn optional_field<'a>(fields: &mut Fields<'a>, tags: &[&str]) -> Option<&'a str> {
+    fields
+        .next_if(|(tag, _)| tags.contains(tag))
+        .map(|(_, value)| value)
+}
+
+fn expect_field<'a>(
+    fields: &mut Fields<'a>,
+    tag: &'static str,
+) -> Result<&'a str, Mt940CustomerStatementMessageParseError> {
+    optional_field(fields, &[tag]).ok_or(Mt940CustomerStatementMessageParseError::MissingField(tag))
+}
+
+fn expect_balance<'a>(
+    fields: &mut Fields<'a>,
+    tag: &'static str,
+) -> Result<&'a str, Mt940CustomerStatementMessageParseError> {
+    let first = format!("{}F", tag);
+    let intermediate = format!("{}M", tag);
+    optional_field(fields, &[first.as_str(), intermediate.as_str()])
+        .ok_or(Mt940CustomerStatementMessageParseError::MissingField(tag))
+}
+
+fn information_lines(value: &str) -> Vec<Cow<'_, str>> {
+    value
+        .lines()
+        .map(|line| Cow::Borrowed(line.trim_end()))
+        .collect()
+}
+
+impl Display for Mt940CustomerStatementMessage<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Transaction reference number: {}",
+            self.transaction_reference_number
+        )?;
+        if let Some(related_reference) = &self.related_reference {
+            writeln!(f, "Related reference: {}", related_reference)?;
+        }
+        writeln!(f, "{}", self.account_identification)?;
+        if let Some(statement_sequence_no) = &self.statement_sequence_no {
+            writeln!(f, "Statement number: {}", statement_sequence_no)?;
+        }
+        writeln!(f, "Opening balance:\n{}", self.opening_balance)?;
+        for statement_line in self.statement_lines() {
+            writeln!(f, "Statement line:\n{}", statement_line)?;
+        }
+        writeln!(f, "Closing balance:\n{}", self.closing_balance)?;
+        if let Some(balance) = &self.closing_available_balance {
+            writeln!(f, "Closing available balance:\n{}", balance)?;
+        }
+        for balance in self.forward_available_balances() {
+            writeln!(f, "Forward available balance:\n{}", balance)?;
+        }
+        if let Some(information) = &self.information_to_account_owner {
+            writeln!(f, "Information to account owner: {}", information.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Mt940CustomerStatementMessageParseError {
+    Empty,
+    InvalidEncoding(TextEncodingError),
+    MissingField(&'static str),
+    UnexpectedField(String),
+    TransactionReferenceNumber(TransactionReferenceNumberParseError),
+    RelatedReference(RelatedReferenceParseError),
+    AccountIdentification(AccountIdentificationParseError),
+    StatementSequenceNumber(StatementSequenceNumberParseError),
+    OpeningBalance(BalanceParseError),
+    StatementLine(StatementLineParseError),
+    ClosingBalance(BalanceParseError),
+    ClosingAvailableBalance(BalanceParseError),
+    ForwardAvailableBalance(BalanceParseError),
+    ForwardAvailableBalanceCurrencyMismatch(String, String),
+    UnorderedForwardAvailableBalance(Date),
+}
+
+impl Display for Mt940CustomerStatementMessageParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mt940CustomerStatementMessageParseError::Empty => write!(f, "Message is empty"),
+            Mt940CustomerStatementMessageParseError::InvalidEncoding(err) => {
+                write!(f, "Message has invalid encoding: {}", err)
+            }
+            Mt940CustomerStatementMessageParseError::MissingField(tag) => {
+                write!(f, "Missing mandatory field :{}:", tag)
+            }
+            Mt940CustomerStatementMessageParseError::UnexpectedField(field) => {
+                write!(f, "Unexpected field {}", field)
+            }
+            Mt940CustomerStatementMessageParseError::TransactionReferenceNumber(err) => {
+                write!(f, ":20: {}", err)
+            }
+            Mt940CustomerStatementMessageParseError::RelatedReference(err) => {
+                write!(f, ":21: {}", err)
+            }
+            Mt940CustomerStatementMessageParseError::AccountIdentification(err) => {
+                write!(f, ":25: {}", err)
+            }
+            Mt940CustomerStatementMessageParseError::StatementSequenceNumber(err) => {
+                write!(f, ":28C: {}", err)
+            }
+            Mt940CustomerStatementMessageParseError::OpeningBalance(err) => {
+                write!(f, ":60a: {}", err)
+            }
+            Mt940CustomerStatementMessageParseError::StatementLine(err) => {
+                write!(f, ":61: {}", err)
+            }
+            Mt940CustomerStatementMessageParseError::ClosingBalance(err) => {
+                write!(f, ":62a: {}", err)
+            }
+            Mt940CustomerStatementMessageParseError::ClosingAvailableBalance(err) => {
+                write!(f, ":64: {}", err)
+            }
+            Mt940CustomerStatementMessageParseError::ForwardAvailableBalance(err) => {
+                write!(f, ":65: {}", err)
+            }
+            Mt940CustomerStatementMessageParseError::ForwardAvailableBalanceCurrencyMismatch(
+                expected,
+                actual,
+            ) => write!(
+                f,
+                ":65: Currency code {} differs from statement currency {}",
+                actual, expected
+            ),
+            Mt940CustomerStatementMessageParseError::UnorderedForwardAvailableBalance(date) => {
+                write!(
+                    f,
+                    ":65: Forward available balance on {} is not after the previous one",
+                    date
+                )
+            }
+        }
+    }
+}
+
+impl Error for Mt940CustomerStatementMessageParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSAGE: &str =
+        "{1:F01BANKDEFFAXXX0000000000}{2:O9401200230307BANKDEFFAXXX00000000002303071200N}{4:
+:20:STMT230306
+:21:NONREF
+:25:DK5030004012345678
+:28C:45/1
+:60F:C230305DKK1000,00
+:61:2303060306D200,50NTRFINV-1001//BR-1
+PAYMENT FOR INVOICE
+:86:INVOICE 1001
+ACME LTD
+:61:230306C50,NCHKNONREF
+:62F:C230306DKK849,50
+:64:C230306DKK849,50
+:65:C230307DKK849,50
+:86:END OF STATEMENT
+-}";
+
+    #[test]
+    fn test_empty_message() {
+        let result = Mt940CustomerStatementMessage::try_from("");
+        assert_eq!(result, Err(Mt940CustomerStatementMessageParseError::Empty));
+        assert_eq!(result.unwrap_err().to_string(), "Message is empty");
+
+        let result = Mt940CustomerStatementMessage::try_from("{1:F01}{4:\n-}");
+        assert_eq!(result, Err(Mt940CustomerStatementMessageParseError::Empty));
+    }
+
+    #[test]
+    fn test_message_missing_field() {
+        let result = Mt940CustomerStatementMessage::try_from(":20:STMT\n:28C:1\n-");
+        assert_eq!(
+            result,
+            Err(Mt940CustomerStatementMessageParseError::MissingField("25"))
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Missing mandatory field :25:"
+        );
+
+        let result = Mt940CustomerStatementMessage::try_from(
+            ":20:STMT\n:25:123\n:28C:1\n:60F:C230305DKK1,00\n-",
+        );
+        assert_eq!(
+            result,
+            Err(Mt940CustomerStatementMessageParseError::MissingField("62"))
+        );
+    }
+
+    #[test]
+    fn test_message_unexpected_field() {
+        let result = Mt940CustomerStatementMessage::try_from(
+            ":20:STMT\n:25:123\n:28C:1\n:60F:C230305DKK1,00\n:62F:C230305DKK1,00\n:99:X\n-",
+        );
+        assert_eq!(
+            result,
+            Err(Mt940CustomerStatementMessageParseError::UnexpectedField(
+                "99".to_string()
+            ))
+        );
+        assert_eq!(result.unwrap_err().to_string(), "Unexpected field 99");
+
+        let result = Mt940CustomerStatementMessage::try_from("GARBAGE\n:20:STMT\n-");
+        assert_eq!(
+            result,
+            Err(Mt940CustomerStatementMessageParseError::UnexpectedField(
+                "GARBAGE".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_message_invalid_field() {
+        let result = Mt940CustomerStatementMessage::try_from(
+            ":20:STMT\n:25:123\n:28C:1\n:60F:C230305DKK1,00\n:61:230306X1,00NTRFREF\n-",
+        );
+        assert_eq!(
+            result,
+            Err(Mt940CustomerStatementMessageParseError::StatementLine(
+                StatementLineParseError::InvalidMark(StatementLineMarkParseError::InvalidValue)
+            ))
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            ":61: Invalid statement line debit/credit mark"
+        );
+    }
+
+    #[test]
+    fn test_valid_message() {
+        let message = Mt940CustomerStatementMessage::try_from(MESSAGE).unwrap();
+        assert_eq!(
+            message.transaction_reference_number().as_str(),
+            "STMT230306"
+        );
+        assert_eq!(message.related_reference().unwrap().as_str(), "NONREF");
+        assert_eq!(
+            message.account_identification().as_str(),
+            "DK5030004012345678"
+        );
+        assert!(message.account_identification().validate().is_err());
+        assert_eq!(message.statement_sequence_no().unwrap().to_string(), "45/1");
+        assert_eq!(
+            message.opening_balance(),
+            &Balance::try_from("C230305DKK1000,00").unwrap()
+        );
+        assert_eq!(message.statement_lines().len(), 2);
+
+        let statement_line = &message.statement_lines()[0];
+        assert_eq!(statement_line.account_owner_ref(), "INV-1001");
+        assert_eq!(statement_line.bank_ref(), Some("BR-1"));
+        assert_eq!(
+            statement_line.supplementary_details(),
+            Some("PAYMENT FOR INVOICE")
+        );
+        assert_eq!(
+            statement_line.information_to_account_owner(),
+            Some(&["INVOICE 1001".into(), "ACME LTD".into()][..])
+        );
+        assert_eq!(
+            message.statement_lines()[1].information_to_account_owner(),
+            None
+        );
+
+        assert_eq!(
+            message.closing_balance(),
+            &Balance::try_from("C230306DKK849,50").unwrap()
+        );
+        assert!(message.closing_available_balance().is_some());
+        assert_eq!(message.forward_available_balances().len(), 1);
+        assert_eq!(
+            message.information_to_account_owner(),
+            Some(&["END OF STATEMENT".into()][..])
+        );
+    }
+
+    #[test]
+    fn test_valid_message_without_blocks() {
+        let message = Mt940CustomerStatementMessage::try_from(
+            ":20:STMT\r\n:25:123\r\n:28C:1\r\n:60M:C230305DKK1,00\r\n:62M:C230305DKK1,00\r\n-\r\n",
+        )
+        .unwrap();
+        assert_eq!(message.transaction_reference_number().as_str(), "STMT");
+        assert!(message.statement_lines().is_empty());
+        assert_eq!(message.information_to_account_owner(), None);
+    }
+
+    #[test]
+    fn test_message_with_back_valued_statement_line() {
+        let message = Mt940CustomerStatementMessage::try_from(
+            ":20:STMT\n:25:123\n:28C:1\n:60F:C230214DKK100,00\n:61:2301010215D10,00NTRFNONREF\n:62F:C230215DKK90,00\n-",
+        )
+        .unwrap();
+        let statement_line = &message.statement_lines()[0];
+        assert_eq!(
+            statement_line.value_date(),
+            &Date::new(NaiveDate::from_ymd_opt(2023, 1, 1).unwrap())
+        );
+        assert_eq!(
+            statement_line.entry_date(),
+            Some(&Date::new(NaiveDate::from_ymd_opt(2023, 2, 15).unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_message_with_dialect() {
+        let message = ":20:STMT
+:21:RELATED-REFERENCE-OVER-16
+:25:123
+:60F:C230305RUB1,00
+:62F:C230305RUB1,00
+}";
+        let result = Mt940CustomerStatementMessage::try_from(message);
+        assert!(matches!(
+            result,
+            Err(Mt940CustomerStatementMessageParseError::RelatedReference(
+                RelatedReferenceParseError::TooLong(16)
+            ))
+        ));
+
+        let message =
+            Mt940CustomerStatementMessage::parse_with_dialect(message, &Sberbank).unwrap();
+        assert_eq!(
+            message.related_reference().unwrap().as_str(),
+            "RELATED-REFERENCE-OVER-16"
+        );
+        assert_eq!(message.statement_sequence_no(), None);
+        assert_eq!(
+            message.information_format(),
+            InformationFormat::Unstructured
+        );
+    }
+
+    #[test]
+    fn test_message_with_detected_dialect() {
+        let message =
+            "{1:F01OWNBDEFFAXXX0000000000}{2:O9401200230307DEUTDEFFAXXX00000000002303071200N}{4:
+:20:STMT
+:25:123
+:28C:1
+:13D:2303071200+0100
+:60F:C230305EUR1,00
+:61:230306C1,00NTRFNONREF
+:86:166?00GUTSCHRIFT?20INVOICE 1001
+:62F:C230306EUR2,00
+:90C:1EUR1,00
+-}";
+        let result = Mt940CustomerStatementMessage::try_from(message);
+        assert_eq!(
+            result,
+            Err(Mt940CustomerStatementMessageParseError::MissingField("60"))
+        );
+
+        let message =
+            Mt940CustomerStatementMessage::parse_with_dialect(message, detect_dialect(message))
+                .unwrap();
+        assert_eq!(
+            message.information_format(),
+            InformationFormat::QuestionMarkSubfields
+        );
+        let information = message.statement_lines()[0]
+            .information_to_account_owner()
+            .unwrap();
+        assert_eq!(
+            message.information_format().subfields(information),
+            vec![
+                InformationSubfield::new("", "166"),
+                InformationSubfield::new("00", "GUTSCHRIFT"),
+                InformationSubfield::new("20", "INVOICE 1001"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_message_recovering() {
+        let message = ":20:STMT
+:25:123
+:28C:1/X
+:60F:C230305DKK1000,00
+:61:230306D200,50NTRFINV-1001
+:86:INVOICE 1001
+:61:230306X1,00NTRFBROKEN
+:86:BROKEN PAYMENT
+:99:STRAY
+:61:230306C50,NCHKNONREF
+:62F:C230306DKK849,50
+:65:C230307DKK849X50
+:86:END OF STATEMENT
+:86:DUPLICATE
+-";
+        assert!(Mt940CustomerStatementMessage::try_from(message).is_err());
+
+        let recovered =
+            Mt940CustomerStatementMessage::parse_recovering(message, &Standard).unwrap();
+        assert!(!recovered.is_complete());
+        let errors = recovered
+            .errors()
+            .iter()
+            .map(|error| (error.tag(), error.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec![
+                ("28C", "1/X"),
+                ("61", "230306X1,00NTRFBROKEN"),
+                ("99", "STRAY"),
+                ("65", "C230307DKK849X50"),
+                ("86", "DUPLICATE"),
+            ]
+        );
+        assert_eq!(
+            recovered.errors()[1].to_string(),
+            ":61: Invalid statement line debit/credit mark"
+        );
+        assert_eq!(recovered.errors()[1].information(), Some("BROKEN PAYMENT"));
+        assert_eq!(recovered.errors()[0].information(), None);
+
+        let message = recovered.into_message();
+        assert_eq!(message.statement_sequence_no(), None);
+        assert_eq!(message.statement_lines().len(), 2);
+        assert_eq!(
+            message.statement_lines()[0].information_to_account_owner(),
+            Some(&["INVOICE 1001".into()][..])
+        );
+        assert_eq!(
+            message.statement_lines()[1].information_to_account_owner(),
+            None
+        );
+        assert!(message.forward_available_balances().is_empty());
+        assert_eq!(
+            message.information_to_account_owner(),
+            Some(&["END OF STATEMENT".into()][..])
+        );
+    }
+
+    #[test]
+    fn test_message_recovering_fatal_error() {
+        let result = Mt940CustomerStatementMessage::parse_recovering(
+            ":20:STMT\n:25:123\n:28C:1\n:60F:C230305DKK1X00\n:62F:C230305DKK1,00\n-",
+            &Standard,
+        );
+        assert!(matches!(
+            result,
+            Err(Mt940CustomerStatementMessageParseError::OpeningBalance(_))
+        ));
+
+        let recovered =
+            Mt940CustomerStatementMessage::parse_recovering(MESSAGE, &Standard).unwrap();
+        assert!(recovered.is_complete());
+        assert_eq!(
+            recovered.message(),
+            &Mt940CustomerStatementMessage::try_from(MESSAGE).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_forward_available_balances() {
+        let text = MESSAGE.replace(
+            ":65:C230307DKK849,50",
+            ":65:C230307DKK849,50\n:65:C230309DKK700,00\n:65:C230310DKK650,00",
+        );
+        let message = Mt940CustomerStatementMessage::try_from(text.as_str()).unwrap();
+        assert_eq!(message.forward_available_balances().len(), 3);
+
+        let date = |day| NaiveDate::from_ymd_opt(2023, 3, day).unwrap();
+        let projected = |day| {
+            message
+                .projected_available_balance(date(day))
+                .map(|balance| balance.amount().to_string())
+        };
+        assert_eq!(projected(6), Some("849.50".to_string()));
+        assert_eq!(projected(8), Some("849.50".to_string()));
+        assert_eq!(projected(9), Some("700.00".to_string()));
+        assert_eq!(projected(31), Some("650.00".to_string()));
+        assert_eq!(projected(5), None);
+
+        let text = text.replace(":64:C230306DKK849,50\n", "");
+        let message = Mt940CustomerStatementMessage::try_from(text.as_str()).unwrap();
+        assert_eq!(message.closing_available_balance(), None);
+        assert_eq!(message.projected_available_balance(date(6)), None);
+        assert_eq!(
+            message
+                .projected_available_balance(date(7))
+                .map(|balance| balance.amount().to_string()),
+            Some("849.50".to_string())
+        );
+
+        let text = MESSAGE.replace(
+            ":65:C230307DKK849,50",
+            ":65:C230307DKK849,50\n:65:C230307DKK700,00",
+        );
+        let result = Mt940CustomerStatementMessage::try_from(text.as_str());
+        assert_eq!(
+            result,
+            Err(
+                Mt940CustomerStatementMessageParseError::UnorderedForwardAvailableBalance(
+                    Date::new(date(7))
+                )
+            )
+        );
+
+        let text = MESSAGE.replace(
+            ":65:C230307DKK849,50",
+            ":65:C230307DKK849,50\n:65:C230308EUR700,00\n:65:C230309DKK650,00",
+        );
+        let result = Mt940CustomerStatementMessage::try_from(text.as_str());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            ":65: Currency code EUR differs from statement currency DKK"
+        );
+
+        let recovered = Mt940CustomerStatementMessage::parse_recovering(&text, &Standard).unwrap();
+        assert_eq!(recovered.errors().len(), 1);
+        assert_eq!(recovered.errors()[0].value(), "C230308EUR700,00");
+        assert_eq!(recovered.message().forward_available_balances().len(), 2);
+    }
+
+    #[test]
+    fn test_message_from_bytes() {
+        let text = MESSAGE.replace("ACME LTD", "ООО РОМАШКА");
+        let bytes = TextEncoding::Windows1251.encode(&text).unwrap();
+        let message = Mt940CustomerStatementMessage::parse_bytes(&bytes, None).unwrap();
+        assert_eq!(
+            message.statement_lines()[0].information_to_account_owner(),
+            Some(&["INVOICE 1001".into(), "ООО РОМАШКА".into()][..])
+        );
+
+        let bytes = TextEncoding::Ebcdic.encode(MESSAGE).unwrap();
+        let message =
+            Mt940CustomerStatementMessage::parse_bytes(&bytes, Some(TextEncoding::Ebcdic)).unwrap();
+        assert_eq!(message.statement_lines().len(), 2);
+
+        let result =
+            Mt940CustomerStatementMessage::parse_bytes(b":20:\xFF", Some(TextEncoding::Utf8));
+        assert_eq!(
+            result,
+            Err(Mt940CustomerStatementMessageParseError::InvalidEncoding(
+                TextEncodingError::UndecodableBytes(vec![4])
+            ))
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Message has invalid encoding: Undecodable bytes at offsets 4"
+        );
+    }
 }
@@ -1,14 +1,74 @@
+mod bic;
+mod iban;
+mod russian_account;
+
+pub use crate::mt_940_customer_statement_message::account_identification::bic::*;
+pub use crate::mt_940_customer_statement_message::account_identification::iban::*;
+pub use crate::mt_940_customer_statement_message::account_identification::russian_account::*;
+use std::borrow::Cow;
 use std::error::Error;
 use std::fmt::Display;
 
 const ACCOUNT_IDENTIFICATION_MAX_LENGTH: usize = 35;
+const UK_SORT_CODE_LENGTH: usize = 6;
+const UK_ACCOUNT_NUMBER_LENGTH: usize = 8;
 
 #[derive(Debug, PartialEq)]
-pub(super) struct AccountIdentification(String);
+pub struct AccountIdentification<'a> {
+    value: Cow<'a, str>,
+    account_number: AccountNumber<'a>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccountNumber<'a> {
+    Iban(Option<Bic<'a>>, Iban<'a>),
+    RussianAccount(Option<Bik<'a>>, RussianAccount<'a>),
+    UkSortCode(Cow<'a, str>, Cow<'a, str>),
+    Other(Cow<'a, str>),
+}
+
+impl AccountIdentification<'_> {
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    pub fn account_number(&self) -> &AccountNumber<'_> {
+        &self.account_number
+    }
+
+    pub fn validate(&self) -> Result<(), AccountIdentificationParseError> {
+        AccountNumber::try_from(self.value.as_ref()).map(|_| ())
+    }
+
+    pub fn into_owned(self) -> AccountIdentification<'static> {
+        AccountIdentification {
+            value: Cow::Owned(self.value.into_owned()),
+            account_number: self.account_number.into_owned(),
+        }
+    }
+}
+
+impl AccountNumber<'_> {
+    pub fn into_owned(self) -> AccountNumber<'static> {
+        match self {
+            AccountNumber::Iban(bic, iban) => {
+                AccountNumber::Iban(bic.map(Bic::into_owned), iban.into_owned())
+            }
+            AccountNumber::RussianAccount(bik, account) => {
+                AccountNumber::RussianAccount(bik.map(Bik::into_owned), account.into_owned())
+            }
+            AccountNumber::UkSortCode(sort_code, account_number) => AccountNumber::UkSortCode(
+                Cow::Owned(sort_code.into_owned()),
+                Cow::Owned(account_number.into_owned()),
+            ),
+            AccountNumber::Other(value) => AccountNumber::Other(Cow::Owned(value.into_owned())),
+        }
+    }
+}
 
-impl TryFrom<&str> for AccountIdentification {
+impl<'a> TryFrom<&'a str> for AccountIdentification<'a> {
     type Error = AccountIdentificationParseError;
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
         let value = value.trim();
         if value.is_empty() {
             return Err(AccountIdentificationParseError::Empty);
@@ -16,20 +76,87 @@ impl TryFrom<&str> for AccountIdentification {
         if value.len() > ACCOUNT_IDENTIFICATION_MAX_LENGTH {
             return Err(AccountIdentificationParseError::TooLong);
         }
-        Ok(AccountIdentification(value.to_string()))
+        Ok(AccountIdentification {
+            value: Cow::Borrowed(value),
+            account_number: AccountNumber::try_from(value)
+                .unwrap_or(AccountNumber::Other(Cow::Borrowed(value))),
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a str> for AccountNumber<'a> {
+    type Error = AccountIdentificationParseError;
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        match value.split_once('/') {
+            Some((bic, iban)) if Bic::is_bic_like(bic) && Iban::is_iban_like(iban) => Ok(
+                AccountNumber::Iban(Some(Bic::try_from(bic)?), Iban::try_from(iban)?),
+            ),
+            Some((bik, account))
+                if Bik::is_bik_like(bik) && RussianAccount::is_russian_account_like(account) =>
+            {
+                let bik = Bik::try_from(bik)?;
+                let account = RussianAccount::try_from(account)?;
+                account.check_control_key(&bik)?;
+                Ok(AccountNumber::RussianAccount(Some(bik), account))
+            }
+            _ if Iban::is_iban_like(value) => Ok(AccountNumber::Iban(None, Iban::try_from(value)?)),
+            _ if RussianAccount::is_russian_account_like(value) => Ok(
+                AccountNumber::RussianAccount(None, RussianAccount::try_from(value)?),
+            ),
+            _ => Ok(uk_sort_code(value).unwrap_or(AccountNumber::Other(Cow::Borrowed(value)))),
+        }
     }
 }
 
-impl Display for AccountIdentification {
+fn uk_sort_code(value: &str) -> Option<AccountNumber<'_>> {
+    let (sort_code, account_number) = value.split_once(['/', ' '])?;
+    let digits = sort_code.split('-').collect::<Vec<_>>();
+    let is_sort_code = match digits.as_slice() {
+        [sort_code] => sort_code.len() == UK_SORT_CODE_LENGTH,
+        [_, _, _] => digits.iter().all(|part| part.len() == 2),
+        _ => false,
+    } && digits
+        .iter()
+        .all(|part| part.bytes().all(|b| b.is_ascii_digit()));
+    let is_account_number = account_number.len() == UK_ACCOUNT_NUMBER_LENGTH
+        && account_number.bytes().all(|b| b.is_ascii_digit());
+    (is_sort_code && is_account_number).then_some(AccountNumber::UkSortCode(
+        Cow::Borrowed(sort_code),
+        Cow::Borrowed(account_number),
+    ))
+}
+
+impl Display for AccountIdentification<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Account identification: {}", self.0)
+        write!(f, "Account identification: {}", self.value)
     }
 }
 
 #[derive(Debug, PartialEq)]
-pub(super) enum AccountIdentificationParseError {
+pub enum AccountIdentificationParseError {
     Empty,
     TooLong,
+    InvalidIban(IbanParseError),
+    InvalidBic(BicParseError),
+    InvalidRussianAccount(RussianAccountParseError),
+}
+
+impl From<IbanParseError> for AccountIdentificationParseError {
+    fn from(err: IbanParseError) -> Self {
+        AccountIdentificationParseError::InvalidIban(err)
+    }
+}
+
+impl From<BicParseError> for AccountIdentificationParseError {
+    fn from(err: BicParseError) -> Self {
+        AccountIdentificationParseError::InvalidBic(err)
+    }
+}
+
+impl From<RussianAccountParseError> for AccountIdentificationParseError {
+    fn from(err: RussianAccountParseError) -> Self {
+        AccountIdentificationParseError::InvalidRussianAccount(err)
+    }
 }
 
 impl Display for AccountIdentificationParseError {
@@ -43,6 +170,15 @@ impl Display for AccountIdentificationParseError {
                 "Account identification exceeds maximum length of {} characters",
                 ACCOUNT_IDENTIFICATION_MAX_LENGTH
             ),
+            AccountIdentificationParseError::InvalidIban(err) => {
+                write!(f, "Account identification is invalid: {}", err)
+            }
+            AccountIdentificationParseError::InvalidBic(err) => {
+                write!(f, "Account identification is invalid: {}", err)
+            }
+            AccountIdentificationParseError::InvalidRussianAccount(err) => {
+                write!(f, "Account identification is invalid: {}", err)
+            }
         }
     }
 }
@@ -65,9 +201,8 @@ mod tests {
 
     #[test]
     fn test_long_account_identification() {
-        let result = AccountIdentification::try_from(
-            "1".repeat(ACCOUNT_IDENTIFICATION_MAX_LENGTH + 1).as_str(),
-        );
+        let value = "1".repeat(ACCOUNT_IDENTIFICATION_MAX_LENGTH + 1);
+        let result = AccountIdentification::try_from(value.as_str());
         assert_eq!(result, Err(AccountIdentificationParseError::TooLong));
         assert_eq!(
             result.unwrap_err().to_string(),
@@ -81,10 +216,137 @@ mod tests {
     #[test]
     fn test_valid_account_identification() {
         let result = AccountIdentification::try_from("12345DK");
-        assert_eq!(result, Ok(AccountIdentification("12345DK".to_string())));
+        assert_eq!(
+            result,
+            Ok(AccountIdentification {
+                value: "12345DK".into(),
+                account_number: AccountNumber::Other("12345DK".into()),
+            })
+        );
         assert_eq!(
             result.unwrap().to_string(),
             "Account identification: 12345DK"
         );
     }
+
+    #[test]
+    fn test_iban_account_identification() {
+        let account_identification =
+            AccountIdentification::try_from("DEUTDEFF/DE89370400440532013000").unwrap();
+        assert_eq!(
+            account_identification.as_str(),
+            "DEUTDEFF/DE89370400440532013000"
+        );
+        assert_eq!(
+            account_identification.account_number(),
+            &AccountNumber::Iban(
+                Some(Bic::try_from("DEUTDEFF").unwrap()),
+                Iban::try_from("DE89370400440532013000").unwrap()
+            )
+        );
+
+        let account_identification = AccountIdentification::try_from("NL91 ABNA 0417 1643 00");
+        assert_eq!(
+            account_identification.unwrap().account_number(),
+            &AccountNumber::Iban(None, Iban::try_from("NL91ABNA0417164300").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_invalid_iban_account_identification() {
+        let account_identification = AccountIdentification::try_from("DK5030004012345678").unwrap();
+        assert_eq!(account_identification.as_str(), "DK5030004012345678");
+        assert_eq!(
+            account_identification.account_number(),
+            &AccountNumber::Other("DK5030004012345678".into())
+        );
+        let result = account_identification.validate();
+        assert_eq!(
+            result,
+            Err(AccountIdentificationParseError::InvalidIban(
+                IbanParseError::InvalidChecksum
+            ))
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Account identification is invalid: IBAN has invalid checksum"
+        );
+
+        let account_identification =
+            AccountIdentification::try_from("DEUTQQFF/DE89370400440532013000").unwrap();
+        assert_eq!(
+            account_identification.account_number(),
+            &AccountNumber::Other("DEUTQQFF/DE89370400440532013000".into())
+        );
+        assert_eq!(
+            account_identification.validate(),
+            Err(AccountIdentificationParseError::InvalidBic(
+                BicParseError::UnknownCountry
+            ))
+        );
+
+        let account_identification =
+            AccountIdentification::try_from("DEUTDEFF/DE89370400440532013000").unwrap();
+        assert_eq!(account_identification.validate(), Ok(()));
+        assert_eq!(
+            AccountIdentification::try_from("12345DK")
+                .unwrap()
+                .validate(),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_russian_account_identification() {
+        let account_identification =
+            AccountIdentification::try_from("044525225/40702810938000012345").unwrap();
+        assert_eq!(
+            account_identification.account_number(),
+            &AccountNumber::RussianAccount(
+                Some(Bik::try_from("044525225").unwrap()),
+                RussianAccount::try_from("40702810938000012345").unwrap()
+            )
+        );
+
+        let account_identification =
+            AccountIdentification::try_from("40702810400000000001").unwrap();
+        assert!(matches!(
+            account_identification.account_number(),
+            AccountNumber::RussianAccount(None, _)
+        ));
+
+        let account_identification =
+            AccountIdentification::try_from("044525225/40702810400000000001").unwrap();
+        assert!(matches!(
+            account_identification.account_number(),
+            AccountNumber::Other(_)
+        ));
+        assert_eq!(
+            account_identification.validate(),
+            Err(AccountIdentificationParseError::InvalidRussianAccount(
+                RussianAccountParseError::InvalidControlKey
+            ))
+        );
+    }
+
+    #[test]
+    fn test_uk_sort_code_account_identification() {
+        let account_identification = AccountIdentification::try_from("60-16-13 31926819").unwrap();
+        assert_eq!(
+            account_identification.account_number(),
+            &AccountNumber::UkSortCode("60-16-13".into(), "31926819".into())
+        );
+
+        let account_identification = AccountIdentification::try_from("601613/31926819").unwrap();
+        assert_eq!(
+            account_identification.account_number(),
+            &AccountNumber::UkSortCode("601613".into(), "31926819".into())
+        );
+
+        let account_identification = AccountIdentification::try_from("60-16 31926819").unwrap();
+        assert!(matches!(
+            account_identification.account_number(),
+            AccountNumber::Other(_)
+        ));
+    }
 }
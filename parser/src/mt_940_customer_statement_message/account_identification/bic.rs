@@ -0,0 +1,157 @@
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+const BIC_SHORT_LENGTH: usize = 8;
+const BIC_LONG_LENGTH: usize = 11;
+const COUNTRY_CODES: [&str; 250] = [
+    "AD", "AE", "AF", "AG", "AI", "AL", "AM", "AO", "AQ", "AR", "AS", "AT", "AU", "AW", "AX", "AZ",
+    "BA", "BB", "BD", "BE", "BF", "BG", "BH", "BI", "BJ", "BL", "BM", "BN", "BO", "BQ", "BR", "BS",
+    "BT", "BV", "BW", "BY", "BZ", "CA", "CC", "CD", "CF", "CG", "CH", "CI", "CK", "CL", "CM", "CN",
+    "CO", "CR", "CU", "CV", "CW", "CX", "CY", "CZ", "DE", "DJ", "DK", "DM", "DO", "DZ", "EC", "EE",
+    "EG", "EH", "ER", "ES", "ET", "FI", "FJ", "FK", "FM", "FO", "FR", "GA", "GB", "GD", "GE", "GF",
+    "GG", "GH", "GI", "GL", "GM", "GN", "GP", "GQ", "GR", "GS", "GT", "GU", "GW", "GY", "HK", "HM",
+    "HN", "HR", "HT", "HU", "ID", "IE", "IL", "IM", "IN", "IO", "IQ", "IR", "IS", "IT", "JE", "JM",
+    "JO", "JP", "KE", "KG", "KH", "KI", "KM", "KN", "KP", "KR", "KW", "KY", "KZ", "LA", "LB", "LC",
+    "LI", "LK", "LR", "LS", "LT", "LU", "LV", "LY", "MA", "MC", "MD", "ME", "MF", "MG", "MH", "MK",
+    "ML", "MM", "MN", "MO", "MP", "MQ", "MR", "MS", "MT", "MU", "MV", "MW", "MX", "MY", "MZ", "NA",
+    "NC", "NE", "NF", "NG", "NI", "NL", "NO", "NP", "NR", "NU", "NZ", "OM", "PA", "PE", "PF", "PG",
+    "PH", "PK", "PL", "PM", "PN", "PR", "PS", "PT", "PW", "PY", "QA", "RE", "RO", "RS", "RU", "RW",
+    "SA", "SB", "SC", "SD", "SE", "SG", "SH", "SI", "SJ", "SK", "SL", "SM", "SN", "SO", "SR", "SS",
+    "ST", "SV", "SX", "SY", "SZ", "TC", "TD", "TF", "TG", "TH", "TJ", "TK", "TL", "TM", "TN", "TO",
+    "TR", "TT", "TV", "TW", "TZ", "UA", "UG", "UM", "US", "UY", "UZ", "VA", "VC", "VE", "VG", "VI",
+    "VN", "VU", "WF", "WS", "XK", "YE", "YT", "ZA", "ZM", "ZW",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bic<'a>(Cow<'a, str>);
+
+impl<'a> Bic<'a> {
+    pub fn is_bic_like(value: &str) -> bool {
+        (value.len() == BIC_SHORT_LENGTH || value.len() == BIC_LONG_LENGTH)
+            && value.bytes().take(6).all(|b| b.is_ascii_uppercase())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn bank_code(&self) -> &str {
+        &self.0[..4]
+    }
+
+    pub fn country_code(&self) -> &str {
+        &self.0[4..6]
+    }
+
+    pub fn location_code(&self) -> &str {
+        &self.0[6..8]
+    }
+
+    pub fn branch_code(&self) -> Option<&str> {
+        self.0
+            .get(8..)
+            .filter(|branch_code| !branch_code.is_empty())
+    }
+
+    pub fn into_owned(self) -> Bic<'static> {
+        Bic(Cow::Owned(self.0.into_owned()))
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Bic<'a> {
+    type Error = BicParseError;
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        let value = value.trim();
+        if value.len() != BIC_SHORT_LENGTH && value.len() != BIC_LONG_LENGTH {
+            return Err(BicParseError::InvalidLength);
+        }
+        let bytes = value.as_bytes();
+        if !bytes[..6].iter().all(u8::is_ascii_uppercase)
+            || !bytes[6..]
+                .iter()
+                .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+        {
+            return Err(BicParseError::InvalidFormat);
+        }
+        if COUNTRY_CODES.binary_search(&&value[4..6]).is_err() {
+            return Err(BicParseError::UnknownCountry);
+        }
+        Ok(Self(Cow::Borrowed(value)))
+    }
+}
+
+impl Display for Bic<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum BicParseError {
+    InvalidLength,
+    InvalidFormat,
+    UnknownCountry,
+}
+
+impl Display for BicParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BicParseError::InvalidLength => write!(f, "BIC must be 8 or 11 characters long"),
+            BicParseError::InvalidFormat => write!(f, "BIC has invalid format"),
+            BicParseError::UnknownCountry => write!(f, "BIC has unknown country code"),
+        }
+    }
+}
+
+impl Error for BicParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_country_codes_are_sorted() {
+        assert!(COUNTRY_CODES.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_invalid_bic_length() {
+        let result = Bic::try_from("DEUTDEF");
+        assert_eq!(result, Err(BicParseError::InvalidLength));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "BIC must be 8 or 11 characters long"
+        );
+    }
+
+    #[test]
+    fn test_invalid_bic_format() {
+        let result = Bic::try_from("DEU1DEFF");
+        assert_eq!(result, Err(BicParseError::InvalidFormat));
+        assert_eq!(result.unwrap_err().to_string(), "BIC has invalid format");
+    }
+
+    #[test]
+    fn test_unknown_bic_country() {
+        let result = Bic::try_from("DEUTQQFF");
+        assert_eq!(result, Err(BicParseError::UnknownCountry));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "BIC has unknown country code"
+        );
+    }
+
+    #[test]
+    fn test_valid_bic() {
+        let bic = Bic::try_from("DEUTDEFF500").unwrap();
+        assert_eq!(bic.bank_code(), "DEUT");
+        assert_eq!(bic.country_code(), "DE");
+        assert_eq!(bic.location_code(), "FF");
+        assert_eq!(bic.branch_code(), Some("500"));
+
+        let bic = Bic::try_from("SABRRUMM").unwrap();
+        assert_eq!(bic.country_code(), "RU");
+        assert_eq!(bic.branch_code(), None);
+    }
+}
@@ -0,0 +1,267 @@
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+const IBAN_MIN_LENGTH: usize = 15;
+const IBAN_MAX_LENGTH: usize = 34;
+const IBAN_LENGTHS: [(&str, usize); 89] = [
+    ("AD", 24),
+    ("AE", 23),
+    ("AL", 28),
+    ("AT", 20),
+    ("AZ", 28),
+    ("BA", 20),
+    ("BE", 16),
+    ("BG", 22),
+    ("BH", 22),
+    ("BI", 27),
+    ("BR", 29),
+    ("BY", 28),
+    ("CH", 21),
+    ("CR", 22),
+    ("CY", 28),
+    ("CZ", 24),
+    ("DE", 22),
+    ("DJ", 27),
+    ("DK", 18),
+    ("DO", 28),
+    ("EE", 20),
+    ("EG", 29),
+    ("ES", 24),
+    ("FI", 18),
+    ("FK", 18),
+    ("FO", 18),
+    ("FR", 27),
+    ("GB", 22),
+    ("GE", 22),
+    ("GI", 23),
+    ("GL", 18),
+    ("GR", 27),
+    ("GT", 28),
+    ("HN", 28),
+    ("HR", 21),
+    ("HU", 28),
+    ("IE", 22),
+    ("IL", 23),
+    ("IQ", 23),
+    ("IS", 26),
+    ("IT", 27),
+    ("JO", 30),
+    ("KW", 30),
+    ("KZ", 20),
+    ("LB", 28),
+    ("LC", 32),
+    ("LI", 21),
+    ("LT", 20),
+    ("LU", 20),
+    ("LV", 21),
+    ("LY", 25),
+    ("MC", 27),
+    ("MD", 24),
+    ("ME", 22),
+    ("MK", 19),
+    ("MN", 20),
+    ("MR", 27),
+    ("MT", 31),
+    ("MU", 30),
+    ("NI", 28),
+    ("NL", 18),
+    ("NO", 15),
+    ("OM", 23),
+    ("PK", 24),
+    ("PL", 28),
+    ("PS", 29),
+    ("PT", 25),
+    ("QA", 29),
+    ("RO", 24),
+    ("RS", 22),
+    ("RU", 33),
+    ("SA", 24),
+    ("SC", 31),
+    ("SD", 18),
+    ("SE", 24),
+    ("SI", 19),
+    ("SK", 24),
+    ("SM", 27),
+    ("SO", 23),
+    ("ST", 25),
+    ("SV", 28),
+    ("TL", 23),
+    ("TN", 24),
+    ("TR", 26),
+    ("UA", 29),
+    ("VA", 22),
+    ("VG", 24),
+    ("XK", 20),
+    ("YE", 30),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Iban<'a>(Cow<'a, str>);
+
+impl<'a> Iban<'a> {
+    pub fn is_iban_like(value: &str) -> bool {
+        let bytes = value.as_bytes();
+        bytes.len() >= 4
+            && bytes[..2].iter().all(u8::is_ascii_uppercase)
+            && bytes[2..4].iter().all(u8::is_ascii_digit)
+            && expected_length(&value[..2]).is_some()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn country_code(&self) -> &str {
+        &self.0[..2]
+    }
+
+    pub fn check_digits(&self) -> &str {
+        &self.0[2..4]
+    }
+
+    pub fn bban(&self) -> &str {
+        &self.0[4..]
+    }
+
+    pub fn into_owned(self) -> Iban<'static> {
+        Iban(Cow::Owned(self.0.into_owned()))
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Iban<'a> {
+    type Error = IbanParseError;
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        let value = value.trim();
+        let value = if value.contains(' ') {
+            Cow::Owned(value.replace(' ', ""))
+        } else {
+            Cow::Borrowed(value)
+        };
+        if value.len() < IBAN_MIN_LENGTH || value.len() > IBAN_MAX_LENGTH {
+            return Err(IbanParseError::InvalidLength);
+        }
+        if !value
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+            || !value[2..4].chars().all(|c| c.is_ascii_digit())
+        {
+            return Err(IbanParseError::InvalidFormat);
+        }
+        let expected_length = expected_length(&value[..2]).ok_or(IbanParseError::UnknownCountry)?;
+        if value.len() != expected_length {
+            return Err(IbanParseError::InvalidLength);
+        }
+        if checksum(&value) != 1 {
+            return Err(IbanParseError::InvalidChecksum);
+        }
+        Ok(Self(value))
+    }
+}
+
+fn expected_length(country_code: &str) -> Option<usize> {
+    IBAN_LENGTHS
+        .binary_search_by(|(code, _)| (*code).cmp(country_code))
+        .ok()
+        .map(|index| IBAN_LENGTHS[index].1)
+}
+
+fn checksum(value: &str) -> u32 {
+    value[4..]
+        .chars()
+        .chain(value[..4].chars())
+        .filter_map(|c| c.to_digit(36))
+        .fold(0, |remainder, digit| {
+            let factor = if digit < 10 { 10 } else { 100 };
+            (remainder * factor + digit) % 97
+        })
+}
+
+impl Display for Iban<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut groups = self.0.as_bytes().chunks(4).peekable();
+        while let Some(group) = groups.next() {
+            write!(f, "{}", String::from_utf8_lossy(group))?;
+            if groups.peek().is_some() {
+                write!(f, " ")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum IbanParseError {
+    InvalidFormat,
+    InvalidLength,
+    UnknownCountry,
+    InvalidChecksum,
+}
+
+impl Display for IbanParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IbanParseError::InvalidFormat => write!(f, "IBAN has invalid format"),
+            IbanParseError::InvalidLength => write!(f, "IBAN has invalid length"),
+            IbanParseError::UnknownCountry => write!(f, "IBAN has unknown country code"),
+            IbanParseError::InvalidChecksum => write!(f, "IBAN has invalid checksum"),
+        }
+    }
+}
+
+impl Error for IbanParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iban_lengths_are_sorted() {
+        assert!(IBAN_LENGTHS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn test_invalid_iban_format() {
+        let result = Iban::try_from("DEXX370400440532013000");
+        assert_eq!(result, Err(IbanParseError::InvalidFormat));
+        assert_eq!(result.unwrap_err().to_string(), "IBAN has invalid format");
+    }
+
+    #[test]
+    fn test_invalid_iban_length() {
+        let result = Iban::try_from("DE8937040044053201300");
+        assert_eq!(result, Err(IbanParseError::InvalidLength));
+        assert_eq!(result.unwrap_err().to_string(), "IBAN has invalid length");
+    }
+
+    #[test]
+    fn test_unknown_iban_country() {
+        let result = Iban::try_from("QQ89370400440532013000");
+        assert_eq!(result, Err(IbanParseError::UnknownCountry));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "IBAN has unknown country code"
+        );
+    }
+
+    #[test]
+    fn test_invalid_iban_checksum() {
+        let result = Iban::try_from("DE88370400440532013000");
+        assert_eq!(result, Err(IbanParseError::InvalidChecksum));
+        assert_eq!(result.unwrap_err().to_string(), "IBAN has invalid checksum");
+    }
+
+    #[test]
+    fn test_valid_iban() {
+        let result = Iban::try_from("GB29 NWBK 6016 1331 9268 19");
+        assert_eq!(result, Ok(Iban("GB29NWBK60161331926819".into())));
+        let iban = result.unwrap();
+        assert_eq!(iban.country_code(), "GB");
+        assert_eq!(iban.check_digits(), "29");
+        assert_eq!(iban.bban(), "NWBK60161331926819");
+        assert_eq!(iban.to_string(), "GB29 NWBK 6016 1331 9268 19");
+
+        assert!(Iban::try_from("DK5000400440116243").is_ok());
+        assert!(Iban::try_from("RU0204452560040702810412345678901").is_ok());
+    }
+}
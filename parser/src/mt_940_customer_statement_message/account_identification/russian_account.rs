@@ -0,0 +1,186 @@
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+const BIK_LENGTH: usize = 9;
+const BIK_PREFIX: &str = "04";
+const ACCOUNT_LENGTH: usize = 20;
+const CORRESPONDENT_ACCOUNT_PREFIX: &str = "30101";
+const CONTROL_KEY_WEIGHTS: [u32; 3] = [7, 1, 3];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bik<'a>(Cow<'a, str>);
+
+impl Bik<'_> {
+    pub fn is_bik_like(value: &str) -> bool {
+        value.len() == BIK_LENGTH && value.bytes().all(|b| b.is_ascii_digit())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_owned(self) -> Bik<'static> {
+        Bik(Cow::Owned(self.0.into_owned()))
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Bik<'a> {
+    type Error = RussianAccountParseError;
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        let value = value.trim();
+        if !Self::is_bik_like(value) || !value.starts_with(BIK_PREFIX) {
+            return Err(RussianAccountParseError::InvalidBik);
+        }
+        Ok(Self(Cow::Borrowed(value)))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RussianAccount<'a>(Cow<'a, str>);
+
+impl RussianAccount<'_> {
+    pub fn is_russian_account_like(value: &str) -> bool {
+        value.len() == ACCOUNT_LENGTH && value.bytes().all(|b| b.is_ascii_digit())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn balance_account(&self) -> &str {
+        &self.0[..5]
+    }
+
+    pub fn currency_code(&self) -> &str {
+        &self.0[5..8]
+    }
+
+    pub fn control_key(&self) -> u32 {
+        u32::from(self.0.as_bytes()[8] - b'0')
+    }
+
+    pub fn is_correspondent_account(&self) -> bool {
+        self.0.starts_with(CORRESPONDENT_ACCOUNT_PREFIX)
+    }
+
+    pub fn check_control_key(&self, bik: &Bik<'_>) -> Result<(), RussianAccountParseError> {
+        let prefix = if self.is_correspondent_account() {
+            format!("0{}", &bik.0[4..6])
+        } else {
+            bik.0[6..].to_string()
+        };
+        let sum = prefix
+            .bytes()
+            .chain(self.0.bytes())
+            .zip(CONTROL_KEY_WEIGHTS.iter().cycle())
+            .map(|(digit, weight)| u32::from(digit - b'0') * weight % 10)
+            .sum::<u32>();
+        if sum % 10 == 0 {
+            Ok(())
+        } else {
+            Err(RussianAccountParseError::InvalidControlKey)
+        }
+    }
+
+    pub fn into_owned(self) -> RussianAccount<'static> {
+        RussianAccount(Cow::Owned(self.0.into_owned()))
+    }
+}
+
+impl<'a> TryFrom<&'a str> for RussianAccount<'a> {
+    type Error = RussianAccountParseError;
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        let value = value.trim();
+        if !Self::is_russian_account_like(value) {
+            return Err(RussianAccountParseError::InvalidAccount);
+        }
+        Ok(Self(Cow::Borrowed(value)))
+    }
+}
+
+impl Display for Bik<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Display for RussianAccount<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RussianAccountParseError {
+    InvalidBik,
+    InvalidAccount,
+    InvalidControlKey,
+}
+
+impl Display for RussianAccountParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RussianAccountParseError::InvalidBik => {
+                write!(f, "BIK must be 9 digits starting with {}", BIK_PREFIX)
+            }
+            RussianAccountParseError::InvalidAccount => {
+                write!(f, "Account number must be {} digits", ACCOUNT_LENGTH)
+            }
+            RussianAccountParseError::InvalidControlKey => {
+                write!(f, "Account number control key does not match BIK")
+            }
+        }
+    }
+}
+
+impl Error for RussianAccountParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_bik() {
+        let result = Bik::try_from("144525225");
+        assert_eq!(result, Err(RussianAccountParseError::InvalidBik));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "BIK must be 9 digits starting with 04"
+        );
+        assert!(Bik::try_from("04452522").is_err());
+    }
+
+    #[test]
+    fn test_invalid_russian_account() {
+        let result = RussianAccount::try_from("4070281093800001234");
+        assert_eq!(result, Err(RussianAccountParseError::InvalidAccount));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Account number must be 20 digits"
+        );
+    }
+
+    #[test]
+    fn test_russian_account_control_key() {
+        let bik = Bik::try_from("044525225").unwrap();
+
+        let account = RussianAccount::try_from("40702810938000012345").unwrap();
+        assert_eq!(account.balance_account(), "40702");
+        assert_eq!(account.currency_code(), "810");
+        assert_eq!(account.control_key(), 9);
+        assert_eq!(account.check_control_key(&bik), Ok(()));
+
+        let account = RussianAccount::try_from("30101810400000000225").unwrap();
+        assert!(account.is_correspondent_account());
+        assert_eq!(account.check_control_key(&bik), Ok(()));
+
+        let account = RussianAccount::try_from("40702810400000000001").unwrap();
+        let result = account.check_control_key(&bik);
+        assert_eq!(result, Err(RussianAccountParseError::InvalidControlKey));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Account number control key does not match BIK"
+        );
+    }
+}
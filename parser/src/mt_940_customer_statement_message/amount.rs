@@ -1,33 +1,74 @@
+use crate::mt_940_customer_statement_message::iso_4217::*;
 use rust_decimal::Decimal;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
 const AMOUNT_MAX_LENGTH: usize = 15;
+const AMOUNT_BUFFER_LENGTH: usize = 32;
 
-#[derive(Debug, PartialEq)]
-pub(super) struct Amount(Decimal);
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Amount(Decimal);
 
-impl TryFrom<&str> for Amount {
-    type Error = AmountParseError;
+impl Amount {
+    pub fn value(&self) -> Decimal {
+        self.0
+    }
 
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let value = value.trim().replace(',', ".");
+    pub fn decimal_places(&self) -> u32 {
+        self.0.scale()
+    }
+
+    pub(crate) fn parse(value: &str, max_length: usize) -> Result<Self, AmountParseError> {
+        let value = value.trim();
         if value.is_empty() {
             return Err(AmountParseError::Empty);
         }
 
-        if value.len() > AMOUNT_MAX_LENGTH {
-            return Err(AmountParseError::TooLong);
+        let max_length = max_length.min(AMOUNT_BUFFER_LENGTH);
+        if value.len() > max_length {
+            return Err(AmountParseError::TooLong(max_length));
         }
 
-        if !value.contains('.') {
+        if !value.contains([',', '.']) {
             return Err(AmountParseError::InvalidFormat);
         }
 
-        let decimal = Decimal::from_str(&value).map_err(|_| AmountParseError::InvalidFormat)?;
+        let mut buffer = [0u8; AMOUNT_BUFFER_LENGTH];
+        for (target, source) in buffer.iter_mut().zip(value.bytes()) {
+            *target = if source == b',' { b'.' } else { source };
+        }
+        let value = std::str::from_utf8(&buffer[..value.len()])
+            .map_err(|_| AmountParseError::InvalidFormat)?;
+        let decimal = Decimal::from_str(value).map_err(|_| AmountParseError::InvalidFormat)?;
         Ok(Self(decimal))
     }
+
+    pub fn try_from_iso_4217(
+        value: &str,
+        currency: &Iso4217Currency,
+    ) -> Result<Self, AmountParseError> {
+        let amount = Self::try_from(value)?;
+        amount.check_minor_units(currency)?;
+        Ok(amount)
+    }
+
+    pub fn check_minor_units(&self, currency: &Iso4217Currency) -> Result<(), AmountParseError> {
+        match currency.minor_units() {
+            Some(minor_units) if self.decimal_places() > u32::from(minor_units) => Err(
+                AmountParseError::TooManyDecimalPlaces(currency.code(), minor_units),
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl TryFrom<&str> for Amount {
+    type Error = AmountParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::parse(value, AMOUNT_MAX_LENGTH)
+    }
 }
 
 impl Display for Amount {
@@ -37,22 +78,28 @@ impl Display for Amount {
 }
 
 #[derive(Debug, PartialEq)]
-pub(super) enum AmountParseError {
+pub enum AmountParseError {
     Empty,
-    TooLong,
+    TooLong(usize),
     InvalidFormat,
+    TooManyDecimalPlaces(&'static str, u8),
 }
 
 impl Display for AmountParseError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             AmountParseError::Empty => write!(f, "Amount cannot be empty"),
-            AmountParseError::TooLong => write!(
+            AmountParseError::TooLong(max_length) => write!(
                 f,
                 "Amount exceeds maximum length of {} characters",
-                AMOUNT_MAX_LENGTH
+                max_length
             ),
             AmountParseError::InvalidFormat => write!(f, "Invalid amount format"),
+            AmountParseError::TooManyDecimalPlaces(code, minor_units) => write!(
+                f,
+                "Amount in {} cannot have more than {} decimal places",
+                code, minor_units
+            ),
         }
     }
 }
@@ -73,7 +120,7 @@ mod tests {
     #[test]
     fn test_amount_too_long() {
         let result = Amount::try_from("123456789,123456789");
-        assert_eq!(result, Err(AmountParseError::TooLong));
+        assert_eq!(result, Err(AmountParseError::TooLong(AMOUNT_MAX_LENGTH)));
         assert_eq!(
             result.unwrap_err().to_string(),
             format!(
@@ -100,4 +147,63 @@ mod tests {
         assert_eq!(result, Ok(Amount(Decimal::new(1234567809, 2))));
         assert_eq!(result.unwrap().to_string(), "12345678.09");
     }
+
+    #[test]
+    fn test_amount_too_many_decimal_places() {
+        let jpy = Iso4217Currency::find("JPY").unwrap();
+        let result = Amount::try_from("100,5").unwrap().check_minor_units(jpy);
+        assert_eq!(
+            result,
+            Err(AmountParseError::TooManyDecimalPlaces("JPY", 0))
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Amount in JPY cannot have more than 0 decimal places"
+        );
+
+        let eur = Iso4217Currency::find("EUR").unwrap();
+        let result = Amount::try_from("100,123").unwrap().check_minor_units(eur);
+        assert_eq!(
+            result,
+            Err(AmountParseError::TooManyDecimalPlaces("EUR", 2))
+        );
+    }
+
+    #[test]
+    fn test_amount_valid_decimal_places() {
+        let jpy = Iso4217Currency::find("JPY").unwrap();
+        assert_eq!(
+            Amount::try_from("100,").unwrap().check_minor_units(jpy),
+            Ok(())
+        );
+
+        let kwd = Iso4217Currency::find("KWD").unwrap();
+        assert_eq!(
+            Amount::try_from("1,125").unwrap().check_minor_units(kwd),
+            Ok(())
+        );
+
+        let xau = Iso4217Currency::find("XAU").unwrap();
+        assert_eq!(
+            Amount::try_from("1,12345").unwrap().check_minor_units(xau),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_amount_try_from_iso_4217() {
+        let jpy = Iso4217Currency::find("JPY").unwrap();
+        assert_eq!(
+            Amount::try_from_iso_4217("100,5", jpy),
+            Err(AmountParseError::TooManyDecimalPlaces("JPY", 0))
+        );
+        assert_eq!(
+            Amount::try_from_iso_4217("100,", jpy),
+            Ok(Amount(Decimal::new(100, 0)))
+        );
+        assert_eq!(
+            Amount::try_from_iso_4217("", jpy),
+            Err(AmountParseError::Empty)
+        );
+    }
 }
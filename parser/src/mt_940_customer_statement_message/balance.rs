@@ -2,27 +2,43 @@ mod credit_debit_mark;
 mod currency_code;
 
 use crate::mt_940_customer_statement_message::amount::*;
-use crate::mt_940_customer_statement_message::balance::credit_debit_mark::*;
-use crate::mt_940_customer_statement_message::balance::currency_code::*;
+pub use crate::mt_940_customer_statement_message::balance::credit_debit_mark::*;
+pub use crate::mt_940_customer_statement_message::balance::currency_code::*;
 use crate::mt_940_customer_statement_message::date::*;
-use std::any::Any;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
+use std::ops::Range;
 
-const BALANCE_MAX_LENGTH: usize = 25;
+pub(crate) const BALANCE_MAX_LENGTH: usize = 25;
 const BALANCE_MIN_LENGTH: usize = 12;
+const BALANCE_AMOUNT_START: usize = 10;
 
 #[derive(Debug, PartialEq)]
-pub(super) struct Balance {
+pub struct Balance<'a> {
     debit_credit_mark: CreditDebitMark,
     date: Date,
-    currency_code: CurrencyCode,
+    currency_code: CurrencyCode<'a>,
     amount: Amount,
 }
 
-impl TryFrom<&str> for Balance {
-    type Error = BalanceParseError;
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
+impl<'a> Balance<'a> {
+    pub fn debit_credit_mark(&self) -> &CreditDebitMark {
+        &self.debit_credit_mark
+    }
+
+    pub fn date(&self) -> &Date {
+        &self.date
+    }
+
+    pub fn currency_code(&self) -> &CurrencyCode<'a> {
+        &self.currency_code
+    }
+
+    pub fn amount(&self) -> &Amount {
+        &self.amount
+    }
+
+    pub(crate) fn parse(value: &'a str, max_length: usize) -> Result<Self, BalanceParseError> {
         let value = value.trim();
         if value.is_empty() {
             return Err(BalanceParseError::Empty);
@@ -30,15 +46,22 @@ impl TryFrom<&str> for Balance {
         if value.len() < BALANCE_MIN_LENGTH {
             return Err(BalanceParseError::InvalidFormat(None));
         }
-        if value.len() > BALANCE_MAX_LENGTH {
-            return Err(BalanceParseError::TooLong);
+        if value.len() > max_length {
+            return Err(BalanceParseError::TooLong(max_length));
         }
 
-        let debit_credit_mark = CreditDebitMark::try_from(&value.chars().nth(0).unwrap())?;
-        let date = Date::try_from(value.chars().skip(1).take(6).collect::<String>().as_str())?;
-        let currency_code =
-            CurrencyCode::try_from(value.chars().skip(7).take(3).collect::<String>().as_str())?;
-        let amount = Amount::try_from(value.chars().skip(10).collect::<String>().as_str())?;
+        let debit_credit_mark = CreditDebitMark::try_from(&value.chars().next().unwrap())?;
+        let subfield = |range| {
+            value
+                .get(range)
+                .ok_or(BalanceParseError::InvalidFormat(None))
+        };
+        let date = Date::try_from(subfield(1..7)?)?;
+        let currency_code = CurrencyCode::try_from(subfield(7..BALANCE_AMOUNT_START)?)?;
+        let amount = Amount::parse(
+            subfield(BALANCE_AMOUNT_START..value.len())?,
+            max_length - BALANCE_AMOUNT_START,
+        )?;
 
         Ok(Self {
             debit_credit_mark,
@@ -47,9 +70,53 @@ impl TryFrom<&str> for Balance {
             amount,
         })
     }
+
+    pub fn check_currency(&self) -> Result<(), BalanceParseError> {
+        let currency = self.currency_code.iso_4217()?;
+        self.amount.check_minor_units(currency)?;
+        Ok(())
+    }
+
+    pub fn into_owned(self) -> Balance<'static> {
+        Balance {
+            debit_credit_mark: self.debit_credit_mark,
+            date: self.date,
+            currency_code: self.currency_code.into_owned(),
+            amount: self.amount,
+        }
+    }
+}
+
+pub(crate) fn balance_error_span(value: &str, error: &BalanceParseError) -> Range<usize> {
+    let start = value.len() - value.trim_start().len();
+    let value = value.trim();
+    let span = match error {
+        BalanceParseError::Empty => 0..0,
+        BalanceParseError::TooLong(max_length) => *max_length..value.len(),
+        BalanceParseError::InvalidFormat(Some(err)) if err.is::<CreditDebitMarkParseError>() => {
+            0..1
+        }
+        BalanceParseError::InvalidFormat(Some(err)) if err.is::<DateParseError>() => 1..7,
+        BalanceParseError::InvalidFormat(Some(err)) if err.is::<CurrencyCodeParseError>() => {
+            7..BALANCE_AMOUNT_START
+        }
+        BalanceParseError::InvalidFormat(Some(err)) if err.is::<AmountParseError>() => {
+            BALANCE_AMOUNT_START..value.len()
+        }
+        BalanceParseError::InvalidFormat(_) => 0..value.len(),
+    };
+    let span_start = span.start.min(value.len());
+    start + span_start..start + span.end.clamp(span_start, value.len())
+}
+
+impl<'a> TryFrom<&'a str> for Balance<'a> {
+    type Error = BalanceParseError;
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        Self::parse(value, BALANCE_MAX_LENGTH)
+    }
 }
 
-impl Display for Balance {
+impl Display for Balance<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "- Debit/Credit: {}", self.debit_credit_mark)?;
         writeln!(f, "- Date: {}", self.date)?;
@@ -59,21 +126,19 @@ impl Display for Balance {
 }
 
 #[derive(Debug)]
-pub(super) enum BalanceParseError {
+pub enum BalanceParseError {
     Empty,
-    TooLong,
-    InvalidFormat(Option<Box<dyn Error>>),
+    TooLong(usize),
+    InvalidFormat(Option<Box<dyn Error + Send + Sync>>),
 }
 
 impl Display for BalanceParseError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             BalanceParseError::Empty => write!(f, "Opening balance is empty"),
-            BalanceParseError::TooLong => write!(
-                f,
-                "Opening balance exceeds {} character length",
-                BALANCE_MAX_LENGTH
-            ),
+            BalanceParseError::TooLong(max_length) => {
+                write!(f, "Opening balance exceeds {} character length", max_length)
+            }
             BalanceParseError::InvalidFormat(None) => {
                 write!(f, "Opening balance has invalid format")
             }
@@ -84,7 +149,7 @@ impl Display for BalanceParseError {
     }
 }
 
-impl<T: Error + 'static> From<T> for BalanceParseError {
+impl<T: Error + Send + Sync + 'static> From<T> for BalanceParseError {
     fn from(value: T) -> Self {
         Self::InvalidFormat(Some(Box::new(value)))
     }
@@ -94,18 +159,19 @@ impl PartialEq for BalanceParseError {
     fn eq(&self, other: &Self) -> bool {
         match self {
             BalanceParseError::Empty => matches!(other, BalanceParseError::Empty),
-            BalanceParseError::TooLong => matches!(other, BalanceParseError::TooLong),
+            BalanceParseError::TooLong(max_length) => {
+                matches!(other, BalanceParseError::TooLong(other) if other == max_length)
+            }
             BalanceParseError::InvalidFormat(None) => {
                 matches!(other, BalanceParseError::InvalidFormat(None))
             }
             BalanceParseError::InvalidFormat(Some(err1)) => {
                 if let BalanceParseError::InvalidFormat(Some(err2)) = other {
-                    err1.type_id() == err2.type_id()
+                    err1.to_string() == err2.to_string()
                 } else {
                     false
                 }
             }
-            _ => false,
         }
     }
 }
@@ -123,8 +189,9 @@ mod tests {
 
     #[test]
     fn test_opening_balance_too_long() {
-        let result = Balance::try_from("1".repeat(BALANCE_MAX_LENGTH + 1).as_str());
-        assert_eq!(result, Err(BalanceParseError::TooLong));
+        let value = "1".repeat(BALANCE_MAX_LENGTH + 1);
+        let result = Balance::try_from(value.as_str());
+        assert_eq!(result, Err(BalanceParseError::TooLong(BALANCE_MAX_LENGTH)));
         assert_eq!(
             result.unwrap_err().to_string(),
             format!(
@@ -142,6 +209,9 @@ mod tests {
             result.unwrap_err().to_string(),
             "Opening balance has invalid format"
         );
+
+        let result = Balance::try_from("C23030éDKK985623,04");
+        assert_eq!(result, Err(BalanceParseError::InvalidFormat(None)));
     }
 
     #[test]
@@ -161,4 +231,28 @@ mod tests {
             "- Debit/Credit: Debit\n- Date: 2023-03-06\n- Currency code: DKK\n- Amount: 985623.04\n"
         );
     }
+
+    #[test]
+    fn test_balance_check_currency() {
+        let result = Balance::try_from("C230306XYZ100,00")
+            .unwrap()
+            .check_currency();
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Opening balance has invalid format: Unknown ISO 4217 currency code XYZ"
+        );
+
+        let result = Balance::try_from("C230306JPY100,50")
+            .unwrap()
+            .check_currency();
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Opening balance has invalid format: Amount in JPY cannot have more than 0 decimal places"
+        );
+
+        let result = Balance::try_from("C230306JPY100,")
+            .unwrap()
+            .check_currency();
+        assert_eq!(result, Ok(()));
+    }
 }
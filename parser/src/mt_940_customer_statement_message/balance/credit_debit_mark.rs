@@ -2,11 +2,20 @@ use std::error::Error;
 use std::fmt::{Display, Formatter};
 
 #[derive(Debug, PartialEq)]
-pub(super) enum CreditDebitMark {
+pub enum CreditDebitMark {
     Credit,
     Debit,
 }
 
+impl CreditDebitMark {
+    pub fn code(&self) -> char {
+        match self {
+            CreditDebitMark::Credit => 'C',
+            CreditDebitMark::Debit => 'D',
+        }
+    }
+}
+
 impl TryFrom<&char> for CreditDebitMark {
     type Error = CreditDebitMarkParseError;
     fn try_from(value: &char) -> Result<Self, Self::Error> {
@@ -28,7 +37,7 @@ impl Display for CreditDebitMark {
 }
 
 #[derive(Debug, PartialEq)]
-pub(super) enum CreditDebitMarkParseError {
+pub enum CreditDebitMarkParseError {
     InvalidValue,
 }
 
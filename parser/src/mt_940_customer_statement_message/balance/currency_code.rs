@@ -1,14 +1,37 @@
+use crate::mt_940_customer_statement_message::iso_4217::*;
+use std::borrow::Cow;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 
 const CURRENCY_CODE_LENGTH: usize = 3;
 
-#[derive(Debug, PartialEq)]
-pub(super) struct CurrencyCode(String);
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CurrencyCode<'a>(Cow<'a, str>);
+
+impl<'a> CurrencyCode<'a> {
+    pub fn try_from_iso_4217(value: &'a str) -> Result<Self, CurrencyCodeParseError> {
+        let currency_code = Self::try_from(value)?;
+        currency_code.iso_4217()?;
+        Ok(currency_code)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_owned(self) -> CurrencyCode<'static> {
+        CurrencyCode(Cow::Owned(self.0.into_owned()))
+    }
 
-impl TryFrom<&str> for CurrencyCode {
+    pub fn iso_4217(&self) -> Result<&'static Iso4217Currency, CurrencyCodeParseError> {
+        Iso4217Currency::find(&self.0)
+            .ok_or_else(|| CurrencyCodeParseError::UnknownCurrency(self.0.to_string()))
+    }
+}
+
+impl<'a> TryFrom<&'a str> for CurrencyCode<'a> {
     type Error = CurrencyCodeParseError;
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
         let value = value.trim();
         if value.len() != CURRENCY_CODE_LENGTH {
             return Err(CurrencyCodeParseError::InvalidLength);
@@ -19,20 +42,21 @@ impl TryFrom<&str> for CurrencyCode {
         {
             return Err(CurrencyCodeParseError::InvalidFormat);
         }
-        Ok(Self(value.to_string()))
+        Ok(Self(Cow::Borrowed(value)))
     }
 }
 
-impl Display for CurrencyCode {
+impl Display for CurrencyCode<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
     }
 }
 
 #[derive(Debug, PartialEq)]
-pub(super) enum CurrencyCodeParseError {
+pub enum CurrencyCodeParseError {
     InvalidLength,
     InvalidFormat,
+    UnknownCurrency(String),
 }
 
 impl Display for CurrencyCodeParseError {
@@ -46,6 +70,9 @@ impl Display for CurrencyCodeParseError {
             CurrencyCodeParseError::InvalidFormat => {
                 write!(f, "Currency code must be uppercase alphabetic characters")
             }
+            CurrencyCodeParseError::UnknownCurrency(code) => {
+                write!(f, "Unknown ISO 4217 currency code {}", code)
+            }
         }
     }
 }
@@ -99,7 +126,43 @@ mod tests {
     #[test]
     fn test_valid_currency_code() {
         let result = CurrencyCode::try_from("EUR");
-        assert_eq!(result, Ok(CurrencyCode("EUR".to_string())));
+        assert_eq!(result, Ok(CurrencyCode("EUR".into())));
         assert_eq!(result.unwrap().to_string(), "EUR");
     }
+
+    #[test]
+    fn test_unknown_iso_4217_currency_code() {
+        let result = CurrencyCode::try_from("XYZ").unwrap().iso_4217();
+        assert_eq!(
+            result,
+            Err(CurrencyCodeParseError::UnknownCurrency("XYZ".to_string()))
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Unknown ISO 4217 currency code XYZ"
+        );
+    }
+
+    #[test]
+    fn test_try_from_iso_4217() {
+        let result = CurrencyCode::try_from_iso_4217("XYZ");
+        assert_eq!(
+            result,
+            Err(CurrencyCodeParseError::UnknownCurrency("XYZ".to_string()))
+        );
+
+        let result = CurrencyCode::try_from_iso_4217("usd");
+        assert_eq!(result, Err(CurrencyCodeParseError::InvalidFormat));
+
+        let result = CurrencyCode::try_from_iso_4217("EUR");
+        assert_eq!(result, Ok(CurrencyCode("EUR".into())));
+    }
+
+    #[test]
+    fn test_iso_4217_currency_code() {
+        let currency = CurrencyCode::try_from("KWD").unwrap().iso_4217().unwrap();
+        assert_eq!(currency.numeric_code(), 414);
+        assert_eq!(currency.name(), "Kuwaiti Dinar");
+        assert_eq!(currency.minor_units(), Some(3));
+    }
 }
@@ -3,14 +3,23 @@ use std::error::Error;
 use std::fmt::Display;
 
 const DATE_LENGTH: usize = 6;
+const CENTURY_START_YEAR: i32 = 2000;
+const CENTURY_END_YEAR: i32 = 2099;
 
-#[derive(Debug, PartialEq)]
-pub(super) struct Date(NaiveDate);
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Date(NaiveDate);
 
 impl Date {
+    pub(crate) const MIN: NaiveDate = NaiveDate::from_ymd_opt(CENTURY_START_YEAR, 1, 1).unwrap();
+    pub(crate) const MAX: NaiveDate = NaiveDate::from_ymd_opt(CENTURY_END_YEAR, 12, 31).unwrap();
+
     pub fn new(date: NaiveDate) -> Self {
         Self(date)
     }
+
+    pub fn value(&self) -> NaiveDate {
+        self.0
+    }
 }
 
 impl TryFrom<&str> for Date {
@@ -23,28 +32,17 @@ impl TryFrom<&str> for Date {
             return Err(DateParseError::InvalidLength);
         }
 
-        let year = value
-            .chars()
-            .take(2)
-            .collect::<String>()
-            .parse::<i32>()
-            .map_err(|_| DateParseError::InvalidFormat)?;
-        let month = value
-            .chars()
-            .skip(2)
-            .take(2)
-            .collect::<String>()
-            .parse::<u32>()
-            .map_err(|_| DateParseError::InvalidFormat)?;
-        let day = value
-            .chars()
-            .skip(4)
-            .take(2)
-            .collect::<String>()
-            .parse::<u32>()
-            .map_err(|_| DateParseError::InvalidFormat)?;
-        let date =
-            NaiveDate::from_ymd_opt(2000 + year, month, day).ok_or(DateParseError::InvalidValue)?;
+        let component = |range| {
+            value
+                .get(range)
+                .and_then(|component: &str| component.parse::<u32>().ok())
+                .ok_or(DateParseError::InvalidFormat)
+        };
+        let year = component(0..2)? as i32;
+        let month = component(2..4)?;
+        let day = component(4..6)?;
+        let date = NaiveDate::from_ymd_opt(CENTURY_START_YEAR + year, month, day)
+            .ok_or(DateParseError::InvalidValue)?;
 
         Ok(Self(date))
     }
@@ -57,7 +55,7 @@ impl Display for Date {
 }
 
 #[derive(Debug, PartialEq)]
-pub(super) enum DateParseError {
+pub enum DateParseError {
     InvalidLength,
     InvalidFormat,
     InvalidValue,
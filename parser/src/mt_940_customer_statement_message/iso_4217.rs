@@ -0,0 +1,294 @@
+#[derive(Debug, PartialEq)]
+pub struct Iso4217Currency {
+    code: &'static str,
+    numeric_code: u16,
+    name: &'static str,
+    minor_units: Option<u8>,
+}
+
+impl Iso4217Currency {
+    const fn new(
+        code: &'static str,
+        numeric_code: u16,
+        name: &'static str,
+        minor_units: Option<u8>,
+    ) -> Self {
+        Self {
+            code,
+            numeric_code,
+            name,
+            minor_units,
+        }
+    }
+
+    pub fn find(code: &str) -> Option<&'static Iso4217Currency> {
+        CURRENCIES
+            .binary_search_by(|currency| currency.code.cmp(code))
+            .ok()
+            .map(|index| &CURRENCIES[index])
+    }
+
+    pub fn find_by_numeric_code(numeric_code: u16) -> Option<&'static Iso4217Currency> {
+        CURRENCIES
+            .iter()
+            .find(|currency| currency.numeric_code == numeric_code)
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    pub fn numeric_code(&self) -> u16 {
+        self.numeric_code
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn minor_units(&self) -> Option<u8> {
+        self.minor_units
+    }
+}
+
+const CURRENCIES: [Iso4217Currency; 175] = [
+    Iso4217Currency::new("AED", 784, "UAE Dirham", Some(2)),
+    Iso4217Currency::new("AFN", 971, "Afghani", Some(2)),
+    Iso4217Currency::new("ALL", 8, "Lek", Some(2)),
+    Iso4217Currency::new("AMD", 51, "Armenian Dram", Some(2)),
+    Iso4217Currency::new("AOA", 973, "Kwanza", Some(2)),
+    Iso4217Currency::new("ARS", 32, "Argentine Peso", Some(2)),
+    Iso4217Currency::new("AUD", 36, "Australian Dollar", Some(2)),
+    Iso4217Currency::new("AWG", 533, "Aruban Florin", Some(2)),
+    Iso4217Currency::new("AZN", 944, "Azerbaijan Manat", Some(2)),
+    Iso4217Currency::new("BAM", 977, "Convertible Mark", Some(2)),
+    Iso4217Currency::new("BBD", 52, "Barbados Dollar", Some(2)),
+    Iso4217Currency::new("BDT", 50, "Taka", Some(2)),
+    Iso4217Currency::new("BHD", 48, "Bahraini Dinar", Some(3)),
+    Iso4217Currency::new("BIF", 108, "Burundi Franc", Some(0)),
+    Iso4217Currency::new("BMD", 60, "Bermudian Dollar", Some(2)),
+    Iso4217Currency::new("BND", 96, "Brunei Dollar", Some(2)),
+    Iso4217Currency::new("BOB", 68, "Boliviano", Some(2)),
+    Iso4217Currency::new("BOV", 984, "Mvdol", Some(2)),
+    Iso4217Currency::new("BRL", 986, "Brazilian Real", Some(2)),
+    Iso4217Currency::new("BSD", 44, "Bahamian Dollar", Some(2)),
+    Iso4217Currency::new("BTN", 64, "Ngultrum", Some(2)),
+    Iso4217Currency::new("BWP", 72, "Pula", Some(2)),
+    Iso4217Currency::new("BYN", 933, "Belarusian Ruble", Some(2)),
+    Iso4217Currency::new("BZD", 84, "Belize Dollar", Some(2)),
+    Iso4217Currency::new("CAD", 124, "Canadian Dollar", Some(2)),
+    Iso4217Currency::new("CDF", 976, "Congolese Franc", Some(2)),
+    Iso4217Currency::new("CHE", 947, "WIR Euro", Some(2)),
+    Iso4217Currency::new("CHF", 756, "Swiss Franc", Some(2)),
+    Iso4217Currency::new("CHW", 948, "WIR Franc", Some(2)),
+    Iso4217Currency::new("CLF", 990, "Unidad de Fomento", Some(4)),
+    Iso4217Currency::new("CLP", 152, "Chilean Peso", Some(0)),
+    Iso4217Currency::new("CNY", 156, "Yuan Renminbi", Some(2)),
+    Iso4217Currency::new("COP", 170, "Colombian Peso", Some(2)),
+    Iso4217Currency::new("COU", 970, "Unidad de Valor Real", Some(2)),
+    Iso4217Currency::new("CRC", 188, "Costa Rican Colon", Some(2)),
+    Iso4217Currency::new("CUP", 192, "Cuban Peso", Some(2)),
+    Iso4217Currency::new("CVE", 132, "Cabo Verde Escudo", Some(2)),
+    Iso4217Currency::new("CZK", 203, "Czech Koruna", Some(2)),
+    Iso4217Currency::new("DJF", 262, "Djibouti Franc", Some(0)),
+    Iso4217Currency::new("DKK", 208, "Danish Krone", Some(2)),
+    Iso4217Currency::new("DOP", 214, "Dominican Peso", Some(2)),
+    Iso4217Currency::new("DZD", 12, "Algerian Dinar", Some(2)),
+    Iso4217Currency::new("EGP", 818, "Egyptian Pound", Some(2)),
+    Iso4217Currency::new("ERN", 232, "Nakfa", Some(2)),
+    Iso4217Currency::new("ETB", 230, "Ethiopian Birr", Some(2)),
+    Iso4217Currency::new("EUR", 978, "Euro", Some(2)),
+    Iso4217Currency::new("FJD", 242, "Fiji Dollar", Some(2)),
+    Iso4217Currency::new("FKP", 238, "Falkland Islands Pound", Some(2)),
+    Iso4217Currency::new("GBP", 826, "Pound Sterling", Some(2)),
+    Iso4217Currency::new("GEL", 981, "Lari", Some(2)),
+    Iso4217Currency::new("GHS", 936, "Ghana Cedi", Some(2)),
+    Iso4217Currency::new("GIP", 292, "Gibraltar Pound", Some(2)),
+    Iso4217Currency::new("GMD", 270, "Dalasi", Some(2)),
+    Iso4217Currency::new("GNF", 324, "Guinean Franc", Some(0)),
+    Iso4217Currency::new("GTQ", 320, "Quetzal", Some(2)),
+    Iso4217Currency::new("GYD", 328, "Guyana Dollar", Some(2)),
+    Iso4217Currency::new("HKD", 344, "Hong Kong Dollar", Some(2)),
+    Iso4217Currency::new("HNL", 340, "Lempira", Some(2)),
+    Iso4217Currency::new("HTG", 332, "Gourde", Some(2)),
+    Iso4217Currency::new("HUF", 348, "Forint", Some(2)),
+    Iso4217Currency::new("IDR", 360, "Rupiah", Some(2)),
+    Iso4217Currency::new("ILS", 376, "New Israeli Sheqel", Some(2)),
+    Iso4217Currency::new("INR", 356, "Indian Rupee", Some(2)),
+    Iso4217Currency::new("IQD", 368, "Iraqi Dinar", Some(3)),
+    Iso4217Currency::new("IRR", 364, "Iranian Rial", Some(2)),
+    Iso4217Currency::new("ISK", 352, "Iceland Krona", Some(0)),
+    Iso4217Currency::new("JMD", 388, "Jamaican Dollar", Some(2)),
+    Iso4217Currency::new("JOD", 400, "Jordanian Dinar", Some(3)),
+    Iso4217Currency::new("JPY", 392, "Yen", Some(0)),
+    Iso4217Currency::new("KES", 404, "Kenyan Shilling", Some(2)),
+    Iso4217Currency::new("KGS", 417, "Som", Some(2)),
+    Iso4217Currency::new("KHR", 116, "Riel", Some(2)),
+    Iso4217Currency::new("KMF", 174, "Comorian Franc", Some(0)),
+    Iso4217Currency::new("KPW", 408, "North Korean Won", Some(2)),
+    Iso4217Currency::new("KRW", 410, "Won", Some(0)),
+    Iso4217Currency::new("KWD", 414, "Kuwaiti Dinar", Some(3)),
+    Iso4217Currency::new("KYD", 136, "Cayman Islands Dollar", Some(2)),
+    Iso4217Currency::new("KZT", 398, "Tenge", Some(2)),
+    Iso4217Currency::new("LAK", 418, "Lao Kip", Some(2)),
+    Iso4217Currency::new("LBP", 422, "Lebanese Pound", Some(2)),
+    Iso4217Currency::new("LKR", 144, "Sri Lanka Rupee", Some(2)),
+    Iso4217Currency::new("LRD", 430, "Liberian Dollar", Some(2)),
+    Iso4217Currency::new("LSL", 426, "Loti", Some(2)),
+    Iso4217Currency::new("LYD", 434, "Libyan Dinar", Some(3)),
+    Iso4217Currency::new("MAD", 504, "Moroccan Dirham", Some(2)),
+    Iso4217Currency::new("MDL", 498, "Moldovan Leu", Some(2)),
+    Iso4217Currency::new("MGA", 969, "Malagasy Ariary", Some(2)),
+    Iso4217Currency::new("MKD", 807, "Denar", Some(2)),
+    Iso4217Currency::new("MMK", 104, "Kyat", Some(2)),
+    Iso4217Currency::new("MNT", 496, "Tugrik", Some(2)),
+    Iso4217Currency::new("MOP", 446, "Pataca", Some(2)),
+    Iso4217Currency::new("MRU", 929, "Ouguiya", Some(2)),
+    Iso4217Currency::new("MUR", 480, "Mauritius Rupee", Some(2)),
+    Iso4217Currency::new("MVR", 462, "Rufiyaa", Some(2)),
+    Iso4217Currency::new("MWK", 454, "Malawi Kwacha", Some(2)),
+    Iso4217Currency::new("MXN", 484, "Mexican Peso", Some(2)),
+    Iso4217Currency::new("MXV", 979, "Mexican Unidad de Inversion (UDI)", Some(2)),
+    Iso4217Currency::new("MYR", 458, "Malaysian Ringgit", Some(2)),
+    Iso4217Currency::new("MZN", 943, "Mozambique Metical", Some(2)),
+    Iso4217Currency::new("NAD", 516, "Namibia Dollar", Some(2)),
+    Iso4217Currency::new("NGN", 566, "Naira", Some(2)),
+    Iso4217Currency::new("NIO", 558, "Cordoba Oro", Some(2)),
+    Iso4217Currency::new("NOK", 578, "Norwegian Krone", Some(2)),
+    Iso4217Currency::new("NPR", 524, "Nepalese Rupee", Some(2)),
+    Iso4217Currency::new("NZD", 554, "New Zealand Dollar", Some(2)),
+    Iso4217Currency::new("OMR", 512, "Rial Omani", Some(3)),
+    Iso4217Currency::new("PAB", 590, "Balboa", Some(2)),
+    Iso4217Currency::new("PEN", 604, "Sol", Some(2)),
+    Iso4217Currency::new("PGK", 598, "Kina", Some(2)),
+    Iso4217Currency::new("PHP", 608, "Philippine Peso", Some(2)),
+    Iso4217Currency::new("PKR", 586, "Pakistan Rupee", Some(2)),
+    Iso4217Currency::new("PLN", 985, "Zloty", Some(2)),
+    Iso4217Currency::new("PYG", 600, "Guarani", Some(0)),
+    Iso4217Currency::new("QAR", 634, "Qatari Rial", Some(2)),
+    Iso4217Currency::new("RON", 946, "Romanian Leu", Some(2)),
+    Iso4217Currency::new("RSD", 941, "Serbian Dinar", Some(2)),
+    Iso4217Currency::new("RUB", 643, "Russian Ruble", Some(2)),
+    Iso4217Currency::new("RWF", 646, "Rwanda Franc", Some(0)),
+    Iso4217Currency::new("SAR", 682, "Saudi Riyal", Some(2)),
+    Iso4217Currency::new("SBD", 90, "Solomon Islands Dollar", Some(2)),
+    Iso4217Currency::new("SCR", 690, "Seychelles Rupee", Some(2)),
+    Iso4217Currency::new("SDG", 938, "Sudanese Pound", Some(2)),
+    Iso4217Currency::new("SEK", 752, "Swedish Krona", Some(2)),
+    Iso4217Currency::new("SGD", 702, "Singapore Dollar", Some(2)),
+    Iso4217Currency::new("SHP", 654, "Saint Helena Pound", Some(2)),
+    Iso4217Currency::new("SLE", 925, "Leone", Some(2)),
+    Iso4217Currency::new("SOS", 706, "Somali Shilling", Some(2)),
+    Iso4217Currency::new("SRD", 968, "Surinam Dollar", Some(2)),
+    Iso4217Currency::new("SSP", 728, "South Sudanese Pound", Some(2)),
+    Iso4217Currency::new("STN", 930, "Dobra", Some(2)),
+    Iso4217Currency::new("SVC", 222, "El Salvador Colon", Some(2)),
+    Iso4217Currency::new("SYP", 760, "Syrian Pound", Some(2)),
+    Iso4217Currency::new("SZL", 748, "Lilangeni", Some(2)),
+    Iso4217Currency::new("THB", 764, "Baht", Some(2)),
+    Iso4217Currency::new("TJS", 972, "Somoni", Some(2)),
+    Iso4217Currency::new("TMT", 934, "Turkmenistan New Manat", Some(2)),
+    Iso4217Currency::new("TND", 788, "Tunisian Dinar", Some(3)),
+    Iso4217Currency::new("TOP", 776, "Pa'anga", Some(2)),
+    Iso4217Currency::new("TRY", 949, "Turkish Lira", Some(2)),
+    Iso4217Currency::new("TTD", 780, "Trinidad and Tobago Dollar", Some(2)),
+    Iso4217Currency::new("TWD", 901, "New Taiwan Dollar", Some(2)),
+    Iso4217Currency::new("TZS", 834, "Tanzanian Shilling", Some(2)),
+    Iso4217Currency::new("UAH", 980, "Hryvnia", Some(2)),
+    Iso4217Currency::new("UGX", 800, "Uganda Shilling", Some(0)),
+    Iso4217Currency::new("USD", 840, "US Dollar", Some(2)),
+    Iso4217Currency::new("USN", 997, "US Dollar (Next day)", Some(2)),
+    Iso4217Currency::new(
+        "UYI",
+        940,
+        "Uruguay Peso en Unidades Indexadas (UI)",
+        Some(0),
+    ),
+    Iso4217Currency::new("UYU", 858, "Peso Uruguayo", Some(2)),
+    Iso4217Currency::new("UYW", 927, "Unidad Previsional", Some(4)),
+    Iso4217Currency::new("UZS", 860, "Uzbekistan Sum", Some(2)),
+    Iso4217Currency::new("VED", 926, "Bolivar Soberano", Some(2)),
+    Iso4217Currency::new("VES", 928, "Bolivar Soberano", Some(2)),
+    Iso4217Currency::new("VND", 704, "Dong", Some(0)),
+    Iso4217Currency::new("VUV", 548, "Vatu", Some(0)),
+    Iso4217Currency::new("WST", 882, "Tala", Some(2)),
+    Iso4217Currency::new("XAF", 950, "CFA Franc BEAC", Some(0)),
+    Iso4217Currency::new("XAG", 961, "Silver", None),
+    Iso4217Currency::new("XAU", 959, "Gold", None),
+    Iso4217Currency::new(
+        "XBA",
+        955,
+        "Bond Markets Unit European Composite Unit (EURCO)",
+        None,
+    ),
+    Iso4217Currency::new(
+        "XBB",
+        956,
+        "Bond Markets Unit European Monetary Unit (E.M.U.-6)",
+        None,
+    ),
+    Iso4217Currency::new(
+        "XBC",
+        957,
+        "Bond Markets Unit European Unit of Account 9 (E.U.A.-9)",
+        None,
+    ),
+    Iso4217Currency::new(
+        "XBD",
+        958,
+        "Bond Markets Unit European Unit of Account 17 (E.U.A.-17)",
+        None,
+    ),
+    Iso4217Currency::new("XCD", 951, "East Caribbean Dollar", Some(2)),
+    Iso4217Currency::new("XCG", 532, "Caribbean Guilder", Some(2)),
+    Iso4217Currency::new("XDR", 960, "SDR (Special Drawing Right)", None),
+    Iso4217Currency::new("XOF", 952, "CFA Franc BCEAO", Some(0)),
+    Iso4217Currency::new("XPD", 964, "Palladium", None),
+    Iso4217Currency::new("XPF", 953, "CFP Franc", Some(0)),
+    Iso4217Currency::new("XPT", 962, "Platinum", None),
+    Iso4217Currency::new("XSU", 994, "Sucre", None),
+    Iso4217Currency::new("XUA", 965, "ADB Unit of Account", None),
+    Iso4217Currency::new("YER", 886, "Yemeni Rial", Some(2)),
+    Iso4217Currency::new("ZAR", 710, "Rand", Some(2)),
+    Iso4217Currency::new("ZMW", 967, "Zambian Kwacha", Some(2)),
+    Iso4217Currency::new("ZWG", 924, "Zimbabwe Gold", Some(2)),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_currencies_are_sorted() {
+        assert!(
+            CURRENCIES
+                .windows(2)
+                .all(|pair| pair[0].code < pair[1].code)
+        );
+    }
+
+    #[test]
+    fn test_unknown_currency() {
+        assert_eq!(Iso4217Currency::find("XYZ"), None);
+        assert_eq!(Iso4217Currency::find("eur"), None);
+    }
+
+    #[test]
+    fn test_known_currency() {
+        let currency = Iso4217Currency::find("EUR").unwrap();
+        assert_eq!(currency.code(), "EUR");
+        assert_eq!(currency.numeric_code(), 978);
+        assert_eq!(currency.name(), "Euro");
+        assert_eq!(currency.minor_units(), Some(2));
+
+        assert_eq!(Iso4217Currency::find("JPY").unwrap().minor_units(), Some(0));
+        assert_eq!(Iso4217Currency::find("KWD").unwrap().minor_units(), Some(3));
+        assert_eq!(Iso4217Currency::find("XAU").unwrap().minor_units(), None);
+
+        assert_eq!(
+            Iso4217Currency::find_by_numeric_code(643).unwrap().code(),
+            "RUB"
+        );
+        assert_eq!(Iso4217Currency::find_by_numeric_code(1), None);
+    }
+}
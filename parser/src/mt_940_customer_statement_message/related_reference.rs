@@ -1,41 +1,59 @@
+use std::borrow::Cow;
 use std::error::Error;
 use std::fmt::Display;
 
-const RELATED_REFERENCE_MAX_LENGTH: usize = 16;
+pub(crate) const RELATED_REFERENCE_MAX_LENGTH: usize = 16;
 
 #[derive(Debug, PartialEq)]
-pub(super) struct RelatedReference(String);
+pub struct RelatedReference<'a>(Cow<'a, str>);
 
-impl TryFrom<&str> for RelatedReference {
-    type Error = RelatedReferenceParseError;
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
+impl<'a> RelatedReference<'a> {
+    pub(crate) fn parse(
+        value: &'a str,
+        max_length: usize,
+    ) -> Result<Self, RelatedReferenceParseError> {
         let value = value.trim();
         if value.is_empty() {
             return Err(RelatedReferenceParseError::Empty);
         }
 
-        if value.len() > RELATED_REFERENCE_MAX_LENGTH {
-            return Err(RelatedReferenceParseError::TooLong);
+        if value.len() > max_length {
+            return Err(RelatedReferenceParseError::TooLong(max_length));
         }
 
         if value.starts_with("/") || value.ends_with("/") || value.contains("//") {
             return Err(RelatedReferenceParseError::InvalidFormat);
         }
 
-        Ok(Self(value.to_string()))
+        Ok(Self(Cow::Borrowed(value)))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_owned(self) -> RelatedReference<'static> {
+        RelatedReference(Cow::Owned(self.0.into_owned()))
     }
 }
 
-impl Display for RelatedReference {
+impl<'a> TryFrom<&'a str> for RelatedReference<'a> {
+    type Error = RelatedReferenceParseError;
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        Self::parse(value, RELATED_REFERENCE_MAX_LENGTH)
+    }
+}
+
+impl Display for RelatedReference<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
     }
 }
 
 #[derive(Debug, PartialEq)]
-pub(super) enum RelatedReferenceParseError {
+pub enum RelatedReferenceParseError {
     Empty,
-    TooLong,
+    TooLong(usize),
     InvalidFormat,
 }
 
@@ -43,10 +61,10 @@ impl Display for RelatedReferenceParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             RelatedReferenceParseError::Empty => write!(f, "Related reference cannot be empty"),
-            RelatedReferenceParseError::TooLong => write!(
+            RelatedReferenceParseError::TooLong(max_length) => write!(
                 f,
                 "Related reference exceeds {} character length",
-                RELATED_REFERENCE_MAX_LENGTH
+                max_length
             ),
             RelatedReferenceParseError::InvalidFormat => {
                 write!(f, "Related reference has invalid format")
@@ -73,9 +91,14 @@ mod tests {
 
     #[test]
     fn test_related_reference_too_long() {
-        let result =
-            RelatedReference::try_from("1".repeat(RELATED_REFERENCE_MAX_LENGTH + 1).as_str());
-        assert_eq!(result, Err(RelatedReferenceParseError::TooLong));
+        let value = "1".repeat(RELATED_REFERENCE_MAX_LENGTH + 1);
+        let result = RelatedReference::try_from(value.as_str());
+        assert_eq!(
+            result,
+            Err(RelatedReferenceParseError::TooLong(
+                RELATED_REFERENCE_MAX_LENGTH
+            ))
+        );
         assert_eq!(
             result.unwrap_err().to_string(),
             format!(
@@ -104,7 +127,7 @@ mod tests {
     #[test]
     fn test_valid_related_reference() {
         let result = RelatedReference::try_from("1234567890");
-        assert_eq!(result, Ok(RelatedReference("1234567890".to_string())));
+        assert_eq!(result, Ok(RelatedReference("1234567890".into())));
         assert_eq!(result.unwrap().to_string(), "1234567890");
     }
 }
@@ -0,0 +1,657 @@
+mod statement_line_mark;
+mod transaction_type_identification_code;
+
+use crate::mt_940_customer_statement_message::amount::*;
+use crate::mt_940_customer_statement_message::date::*;
+pub use crate::mt_940_customer_statement_message::statement_line::statement_line_mark::*;
+pub use crate::mt_940_customer_statement_message::statement_line::transaction_type_identification_code::*;
+use chrono::{Datelike, NaiveDate};
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+const VALUE_DATE_LENGTH: usize = 6;
+const ENTRY_DATE_LENGTH: usize = 4;
+const TRANSACTION_TYPE_IDENTIFICATION_CODE_LENGTH: usize = 4;
+const ACCOUNT_OWNER_REFERENCE_MAX_LENGTH: usize = 16;
+const BANK_REFERENCE_MAX_LENGTH: usize = 16;
+const SUPPLEMENTARY_DETAILS_MAX_LENGTH: usize = 34;
+
+#[derive(Debug, PartialEq)]
+pub struct StatementLine<'a> {
+    value_date: Date,
+    entry_date: Option<Date>,
+    debit_credit_mark: StatementLineMark,
+    funds_code: Option<char>,
+    amount: Amount,
+    transaction_type_identification_code: TransactionTypeIdentificationCode<'a>,
+    account_owner_ref: Cow<'a, str>,
+    bank_ref: Option<Cow<'a, str>>,
+    supplementary_details: Option<Cow<'a, str>>,
+    information_to_account_owner: Option<Vec<Cow<'a, str>>>,
+}
+
+impl<'a> StatementLine<'a> {
+    pub fn value_date(&self) -> &Date {
+        &self.value_date
+    }
+
+    pub fn entry_date(&self) -> Option<&Date> {
+        self.entry_date.as_ref()
+    }
+
+    pub fn booking_date(&self) -> &Date {
+        self.entry_date.as_ref().unwrap_or(&self.value_date)
+    }
+
+    pub fn debit_credit_mark(&self) -> &StatementLineMark {
+        &self.debit_credit_mark
+    }
+
+    pub fn funds_code(&self) -> Option<char> {
+        self.funds_code
+    }
+
+    pub fn amount(&self) -> &Amount {
+        &self.amount
+    }
+
+    pub fn transaction_type_identification_code(&self) -> &TransactionTypeIdentificationCode<'a> {
+        &self.transaction_type_identification_code
+    }
+
+    pub fn account_owner_ref(&self) -> &str {
+        &self.account_owner_ref
+    }
+
+    pub fn bank_ref(&self) -> Option<&str> {
+        self.bank_ref.as_deref()
+    }
+
+    pub fn supplementary_details(&self) -> Option<&str> {
+        self.supplementary_details.as_deref()
+    }
+
+    pub fn information_to_account_owner(&self) -> Option<&[Cow<'a, str>]> {
+        self.information_to_account_owner.as_deref()
+    }
+
+    pub(super) fn resolve_entry_date_within(&mut self, start: &Date, end: &Date) {
+        let Some(entry_date) = self.entry_date else {
+            return;
+        };
+        let period = start.value()..=end.value();
+        if period.contains(&entry_date.value()) {
+            return;
+        }
+        let value_date = self.value_date.value();
+        let entry_date = entry_date.value();
+        let distance = distance_days(entry_date, value_date);
+        if let Some(resolved) = year_candidates(entry_date.month(), entry_date.day(), value_date)
+            .find(|date| distance_days(*date, value_date) == distance && period.contains(date))
+        {
+            self.entry_date = Some(Date::new(resolved));
+        }
+    }
+
+    pub(super) fn set_information_to_account_owner(&mut self, information: Vec<Cow<'a, str>>) {
+        self.information_to_account_owner = Some(information);
+    }
+
+    pub fn into_owned(self) -> StatementLine<'static> {
+        StatementLine {
+            value_date: self.value_date,
+            entry_date: self.entry_date,
+            debit_credit_mark: self.debit_credit_mark,
+            funds_code: self.funds_code,
+            amount: self.amount,
+            transaction_type_identification_code: self
+                .transaction_type_identification_code
+                .into_owned(),
+            account_owner_ref: into_owned(self.account_owner_ref),
+            bank_ref: self.bank_ref.map(into_owned),
+            supplementary_details: self.supplementary_details.map(into_owned),
+            information_to_account_owner: self
+                .information_to_account_owner
+                .map(|information| information.into_iter().map(into_owned).collect()),
+        }
+    }
+}
+
+fn into_owned(value: Cow<'_, str>) -> Cow<'static, str> {
+    Cow::Owned(value.into_owned())
+}
+
+impl<'a> TryFrom<&'a str> for StatementLine<'a> {
+    type Error = StatementLineParseError;
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Err(StatementLineParseError::Empty);
+        }
+
+        let mut lines = value.lines();
+        let line = lines.next().unwrap_or_default().trim_end();
+        let supplementary_details = lines
+            .next()
+            .map(str::trim)
+            .filter(|details| !details.is_empty());
+        if lines.next().is_some() {
+            return Err(StatementLineParseError::InvalidFormat);
+        }
+
+        let (value_date, rest) = split_prefix(line, VALUE_DATE_LENGTH);
+        let value_date =
+            Date::try_from(value_date).map_err(StatementLineParseError::InvalidValueDate)?;
+
+        let (entry_date, rest) = match rest.get(..ENTRY_DATE_LENGTH) {
+            Some(entry_date) if entry_date.chars().all(|c| c.is_ascii_digit()) => (
+                Some(parse_entry_date(entry_date, &value_date)?),
+                &rest[ENTRY_DATE_LENGTH..],
+            ),
+            _ => (None, rest),
+        };
+
+        let mark_length = if rest.starts_with('R') { 2 } else { 1 };
+        let (debit_credit_mark, rest) = split_prefix(rest, mark_length);
+        let debit_credit_mark = StatementLineMark::try_from(debit_credit_mark)
+            .map_err(StatementLineParseError::InvalidMark)?;
+
+        let (funds_code, rest) = match rest.chars().next() {
+            Some(c) if c.is_ascii_alphabetic() => (Some(c), &rest[1..]),
+            _ => (None, rest),
+        };
+
+        let amount_length = rest
+            .find(|c: char| !c.is_ascii_digit() && c != ',')
+            .unwrap_or(rest.len());
+        let (amount, rest) = rest.split_at(amount_length);
+        let amount = Amount::try_from(amount).map_err(StatementLineParseError::InvalidAmount)?;
+
+        let (transaction_type_identification_code, rest) =
+            split_prefix(rest, TRANSACTION_TYPE_IDENTIFICATION_CODE_LENGTH);
+        let transaction_type_identification_code =
+            TransactionTypeIdentificationCode::try_from(transaction_type_identification_code)
+                .map_err(StatementLineParseError::InvalidTransactionTypeIdentificationCode)?;
+
+        let (account_owner_ref, bank_ref) = match rest.split_once("//") {
+            Some((account_owner_ref, bank_ref)) => (account_owner_ref, Some(bank_ref)),
+            None => (rest, None),
+        };
+        if account_owner_ref.is_empty()
+            || account_owner_ref.len() > ACCOUNT_OWNER_REFERENCE_MAX_LENGTH
+        {
+            return Err(StatementLineParseError::InvalidAccountOwnerReference);
+        }
+        if bank_ref.is_some_and(|bank_ref| bank_ref.len() > BANK_REFERENCE_MAX_LENGTH) {
+            return Err(StatementLineParseError::BankReferenceTooLong);
+        }
+        if supplementary_details
+            .is_some_and(|details| details.len() > SUPPLEMENTARY_DETAILS_MAX_LENGTH)
+        {
+            return Err(StatementLineParseError::SupplementaryDetailsTooLong);
+        }
+
+        Ok(Self {
+            value_date,
+            entry_date,
+            debit_credit_mark,
+            funds_code,
+            amount,
+            transaction_type_identification_code,
+            account_owner_ref: Cow::Borrowed(account_owner_ref),
+            bank_ref: bank_ref.map(Cow::Borrowed),
+            supplementary_details: supplementary_details.map(Cow::Borrowed),
+            information_to_account_owner: None,
+        })
+    }
+}
+
+pub(crate) fn statement_line_error_span(
+    value: &str,
+    error: &StatementLineParseError,
+) -> Range<usize> {
+    let start = value.len() - value.trim_start().len();
+    let value = value.trim();
+    let line_starts = std::iter::once(0)
+        .chain(value.match_indices('\n').map(|(index, _)| index + 1))
+        .collect::<Vec<_>>();
+    let line = value.lines().next().unwrap_or_default().trim_end();
+
+    let mark_start = match line.get(VALUE_DATE_LENGTH..VALUE_DATE_LENGTH + ENTRY_DATE_LENGTH) {
+        Some(entry_date) if entry_date.chars().all(|c| c.is_ascii_digit()) => {
+            VALUE_DATE_LENGTH + ENTRY_DATE_LENGTH
+        }
+        _ => VALUE_DATE_LENGTH,
+    };
+    let rest = |start: usize| line.get(start..).unwrap_or_default();
+    let mark_end = mark_start
+        + if rest(mark_start).starts_with('R') {
+            2
+        } else {
+            1
+        };
+    let amount_start = match rest(mark_end).chars().next() {
+        Some(c) if c.is_ascii_alphabetic() => mark_end + 1,
+        _ => mark_end,
+    };
+    let amount_end = amount_start
+        + rest(amount_start)
+            .find(|c: char| !c.is_ascii_digit() && c != ',')
+            .unwrap_or(rest(amount_start).len());
+    let code_end = amount_end + TRANSACTION_TYPE_IDENTIFICATION_CODE_LENGTH;
+    let account_owner_ref_end = rest(code_end)
+        .find("//")
+        .map_or(line.len(), |index| code_end + index);
+
+    let span = match error {
+        StatementLineParseError::Empty => 0..0,
+        StatementLineParseError::InvalidFormat => {
+            line_starts.get(2).copied().unwrap_or(line.len())..value.len()
+        }
+        StatementLineParseError::InvalidValueDate(_) => 0..VALUE_DATE_LENGTH,
+        StatementLineParseError::InvalidEntryDate(_) => VALUE_DATE_LENGTH..mark_start,
+        StatementLineParseError::InvalidMark(_) => mark_start..mark_end,
+        StatementLineParseError::InvalidAmount(_) => amount_start..amount_end,
+        StatementLineParseError::InvalidTransactionTypeIdentificationCode(_) => {
+            amount_end..code_end
+        }
+        StatementLineParseError::InvalidAccountOwnerReference => code_end..account_owner_ref_end,
+        StatementLineParseError::BankReferenceTooLong => account_owner_ref_end + 2..line.len(),
+        StatementLineParseError::SupplementaryDetailsTooLong => {
+            let details_start = line_starts.get(1).copied().unwrap_or(line.len());
+            let details = value[details_start..].lines().next().unwrap_or_default();
+            details_start..details_start + details.trim_end().len()
+        }
+    };
+    let span_start = span.start.min(value.len());
+    start + span_start..start + span.end.clamp(span_start, value.len())
+}
+
+fn split_prefix(value: &str, length: usize) -> (&str, &str) {
+    value.split_at_checked(length).unwrap_or((value, ""))
+}
+
+fn parse_entry_date(value: &str, value_date: &Date) -> Result<Date, StatementLineParseError> {
+    let month = value[..2].parse::<u32>();
+    let day = value[2..].parse::<u32>();
+    let (Ok(month), Ok(day)) = (month, day) else {
+        return Err(StatementLineParseError::InvalidEntryDate(
+            DateParseError::InvalidFormat,
+        ));
+    };
+    resolve_year(month, day, value_date.value())
+        .map(Date::new)
+        .ok_or(StatementLineParseError::InvalidEntryDate(
+            DateParseError::InvalidValue,
+        ))
+}
+
+fn resolve_year(month: u32, day: u32, reference: NaiveDate) -> Option<NaiveDate> {
+    year_candidates(month, day, reference).min_by_key(|date| distance_days(*date, reference))
+}
+
+fn year_candidates(month: u32, day: u32, reference: NaiveDate) -> impl Iterator<Item = NaiveDate> {
+    (reference.year() - 1..=reference.year() + 1)
+        .filter_map(move |year| NaiveDate::from_ymd_opt(year, month, day))
+}
+
+fn distance_days(date: NaiveDate, reference: NaiveDate) -> i64 {
+    (date - reference).num_days().abs()
+}
+
+impl Display for StatementLine<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "- Value date: {}", self.value_date)?;
+        if let Some(entry_date) = &self.entry_date {
+            writeln!(f, "- Entry date: {}", entry_date)?;
+        }
+        writeln!(f, "- Debit/Credit: {}", self.debit_credit_mark)?;
+        if let Some(funds_code) = self.funds_code {
+            writeln!(f, "- Funds code: {}", funds_code)?;
+        }
+        writeln!(f, "- Amount: {}", self.amount)?;
+        writeln!(
+            f,
+            "- Transaction type: {}",
+            self.transaction_type_identification_code
+        )?;
+        writeln!(f, "- Account owner reference: {}", self.account_owner_ref)?;
+        if let Some(bank_ref) = &self.bank_ref {
+            writeln!(f, "- Bank reference: {}", bank_ref)?;
+        }
+        if let Some(supplementary_details) = &self.supplementary_details {
+            writeln!(f, "- Supplementary details: {}", supplementary_details)?;
+        }
+        if let Some(information) = &self.information_to_account_owner {
+            writeln!(
+                f,
+                "- Information to account owner: {}",
+                information.join(" ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum StatementLineParseError {
+    Empty,
+    InvalidFormat,
+    InvalidValueDate(DateParseError),
+    InvalidEntryDate(DateParseError),
+    InvalidMark(StatementLineMarkParseError),
+    InvalidAmount(AmountParseError),
+    InvalidTransactionTypeIdentificationCode(TransactionTypeIdentificationCodeParseError),
+    InvalidAccountOwnerReference,
+    BankReferenceTooLong,
+    SupplementaryDetailsTooLong,
+}
+
+impl Display for StatementLineParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatementLineParseError::Empty => write!(f, "Statement line is empty"),
+            StatementLineParseError::InvalidFormat => {
+                write!(f, "Statement line has invalid format")
+            }
+            StatementLineParseError::InvalidValueDate(err) => {
+                write!(f, "Statement line has invalid value date: {}", err)
+            }
+            StatementLineParseError::InvalidEntryDate(err) => {
+                write!(f, "Statement line has invalid entry date: {}", err)
+            }
+            StatementLineParseError::InvalidMark(err) => write!(f, "{}", err),
+            StatementLineParseError::InvalidAmount(err) => {
+                write!(f, "Statement line has invalid amount: {}", err)
+            }
+            StatementLineParseError::InvalidTransactionTypeIdentificationCode(err) => {
+                write!(f, "{}", err)
+            }
+            StatementLineParseError::InvalidAccountOwnerReference => write!(
+                f,
+                "Reference for the account owner must be 1 to {} characters long",
+                ACCOUNT_OWNER_REFERENCE_MAX_LENGTH
+            ),
+            StatementLineParseError::BankReferenceTooLong => write!(
+                f,
+                "Reference of the account servicing institution exceeds {} character length",
+                BANK_REFERENCE_MAX_LENGTH
+            ),
+            StatementLineParseError::SupplementaryDetailsTooLong => write!(
+                f,
+                "Supplementary details exceed {} character length",
+                SUPPLEMENTARY_DETAILS_MAX_LENGTH
+            ),
+        }
+    }
+}
+
+impl Error for StatementLineParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_statement_line() {
+        let result = StatementLine::try_from("");
+        assert_eq!(result, Err(StatementLineParseError::Empty));
+        assert_eq!(result.unwrap_err().to_string(), "Statement line is empty");
+    }
+
+    #[test]
+    fn test_statement_line_invalid_value_date() {
+        let result = StatementLine::try_from("2313010301D100,00NTRFNONREF");
+        assert_eq!(
+            result,
+            Err(StatementLineParseError::InvalidValueDate(
+                DateParseError::InvalidValue
+            ))
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Statement line has invalid value date: Invalid date"
+        );
+    }
+
+    #[test]
+    fn test_statement_line_invalid_entry_date() {
+        let result = StatementLine::try_from("2303011301D100,00NTRFNONREF");
+        assert_eq!(
+            result,
+            Err(StatementLineParseError::InvalidEntryDate(
+                DateParseError::InvalidValue
+            ))
+        );
+    }
+
+    #[test]
+    fn test_statement_line_entry_date_across_year_boundary() {
+        let statement_line = StatementLine::try_from("2312310102D100,00NTRFNONREF").unwrap();
+        assert_eq!(
+            statement_line.entry_date(),
+            Some(&Date::new(NaiveDate::from_ymd_opt(2024, 1, 2).unwrap()))
+        );
+        assert_eq!(
+            statement_line.booking_date(),
+            statement_line.entry_date().unwrap()
+        );
+
+        let statement_line = StatementLine::try_from("2401021229D100,00NTRFNONREF").unwrap();
+        assert_eq!(
+            statement_line.entry_date(),
+            Some(&Date::new(NaiveDate::from_ymd_opt(2023, 12, 29).unwrap()))
+        );
+
+        let statement_line = StatementLine::try_from("230306D100,00NTRFNONREF").unwrap();
+        assert_eq!(statement_line.booking_date(), statement_line.value_date());
+    }
+
+    #[test]
+    fn test_statement_line_entry_date_leap_day() {
+        let statement_line = StatementLine::try_from("2403010229D100,00NTRFNONREF").unwrap();
+        assert_eq!(
+            statement_line.entry_date(),
+            Some(&Date::new(NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()))
+        );
+
+        let statement_line = StatementLine::try_from("2501020229D100,00NTRFNONREF").unwrap();
+        assert_eq!(
+            statement_line.entry_date(),
+            Some(&Date::new(NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()))
+        );
+
+        let result = StatementLine::try_from("2303010230D100,00NTRFNONREF");
+        assert_eq!(
+            result,
+            Err(StatementLineParseError::InvalidEntryDate(
+                DateParseError::InvalidValue
+            ))
+        );
+    }
+
+    #[test]
+    fn test_statement_line_back_valued_entry_date() {
+        let statement_line = StatementLine::try_from("2301010215D10,00NTRFNONREF").unwrap();
+        assert_eq!(
+            statement_line.entry_date(),
+            Some(&Date::new(NaiveDate::from_ymd_opt(2023, 2, 15).unwrap()))
+        );
+        assert_eq!(
+            statement_line.value_date(),
+            &Date::new(NaiveDate::from_ymd_opt(2023, 1, 1).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_statement_line_entry_date_within_statement_period() {
+        let date = |year, month, day| Date::new(NaiveDate::from_ymd_opt(year, month, day).unwrap());
+
+        let mut statement_line = StatementLine::try_from("2407020101D100,00NTRFNONREF").unwrap();
+        assert_eq!(statement_line.entry_date(), Some(&date(2024, 1, 1)));
+        statement_line.resolve_entry_date_within(&date(2024, 12, 31), &date(2025, 1, 2));
+        assert_eq!(statement_line.entry_date(), Some(&date(2025, 1, 1)));
+
+        let mut statement_line = StatementLine::try_from("2306300102D100,00NTRFNONREF").unwrap();
+        assert_eq!(statement_line.entry_date(), Some(&date(2023, 1, 2)));
+        statement_line.resolve_entry_date_within(&date(2023, 12, 31), &date(2024, 1, 2));
+        assert_eq!(statement_line.entry_date(), Some(&date(2023, 1, 2)));
+
+        let mut statement_line = StatementLine::try_from("2301021230D100,00NTRFNONREF").unwrap();
+        statement_line.resolve_entry_date_within(&date(2023, 1, 1), &date(2023, 1, 31));
+        assert_eq!(statement_line.entry_date(), Some(&date(2022, 12, 30)));
+    }
+
+    #[test]
+    fn test_statement_line_invalid_mark() {
+        let result = StatementLine::try_from("230301X100,00NTRFNONREF");
+        assert_eq!(
+            result,
+            Err(StatementLineParseError::InvalidMark(
+                StatementLineMarkParseError::InvalidValue
+            ))
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Invalid statement line debit/credit mark"
+        );
+    }
+
+    #[test]
+    fn test_statement_line_invalid_amount() {
+        let result = StatementLine::try_from("230301D100NTRFNONREF");
+        assert_eq!(
+            result,
+            Err(StatementLineParseError::InvalidAmount(
+                AmountParseError::InvalidFormat
+            ))
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Statement line has invalid amount: Invalid amount format"
+        );
+    }
+
+    #[test]
+    fn test_statement_line_invalid_transaction_type_identification_code() {
+        let result = StatementLine::try_from("230301D100,00N-RFNONREF");
+        assert_eq!(
+            result,
+            Err(
+                StatementLineParseError::InvalidTransactionTypeIdentificationCode(
+                    TransactionTypeIdentificationCodeParseError::InvalidCode
+                )
+            )
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Invalid transaction type identification code"
+        );
+    }
+
+    #[test]
+    fn test_statement_line_invalid_account_owner_reference() {
+        let result = StatementLine::try_from("230301D100,00NTRF");
+        assert_eq!(
+            result,
+            Err(StatementLineParseError::InvalidAccountOwnerReference)
+        );
+
+        let value = format!(
+            "230301D100,00NTRF{}",
+            "1".repeat(ACCOUNT_OWNER_REFERENCE_MAX_LENGTH + 1)
+        );
+        let result = StatementLine::try_from(value.as_str());
+        assert_eq!(
+            result,
+            Err(StatementLineParseError::InvalidAccountOwnerReference)
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            format!(
+                "Reference for the account owner must be 1 to {} characters long",
+                ACCOUNT_OWNER_REFERENCE_MAX_LENGTH
+            )
+        );
+    }
+
+    #[test]
+    fn test_statement_line_bank_reference_too_long() {
+        let value = format!(
+            "230301D100,00NTRFNONREF//{}",
+            "1".repeat(BANK_REFERENCE_MAX_LENGTH + 1)
+        );
+        let result = StatementLine::try_from(value.as_str());
+        assert_eq!(result, Err(StatementLineParseError::BankReferenceTooLong));
+    }
+
+    #[test]
+    fn test_statement_line_supplementary_details_too_long() {
+        let value = format!(
+            "230301D100,00NTRFNONREF\n{}",
+            "1".repeat(SUPPLEMENTARY_DETAILS_MAX_LENGTH + 1)
+        );
+        let result = StatementLine::try_from(value.as_str());
+        assert_eq!(
+            result,
+            Err(StatementLineParseError::SupplementaryDetailsTooLong)
+        );
+    }
+
+    #[test]
+    fn test_statement_line_invalid_format() {
+        let result = StatementLine::try_from("230301D100,00NTRFNONREF\nDETAILS\nMORE");
+        assert_eq!(result, Err(StatementLineParseError::InvalidFormat));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Statement line has invalid format"
+        );
+    }
+
+    #[test]
+    fn test_valid_statement_line() {
+        let result = StatementLine::try_from("2303060307RDE1234,5NCHK4711//BANKREF1\nCHEQUE 4711");
+        assert_eq!(
+            result,
+            Ok(StatementLine {
+                value_date: Date::new(NaiveDate::from_ymd_opt(2023, 3, 6).unwrap()),
+                entry_date: Some(Date::new(NaiveDate::from_ymd_opt(2023, 3, 7).unwrap())),
+                debit_credit_mark: StatementLineMark::ReversalOfDebit,
+                funds_code: Some('E'),
+                amount: Amount::try_from("1234,5").unwrap(),
+                transaction_type_identification_code: TransactionTypeIdentificationCode::NonSwift(
+                    TransactionType::Cheque
+                ),
+                account_owner_ref: "4711".into(),
+                bank_ref: Some("BANKREF1".into()),
+                supplementary_details: Some("CHEQUE 4711".into()),
+                information_to_account_owner: None,
+            })
+        );
+        assert_eq!(
+            result.unwrap().to_string(),
+            "- Value date: 2023-03-06\n- Entry date: 2023-03-07\n- Debit/Credit: Reversal of debit\n- Funds code: E\n- Amount: 1234.5\n- Transaction type: NCHK\n- Account owner reference: 4711\n- Bank reference: BANKREF1\n- Supplementary details: CHEQUE 4711\n"
+        );
+
+        let result = StatementLine::try_from("230306C500,NTRFNONREF");
+        assert_eq!(
+            result,
+            Ok(StatementLine {
+                value_date: Date::new(NaiveDate::from_ymd_opt(2023, 3, 6).unwrap()),
+                entry_date: None,
+                debit_credit_mark: StatementLineMark::Credit,
+                funds_code: None,
+                amount: Amount::try_from("500,").unwrap(),
+                transaction_type_identification_code: TransactionTypeIdentificationCode::NonSwift(
+                    TransactionType::Transfer
+                ),
+                account_owner_ref: "NONREF".into(),
+                bank_ref: None,
+                supplementary_details: None,
+                information_to_account_owner: None,
+            })
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, PartialEq)]
+pub enum StatementLineMark {
+    Credit,
+    Debit,
+    ReversalOfCredit,
+    ReversalOfDebit,
+}
+
+impl StatementLineMark {
+    pub fn code(&self) -> &'static str {
+        match self {
+            StatementLineMark::Credit => "C",
+            StatementLineMark::Debit => "D",
+            StatementLineMark::ReversalOfCredit => "RC",
+            StatementLineMark::ReversalOfDebit => "RD",
+        }
+    }
+}
+
+impl TryFrom<&str> for StatementLineMark {
+    type Error = StatementLineMarkParseError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "C" => Ok(Self::Credit),
+            "D" => Ok(Self::Debit),
+            "RC" => Ok(Self::ReversalOfCredit),
+            "RD" => Ok(Self::ReversalOfDebit),
+            _ => Err(StatementLineMarkParseError::InvalidValue),
+        }
+    }
+}
+
+impl Display for StatementLineMark {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatementLineMark::Credit => write!(f, "Credit"),
+            StatementLineMark::Debit => write!(f, "Debit"),
+            StatementLineMark::ReversalOfCredit => write!(f, "Reversal of credit"),
+            StatementLineMark::ReversalOfDebit => write!(f, "Reversal of debit"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum StatementLineMarkParseError {
+    InvalidValue,
+}
+
+impl Display for StatementLineMarkParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatementLineMarkParseError::InvalidValue => {
+                write!(f, "Invalid statement line debit/credit mark")
+            }
+        }
+    }
+}
+
+impl Error for StatementLineMarkParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_statement_line_mark() {
+        let result = StatementLineMark::try_from("X");
+        assert_eq!(result, Err(StatementLineMarkParseError::InvalidValue));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Invalid statement line debit/credit mark"
+        );
+    }
+
+    #[test]
+    fn test_valid_statement_line_mark() {
+        let result = StatementLineMark::try_from("C");
+        assert_eq!(result, Ok(StatementLineMark::Credit));
+        assert_eq!(result.unwrap().to_string(), "Credit");
+
+        let result = StatementLineMark::try_from("D");
+        assert_eq!(result, Ok(StatementLineMark::Debit));
+        assert_eq!(result.unwrap().to_string(), "Debit");
+
+        let result = StatementLineMark::try_from("RC");
+        assert_eq!(result, Ok(StatementLineMark::ReversalOfCredit));
+        assert_eq!(result.unwrap().to_string(), "Reversal of credit");
+
+        let result = StatementLineMark::try_from("RD");
+        assert_eq!(result, Ok(StatementLineMark::ReversalOfDebit));
+        assert_eq!(result.unwrap().to_string(), "Reversal of debit");
+    }
+}
@@ -0,0 +1,891 @@
+use crate::mt_940_customer_statement_message::statement_line::statement_line_mark::*;
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+const TRANSACTION_TYPE_IDENTIFICATION_CODE_LENGTH: usize = 4;
+const TRANSACTION_TYPES: [TransactionType<'static>; 57] = [
+    TransactionType::SecuritiesBankFees,
+    TransactionType::BillOfExchange,
+    TransactionType::BrokerageFee,
+    TransactionType::SecuritiesCorporateAction,
+    TransactionType::SecuritiesCashInLieu,
+    TransactionType::Charges,
+    TransactionType::Cheque,
+    TransactionType::CashLetter,
+    TransactionType::CashManagementItem,
+    TransactionType::NotionalPooling,
+    TransactionType::CompensationClaim,
+    TransactionType::Sweeping,
+    TransactionType::Topping,
+    TransactionType::ZeroBalancing,
+    TransactionType::Collection,
+    TransactionType::Commission,
+    TransactionType::SecuritiesCoupon,
+    TransactionType::DocumentaryCredit,
+    TransactionType::DirectDebit,
+    TransactionType::SecuritiesGainsDisbursement,
+    TransactionType::SecuritiesDividend,
+    TransactionType::EquivalentAmount,
+    TransactionType::SecuritiesExternalTransfer,
+    TransactionType::ForeignExchange,
+    TransactionType::Interest,
+    TransactionType::LockBox,
+    TransactionType::LoanDeposit,
+    TransactionType::SecuritiesMargin,
+    TransactionType::SecuritiesMaturity,
+    TransactionType::SecuritiesManagementFees,
+    TransactionType::Miscellaneous,
+    TransactionType::SecuritiesNewIssue,
+    TransactionType::OverdraftCharge,
+    TransactionType::SecuritiesOptions,
+    TransactionType::SecuritiesPurchase,
+    TransactionType::SecuritiesPairOff,
+    TransactionType::SecuritiesPrincipalPayDown,
+    TransactionType::SecuritiesTaxReclaim,
+    TransactionType::SecuritiesRedemption,
+    TransactionType::SecuritiesRights,
+    TransactionType::ReturnedItem,
+    TransactionType::SecuritiesSale,
+    TransactionType::Securities,
+    TransactionType::SecuritiesLending,
+    TransactionType::StandingOrder,
+    TransactionType::SecuritiesStampDuty,
+    TransactionType::SecuritiesSubscription,
+    TransactionType::SecuritiesSwap,
+    TransactionType::SecuritiesWithholdingTax,
+    TransactionType::TravellersCheque,
+    TransactionType::SecuritiesTripartiteCollateral,
+    TransactionType::SecuritiesInternalTransfer,
+    TransactionType::Transfer,
+    TransactionType::SecuritiesTransactionFee,
+    TransactionType::SecuritiesUnderwritingCommission,
+    TransactionType::ValueDateAdjustment,
+    TransactionType::SecuritiesWarrant,
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransactionTypeIdentificationCode<'a> {
+    Swift(u16),
+    NonSwift(TransactionType<'a>),
+    FirstAdvice(TransactionType<'a>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransactionType<'a> {
+    SecuritiesBankFees,
+    BillOfExchange,
+    BrokerageFee,
+    SecuritiesCorporateAction,
+    SecuritiesCashInLieu,
+    Charges,
+    Cheque,
+    CashLetter,
+    CashManagementItem,
+    NotionalPooling,
+    CompensationClaim,
+    Sweeping,
+    Topping,
+    ZeroBalancing,
+    Collection,
+    Commission,
+    SecuritiesCoupon,
+    DocumentaryCredit,
+    DirectDebit,
+    SecuritiesGainsDisbursement,
+    SecuritiesDividend,
+    EquivalentAmount,
+    SecuritiesExternalTransfer,
+    ForeignExchange,
+    Interest,
+    LockBox,
+    LoanDeposit,
+    SecuritiesMargin,
+    SecuritiesMaturity,
+    SecuritiesManagementFees,
+    Miscellaneous,
+    SecuritiesNewIssue,
+    OverdraftCharge,
+    SecuritiesOptions,
+    SecuritiesPurchase,
+    SecuritiesPairOff,
+    SecuritiesPrincipalPayDown,
+    SecuritiesTaxReclaim,
+    SecuritiesRedemption,
+    SecuritiesRights,
+    ReturnedItem,
+    SecuritiesSale,
+    Securities,
+    SecuritiesLending,
+    StandingOrder,
+    SecuritiesStampDuty,
+    SecuritiesSubscription,
+    SecuritiesSwap,
+    SecuritiesWithholdingTax,
+    TravellersCheque,
+    SecuritiesTripartiteCollateral,
+    SecuritiesInternalTransfer,
+    Transfer,
+    SecuritiesTransactionFee,
+    SecuritiesUnderwritingCommission,
+    ValueDateAdjustment,
+    SecuritiesWarrant,
+    Other(Cow<'a, str>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BankTransactionCode<'a> {
+    domain: &'a str,
+    family: &'a str,
+    sub_family: &'a str,
+}
+
+impl<'a> TransactionTypeIdentificationCode<'a> {
+    pub fn prefix(&self) -> char {
+        match self {
+            TransactionTypeIdentificationCode::Swift(_) => 'S',
+            TransactionTypeIdentificationCode::NonSwift(_) => 'N',
+            TransactionTypeIdentificationCode::FirstAdvice(_) => 'F',
+        }
+    }
+
+    pub fn transaction_type(&self) -> Option<&TransactionType<'a>> {
+        match self {
+            TransactionTypeIdentificationCode::Swift(_) => None,
+            TransactionTypeIdentificationCode::NonSwift(transaction_type)
+            | TransactionTypeIdentificationCode::FirstAdvice(transaction_type) => {
+                Some(transaction_type)
+            }
+        }
+    }
+
+    pub fn description(&self) -> Option<&'static str> {
+        match self {
+            TransactionTypeIdentificationCode::Swift(_) => Some("SWIFT transfer"),
+            TransactionTypeIdentificationCode::NonSwift(transaction_type)
+            | TransactionTypeIdentificationCode::FirstAdvice(transaction_type) => {
+                transaction_type.description()
+            }
+        }
+    }
+
+    pub fn bank_transaction_code(
+        &self,
+        debit_credit_mark: &StatementLineMark,
+    ) -> Option<BankTransactionCode<'static>> {
+        match self {
+            TransactionTypeIdentificationCode::Swift(_) => {
+                TransactionType::Transfer.bank_transaction_code(debit_credit_mark)
+            }
+            TransactionTypeIdentificationCode::NonSwift(transaction_type)
+            | TransactionTypeIdentificationCode::FirstAdvice(transaction_type) => {
+                transaction_type.bank_transaction_code(debit_credit_mark)
+            }
+        }
+    }
+
+    pub fn from_bank_transaction_code(
+        bank_transaction_code: &BankTransactionCode<'_>,
+    ) -> TransactionTypeIdentificationCode<'static> {
+        let marks = [StatementLineMark::Debit, StatementLineMark::Credit];
+        let transaction_type = TRANSACTION_TYPES
+            .into_iter()
+            .find(|transaction_type| {
+                marks.iter().any(|mark| {
+                    transaction_type.bank_transaction_code(mark).as_ref()
+                        == Some(bank_transaction_code)
+                })
+            })
+            .unwrap_or(TransactionType::Miscellaneous);
+        TransactionTypeIdentificationCode::NonSwift(transaction_type)
+    }
+
+    pub fn into_owned(self) -> TransactionTypeIdentificationCode<'static> {
+        match self {
+            TransactionTypeIdentificationCode::Swift(message_type) => {
+                TransactionTypeIdentificationCode::Swift(message_type)
+            }
+            TransactionTypeIdentificationCode::NonSwift(transaction_type) => {
+                TransactionTypeIdentificationCode::NonSwift(transaction_type.into_owned())
+            }
+            TransactionTypeIdentificationCode::FirstAdvice(transaction_type) => {
+                TransactionTypeIdentificationCode::FirstAdvice(transaction_type.into_owned())
+            }
+        }
+    }
+}
+
+impl TransactionType<'_> {
+    pub fn code(&self) -> &str {
+        match self {
+            TransactionType::SecuritiesBankFees => "BNK",
+            TransactionType::BillOfExchange => "BOE",
+            TransactionType::BrokerageFee => "BRF",
+            TransactionType::SecuritiesCorporateAction => "CAR",
+            TransactionType::SecuritiesCashInLieu => "CAS",
+            TransactionType::Charges => "CHG",
+            TransactionType::Cheque => "CHK",
+            TransactionType::CashLetter => "CLR",
+            TransactionType::CashManagementItem => "CMI",
+            TransactionType::NotionalPooling => "CMN",
+            TransactionType::CompensationClaim => "CMP",
+            TransactionType::Sweeping => "CMS",
+            TransactionType::Topping => "CMT",
+            TransactionType::ZeroBalancing => "CMZ",
+            TransactionType::Collection => "COL",
+            TransactionType::Commission => "COM",
+            TransactionType::SecuritiesCoupon => "CPN",
+            TransactionType::DocumentaryCredit => "DCR",
+            TransactionType::DirectDebit => "DDT",
+            TransactionType::SecuritiesGainsDisbursement => "DIS",
+            TransactionType::SecuritiesDividend => "DIV",
+            TransactionType::EquivalentAmount => "EQA",
+            TransactionType::SecuritiesExternalTransfer => "EXT",
+            TransactionType::ForeignExchange => "FEX",
+            TransactionType::Interest => "INT",
+            TransactionType::LockBox => "LBX",
+            TransactionType::LoanDeposit => "LDP",
+            TransactionType::SecuritiesMargin => "MAR",
+            TransactionType::SecuritiesMaturity => "MAT",
+            TransactionType::SecuritiesManagementFees => "MGT",
+            TransactionType::Miscellaneous => "MSC",
+            TransactionType::SecuritiesNewIssue => "NWI",
+            TransactionType::OverdraftCharge => "ODC",
+            TransactionType::SecuritiesOptions => "OPT",
+            TransactionType::SecuritiesPurchase => "PCH",
+            TransactionType::SecuritiesPairOff => "POP",
+            TransactionType::SecuritiesPrincipalPayDown => "PRN",
+            TransactionType::SecuritiesTaxReclaim => "REC",
+            TransactionType::SecuritiesRedemption => "RED",
+            TransactionType::SecuritiesRights => "RIG",
+            TransactionType::ReturnedItem => "RTI",
+            TransactionType::SecuritiesSale => "SAL",
+            TransactionType::Securities => "SEC",
+            TransactionType::SecuritiesLending => "SLE",
+            TransactionType::StandingOrder => "STO",
+            TransactionType::SecuritiesStampDuty => "STP",
+            TransactionType::SecuritiesSubscription => "SUB",
+            TransactionType::SecuritiesSwap => "SWP",
+            TransactionType::SecuritiesWithholdingTax => "TAX",
+            TransactionType::TravellersCheque => "TCK",
+            TransactionType::SecuritiesTripartiteCollateral => "TCM",
+            TransactionType::SecuritiesInternalTransfer => "TRA",
+            TransactionType::Transfer => "TRF",
+            TransactionType::SecuritiesTransactionFee => "TRN",
+            TransactionType::SecuritiesUnderwritingCommission => "UWC",
+            TransactionType::ValueDateAdjustment => "VDA",
+            TransactionType::SecuritiesWarrant => "WAR",
+            TransactionType::Other(code) => code,
+        }
+    }
+
+    pub fn description(&self) -> Option<&'static str> {
+        let description = match self {
+            TransactionType::SecuritiesBankFees => "Securities related item - bank fees",
+            TransactionType::BillOfExchange => "Bill of exchange",
+            TransactionType::BrokerageFee => "Brokerage fee",
+            TransactionType::SecuritiesCorporateAction => {
+                "Securities related item - corporate actions related"
+            }
+            TransactionType::SecuritiesCashInLieu => "Securities related item - cash in lieu",
+            TransactionType::Charges => "Charges and other expenses",
+            TransactionType::Cheque => "Cheques",
+            TransactionType::CashLetter => "Cash letters/cheques remittance",
+            TransactionType::CashManagementItem => "Cash management item - no detail",
+            TransactionType::NotionalPooling => "Cash management item - notional pooling",
+            TransactionType::CompensationClaim => "Compensation claims",
+            TransactionType::Sweeping => "Cash management item - sweeping",
+            TransactionType::Topping => "Cash management item - topping",
+            TransactionType::ZeroBalancing => "Cash management item - zero balancing",
+            TransactionType::Collection => "Collections",
+            TransactionType::Commission => "Commission",
+            TransactionType::SecuritiesCoupon => "Securities related item - coupon payments",
+            TransactionType::DocumentaryCredit => "Documentary credit",
+            TransactionType::DirectDebit => "Direct debit item",
+            TransactionType::SecuritiesGainsDisbursement => {
+                "Securities related item - gains disbursement"
+            }
+            TransactionType::SecuritiesDividend => "Securities related item - dividends",
+            TransactionType::EquivalentAmount => "Equivalent amount",
+            TransactionType::SecuritiesExternalTransfer => {
+                "Securities related item - external transfer for own account"
+            }
+            TransactionType::ForeignExchange => "Foreign exchange",
+            TransactionType::Interest => "Interest",
+            TransactionType::LockBox => "Lock box",
+            TransactionType::LoanDeposit => "Loan deposit",
+            TransactionType::SecuritiesMargin => {
+                "Securities related item - margin payments/receipts"
+            }
+            TransactionType::SecuritiesMaturity => "Securities related item - maturity",
+            TransactionType::SecuritiesManagementFees => {
+                "Securities related item - management fees"
+            }
+            TransactionType::Miscellaneous => "Miscellaneous",
+            TransactionType::SecuritiesNewIssue => {
+                "Securities related item - new issues distribution"
+            }
+            TransactionType::OverdraftCharge => "Overdraft charge",
+            TransactionType::SecuritiesOptions => "Securities related item - options",
+            TransactionType::SecuritiesPurchase => "Securities related item - purchase",
+            TransactionType::SecuritiesPairOff => "Securities related item - pair-off proceeds",
+            TransactionType::SecuritiesPrincipalPayDown => {
+                "Securities related item - principal pay-down/pay-up"
+            }
+            TransactionType::SecuritiesTaxReclaim => "Securities related item - tax reclaim",
+            TransactionType::SecuritiesRedemption => {
+                "Securities related item - redemption/withdrawal"
+            }
+            TransactionType::SecuritiesRights => "Securities related item - rights",
+            TransactionType::ReturnedItem => "Returned item",
+            TransactionType::SecuritiesSale => "Securities related item - sale",
+            TransactionType::Securities => "Securities",
+            TransactionType::SecuritiesLending => {
+                "Securities related item - securities lending related"
+            }
+            TransactionType::StandingOrder => "Standing order",
+            TransactionType::SecuritiesStampDuty => "Securities related item - stamp duty",
+            TransactionType::SecuritiesSubscription => "Securities related item - subscription",
+            TransactionType::SecuritiesSwap => "Securities related item - SWAP payment",
+            TransactionType::SecuritiesWithholdingTax => {
+                "Securities related item - withholding tax payment"
+            }
+            TransactionType::TravellersCheque => "Travellers cheques",
+            TransactionType::SecuritiesTripartiteCollateral => {
+                "Securities related item - tripartite collateral management"
+            }
+            TransactionType::SecuritiesInternalTransfer => {
+                "Securities related item - internal transfer for own account"
+            }
+            TransactionType::Transfer => "Transfer",
+            TransactionType::SecuritiesTransactionFee => {
+                "Securities related item - transaction fee"
+            }
+            TransactionType::SecuritiesUnderwritingCommission => {
+                "Securities related item - underwriting commission"
+            }
+            TransactionType::ValueDateAdjustment => "Value date adjustment",
+            TransactionType::SecuritiesWarrant => "Securities related item - warrant",
+            TransactionType::Other(_) => return None,
+        };
+        Some(description)
+    }
+
+    pub fn bank_transaction_code(
+        &self,
+        debit_credit_mark: &StatementLineMark,
+    ) -> Option<BankTransactionCode<'static>> {
+        let is_debit = matches!(
+            debit_credit_mark,
+            StatementLineMark::Debit | StatementLineMark::ReversalOfCredit
+        );
+        let bank_transaction_code = match self {
+            TransactionType::SecuritiesBankFees if is_debit => {
+                BankTransactionCode::new("SECU", "MDOP", "CHRG")
+            }
+            TransactionType::SecuritiesBankFees => BankTransactionCode::new("SECU", "MCOP", "CHRG"),
+            TransactionType::BillOfExchange => BankTransactionCode::new("PMNT", "DRFT", "OTHR"),
+            TransactionType::BrokerageFee if is_debit => {
+                BankTransactionCode::new("SECU", "MDOP", "CHRG")
+            }
+            TransactionType::BrokerageFee => BankTransactionCode::new("SECU", "MCOP", "CHRG"),
+            TransactionType::SecuritiesCorporateAction => {
+                BankTransactionCode::new("SECU", "CORP", "OTHR")
+            }
+            TransactionType::SecuritiesCashInLieu => {
+                BankTransactionCode::new("SECU", "CORP", "OTHR")
+            }
+            TransactionType::Charges if is_debit => {
+                BankTransactionCode::new("ACMT", "MDOP", "CHRG")
+            }
+            TransactionType::Charges => BankTransactionCode::new("ACMT", "MCOP", "CHRG"),
+            TransactionType::Cheque if is_debit => BankTransactionCode::new("PMNT", "ICHQ", "CCHQ"),
+            TransactionType::Cheque => BankTransactionCode::new("PMNT", "RCHQ", "CCHQ"),
+            TransactionType::CashLetter if is_debit => {
+                BankTransactionCode::new("PMNT", "ICHQ", "OTHR")
+            }
+            TransactionType::CashLetter => BankTransactionCode::new("PMNT", "RCHQ", "OTHR"),
+            TransactionType::CashManagementItem => BankTransactionCode::new("CAMT", "ACCB", "OTHR"),
+            TransactionType::NotionalPooling => BankTransactionCode::new("CAMT", "ACCB", "OTHR"),
+            TransactionType::CompensationClaim if is_debit => {
+                BankTransactionCode::new("PMNT", "MDOP", "OTHR")
+            }
+            TransactionType::CompensationClaim => BankTransactionCode::new("PMNT", "MCOP", "OTHR"),
+            TransactionType::Sweeping => BankTransactionCode::new("CAMT", "ACCB", "SWEP"),
+            TransactionType::Topping => BankTransactionCode::new("CAMT", "ACCB", "TOPG"),
+            TransactionType::ZeroBalancing => BankTransactionCode::new("CAMT", "ACCB", "ZABA"),
+            TransactionType::Collection => BankTransactionCode::new("TRAD", "CLNC", "OTHR"),
+            TransactionType::Commission if is_debit => {
+                BankTransactionCode::new("ACMT", "MDOP", "COMM")
+            }
+            TransactionType::Commission => BankTransactionCode::new("ACMT", "MCOP", "COMM"),
+            TransactionType::SecuritiesCoupon => BankTransactionCode::new("SECU", "CORP", "INTR"),
+            TransactionType::DocumentaryCredit => BankTransactionCode::new("TRAD", "DCCT", "OTHR"),
+            TransactionType::DirectDebit if is_debit => {
+                BankTransactionCode::new("PMNT", "IDDT", "ESDD")
+            }
+            TransactionType::DirectDebit => BankTransactionCode::new("PMNT", "RDDT", "ESDD"),
+            TransactionType::SecuritiesGainsDisbursement => {
+                BankTransactionCode::new("SECU", "CORP", "OTHR")
+            }
+            TransactionType::SecuritiesDividend => BankTransactionCode::new("SECU", "CORP", "DVCA"),
+            TransactionType::EquivalentAmount => BankTransactionCode::new("ACMT", "NTAV", "NTAV"),
+            TransactionType::SecuritiesExternalTransfer => {
+                BankTransactionCode::new("SECU", "SETT", "OTHR")
+            }
+            TransactionType::ForeignExchange => BankTransactionCode::new("FORX", "SPOT", "OTHR"),
+            TransactionType::Interest if is_debit => {
+                BankTransactionCode::new("ACMT", "MDOP", "INTR")
+            }
+            TransactionType::Interest => BankTransactionCode::new("ACMT", "MCOP", "INTR"),
+            TransactionType::LockBox => BankTransactionCode::new("PMNT", "LBOX", "OTHR"),
+            TransactionType::LoanDeposit => BankTransactionCode::new("LDAS", "NTAV", "NTAV"),
+            TransactionType::SecuritiesMargin => BankTransactionCode::new("SECU", "COLL", "OTHR"),
+            TransactionType::SecuritiesMaturity => BankTransactionCode::new("SECU", "CORP", "REDM"),
+            TransactionType::SecuritiesManagementFees if is_debit => {
+                BankTransactionCode::new("SECU", "MDOP", "CHRG")
+            }
+            TransactionType::SecuritiesManagementFees => {
+                BankTransactionCode::new("SECU", "MCOP", "CHRG")
+            }
+            TransactionType::Miscellaneous if is_debit => {
+                BankTransactionCode::new("ACMT", "MDOP", "OTHR")
+            }
+            TransactionType::Miscellaneous => BankTransactionCode::new("ACMT", "MCOP", "OTHR"),
+            TransactionType::SecuritiesNewIssue => BankTransactionCode::new("SECU", "NTAV", "NTAV"),
+            TransactionType::OverdraftCharge if is_debit => {
+                BankTransactionCode::new("ACMT", "MDOP", "ODFT")
+            }
+            TransactionType::OverdraftCharge => BankTransactionCode::new("ACMT", "MCOP", "ODFT"),
+            TransactionType::SecuritiesOptions => BankTransactionCode::new("SECU", "OPTN", "OTHR"),
+            TransactionType::SecuritiesPurchase => BankTransactionCode::new("SECU", "SETT", "TRAD"),
+            TransactionType::SecuritiesPairOff => BankTransactionCode::new("SECU", "SETT", "PAIR"),
+            TransactionType::SecuritiesPrincipalPayDown => {
+                BankTransactionCode::new("SECU", "CORP", "PRII")
+            }
+            TransactionType::SecuritiesTaxReclaim => {
+                BankTransactionCode::new("SECU", "CORP", "TREC")
+            }
+            TransactionType::SecuritiesRedemption => {
+                BankTransactionCode::new("SECU", "CORP", "REDM")
+            }
+            TransactionType::SecuritiesRights => BankTransactionCode::new("SECU", "CORP", "RHTS"),
+            TransactionType::ReturnedItem if is_debit => {
+                BankTransactionCode::new("PMNT", "ICDT", "RRTN")
+            }
+            TransactionType::ReturnedItem => BankTransactionCode::new("PMNT", "RCDT", "RRTN"),
+            TransactionType::SecuritiesSale => BankTransactionCode::new("SECU", "SETT", "TRAD"),
+            TransactionType::Securities => BankTransactionCode::new("SECU", "NTAV", "NTAV"),
+            TransactionType::SecuritiesLending => BankTransactionCode::new("SECU", "LACK", "OTHR"),
+            TransactionType::StandingOrder if is_debit => {
+                BankTransactionCode::new("PMNT", "ICDT", "STDO")
+            }
+            TransactionType::StandingOrder => BankTransactionCode::new("PMNT", "RCDT", "STDO"),
+            TransactionType::SecuritiesStampDuty if is_debit => {
+                BankTransactionCode::new("SECU", "MDOP", "TAXE")
+            }
+            TransactionType::SecuritiesStampDuty => {
+                BankTransactionCode::new("SECU", "MCOP", "TAXE")
+            }
+            TransactionType::SecuritiesSubscription => {
+                BankTransactionCode::new("SECU", "SETT", "SUBS")
+            }
+            TransactionType::SecuritiesSwap => BankTransactionCode::new("SECU", "SWAP", "OTHR"),
+            TransactionType::SecuritiesWithholdingTax if is_debit => {
+                BankTransactionCode::new("SECU", "MDOP", "TAXE")
+            }
+            TransactionType::SecuritiesWithholdingTax => {
+                BankTransactionCode::new("SECU", "MCOP", "TAXE")
+            }
+            TransactionType::TravellersCheque if is_debit => {
+                BankTransactionCode::new("PMNT", "ICHQ", "OTHR")
+            }
+            TransactionType::TravellersCheque => BankTransactionCode::new("PMNT", "RCHQ", "OTHR"),
+            TransactionType::SecuritiesTripartiteCollateral => {
+                BankTransactionCode::new("SECU", "COLL", "OTHR")
+            }
+            TransactionType::SecuritiesInternalTransfer => {
+                BankTransactionCode::new("SECU", "SETT", "OTHR")
+            }
+            TransactionType::Transfer if is_debit => {
+                BankTransactionCode::new("PMNT", "ICDT", "DMCT")
+            }
+            TransactionType::Transfer => BankTransactionCode::new("PMNT", "RCDT", "DMCT"),
+            TransactionType::SecuritiesTransactionFee if is_debit => {
+                BankTransactionCode::new("SECU", "MDOP", "CHRG")
+            }
+            TransactionType::SecuritiesTransactionFee => {
+                BankTransactionCode::new("SECU", "MCOP", "CHRG")
+            }
+            TransactionType::SecuritiesUnderwritingCommission if is_debit => {
+                BankTransactionCode::new("SECU", "MDOP", "COMM")
+            }
+            TransactionType::SecuritiesUnderwritingCommission => {
+                BankTransactionCode::new("SECU", "MCOP", "COMM")
+            }
+            TransactionType::ValueDateAdjustment if is_debit => {
+                BankTransactionCode::new("ACMT", "MDOP", "ADJT")
+            }
+            TransactionType::ValueDateAdjustment => {
+                BankTransactionCode::new("ACMT", "MCOP", "ADJT")
+            }
+            TransactionType::SecuritiesWarrant => BankTransactionCode::new("SECU", "CORP", "EXWA"),
+            TransactionType::Other(_) => return None,
+        };
+        Some(bank_transaction_code)
+    }
+
+    pub fn into_owned(self) -> TransactionType<'static> {
+        match self {
+            TransactionType::Other(code) => TransactionType::Other(Cow::Owned(code.into_owned())),
+            TransactionType::SecuritiesBankFees => TransactionType::SecuritiesBankFees,
+            TransactionType::BillOfExchange => TransactionType::BillOfExchange,
+            TransactionType::BrokerageFee => TransactionType::BrokerageFee,
+            TransactionType::SecuritiesCorporateAction => {
+                TransactionType::SecuritiesCorporateAction
+            }
+            TransactionType::SecuritiesCashInLieu => TransactionType::SecuritiesCashInLieu,
+            TransactionType::Charges => TransactionType::Charges,
+            TransactionType::Cheque => TransactionType::Cheque,
+            TransactionType::CashLetter => TransactionType::CashLetter,
+            TransactionType::CashManagementItem => TransactionType::CashManagementItem,
+            TransactionType::NotionalPooling => TransactionType::NotionalPooling,
+            TransactionType::CompensationClaim => TransactionType::CompensationClaim,
+            TransactionType::Sweeping => TransactionType::Sweeping,
+            TransactionType::Topping => TransactionType::Topping,
+            TransactionType::ZeroBalancing => TransactionType::ZeroBalancing,
+            TransactionType::Collection => TransactionType::Collection,
+            TransactionType::Commission => TransactionType::Commission,
+            TransactionType::SecuritiesCoupon => TransactionType::SecuritiesCoupon,
+            TransactionType::DocumentaryCredit => TransactionType::DocumentaryCredit,
+            TransactionType::DirectDebit => TransactionType::DirectDebit,
+            TransactionType::SecuritiesGainsDisbursement => {
+                TransactionType::SecuritiesGainsDisbursement
+            }
+            TransactionType::SecuritiesDividend => TransactionType::SecuritiesDividend,
+            TransactionType::EquivalentAmount => TransactionType::EquivalentAmount,
+            TransactionType::SecuritiesExternalTransfer => {
+                TransactionType::SecuritiesExternalTransfer
+            }
+            TransactionType::ForeignExchange => TransactionType::ForeignExchange,
+            TransactionType::Interest => TransactionType::Interest,
+            TransactionType::LockBox => TransactionType::LockBox,
+            TransactionType::LoanDeposit => TransactionType::LoanDeposit,
+            TransactionType::SecuritiesMargin => TransactionType::SecuritiesMargin,
+            TransactionType::SecuritiesMaturity => TransactionType::SecuritiesMaturity,
+            TransactionType::SecuritiesManagementFees => TransactionType::SecuritiesManagementFees,
+            TransactionType::Miscellaneous => TransactionType::Miscellaneous,
+            TransactionType::SecuritiesNewIssue => TransactionType::SecuritiesNewIssue,
+            TransactionType::OverdraftCharge => TransactionType::OverdraftCharge,
+            TransactionType::SecuritiesOptions => TransactionType::SecuritiesOptions,
+            TransactionType::SecuritiesPurchase => TransactionType::SecuritiesPurchase,
+            TransactionType::SecuritiesPairOff => TransactionType::SecuritiesPairOff,
+            TransactionType::SecuritiesPrincipalPayDown => {
+                TransactionType::SecuritiesPrincipalPayDown
+            }
+            TransactionType::SecuritiesTaxReclaim => TransactionType::SecuritiesTaxReclaim,
+            TransactionType::SecuritiesRedemption => TransactionType::SecuritiesRedemption,
+            TransactionType::SecuritiesRights => TransactionType::SecuritiesRights,
+            TransactionType::ReturnedItem => TransactionType::ReturnedItem,
+            TransactionType::SecuritiesSale => TransactionType::SecuritiesSale,
+            TransactionType::Securities => TransactionType::Securities,
+            TransactionType::SecuritiesLending => TransactionType::SecuritiesLending,
+            TransactionType::StandingOrder => TransactionType::StandingOrder,
+            TransactionType::SecuritiesStampDuty => TransactionType::SecuritiesStampDuty,
+            TransactionType::SecuritiesSubscription => TransactionType::SecuritiesSubscription,
+            TransactionType::SecuritiesSwap => TransactionType::SecuritiesSwap,
+            TransactionType::SecuritiesWithholdingTax => TransactionType::SecuritiesWithholdingTax,
+            TransactionType::TravellersCheque => TransactionType::TravellersCheque,
+            TransactionType::SecuritiesTripartiteCollateral => {
+                TransactionType::SecuritiesTripartiteCollateral
+            }
+            TransactionType::SecuritiesInternalTransfer => {
+                TransactionType::SecuritiesInternalTransfer
+            }
+            TransactionType::Transfer => TransactionType::Transfer,
+            TransactionType::SecuritiesTransactionFee => TransactionType::SecuritiesTransactionFee,
+            TransactionType::SecuritiesUnderwritingCommission => {
+                TransactionType::SecuritiesUnderwritingCommission
+            }
+            TransactionType::ValueDateAdjustment => TransactionType::ValueDateAdjustment,
+            TransactionType::SecuritiesWarrant => TransactionType::SecuritiesWarrant,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for TransactionType<'a> {
+    fn from(value: &'a str) -> Self {
+        match value {
+            "BNK" => TransactionType::SecuritiesBankFees,
+            "BOE" => TransactionType::BillOfExchange,
+            "BRF" => TransactionType::BrokerageFee,
+            "CAR" => TransactionType::SecuritiesCorporateAction,
+            "CAS" => TransactionType::SecuritiesCashInLieu,
+            "CHG" => TransactionType::Charges,
+            "CHK" => TransactionType::Cheque,
+            "CLR" => TransactionType::CashLetter,
+            "CMI" => TransactionType::CashManagementItem,
+            "CMN" => TransactionType::NotionalPooling,
+            "CMP" => TransactionType::CompensationClaim,
+            "CMS" => TransactionType::Sweeping,
+            "CMT" => TransactionType::Topping,
+            "CMZ" => TransactionType::ZeroBalancing,
+            "COL" => TransactionType::Collection,
+            "COM" => TransactionType::Commission,
+            "CPN" => TransactionType::SecuritiesCoupon,
+            "DCR" => TransactionType::DocumentaryCredit,
+            "DDT" => TransactionType::DirectDebit,
+            "DIS" => TransactionType::SecuritiesGainsDisbursement,
+            "DIV" => TransactionType::SecuritiesDividend,
+            "EQA" => TransactionType::EquivalentAmount,
+            "EXT" => TransactionType::SecuritiesExternalTransfer,
+            "FEX" => TransactionType::ForeignExchange,
+            "INT" => TransactionType::Interest,
+            "LBX" => TransactionType::LockBox,
+            "LDP" => TransactionType::LoanDeposit,
+            "MAR" => TransactionType::SecuritiesMargin,
+            "MAT" => TransactionType::SecuritiesMaturity,
+            "MGT" => TransactionType::SecuritiesManagementFees,
+            "MSC" => TransactionType::Miscellaneous,
+            "NWI" => TransactionType::SecuritiesNewIssue,
+            "ODC" => TransactionType::OverdraftCharge,
+            "OPT" => TransactionType::SecuritiesOptions,
+            "PCH" => TransactionType::SecuritiesPurchase,
+            "POP" => TransactionType::SecuritiesPairOff,
+            "PRN" => TransactionType::SecuritiesPrincipalPayDown,
+            "REC" => TransactionType::SecuritiesTaxReclaim,
+            "RED" => TransactionType::SecuritiesRedemption,
+            "RIG" => TransactionType::SecuritiesRights,
+            "RTI" => TransactionType::ReturnedItem,
+            "SAL" => TransactionType::SecuritiesSale,
+            "SEC" => TransactionType::Securities,
+            "SLE" => TransactionType::SecuritiesLending,
+            "STO" => TransactionType::StandingOrder,
+            "STP" => TransactionType::SecuritiesStampDuty,
+            "SUB" => TransactionType::SecuritiesSubscription,
+            "SWP" => TransactionType::SecuritiesSwap,
+            "TAX" => TransactionType::SecuritiesWithholdingTax,
+            "TCK" => TransactionType::TravellersCheque,
+            "TCM" => TransactionType::SecuritiesTripartiteCollateral,
+            "TRA" => TransactionType::SecuritiesInternalTransfer,
+            "TRF" => TransactionType::Transfer,
+            "TRN" => TransactionType::SecuritiesTransactionFee,
+            "UWC" => TransactionType::SecuritiesUnderwritingCommission,
+            "VDA" => TransactionType::ValueDateAdjustment,
+            "WAR" => TransactionType::SecuritiesWarrant,
+            _ => TransactionType::Other(Cow::Borrowed(value)),
+        }
+    }
+}
+
+impl<'a> BankTransactionCode<'a> {
+    pub const fn new(domain: &'a str, family: &'a str, sub_family: &'a str) -> Self {
+        Self {
+            domain,
+            family,
+            sub_family,
+        }
+    }
+
+    pub fn domain(&self) -> &'a str {
+        self.domain
+    }
+
+    pub fn family(&self) -> &'a str {
+        self.family
+    }
+
+    pub fn sub_family(&self) -> &'a str {
+        self.sub_family
+    }
+}
+
+impl<'a> TryFrom<&'a str> for TransactionTypeIdentificationCode<'a> {
+    type Error = TransactionTypeIdentificationCodeParseError;
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        if value.len() != TRANSACTION_TYPE_IDENTIFICATION_CODE_LENGTH || !value.is_ascii() {
+            return Err(TransactionTypeIdentificationCodeParseError::InvalidLength);
+        }
+        let (prefix, code) = value.split_at(1);
+        if !code
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+        {
+            return Err(TransactionTypeIdentificationCodeParseError::InvalidCode);
+        }
+        match prefix {
+            "S" => code
+                .parse()
+                .map(TransactionTypeIdentificationCode::Swift)
+                .map_err(|_| TransactionTypeIdentificationCodeParseError::InvalidCode),
+            "N" => Ok(TransactionTypeIdentificationCode::NonSwift(code.into())),
+            "F" => Ok(TransactionTypeIdentificationCode::FirstAdvice(code.into())),
+            _ => Err(TransactionTypeIdentificationCodeParseError::InvalidPrefix),
+        }
+    }
+}
+
+impl Display for TransactionTypeIdentificationCode<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransactionTypeIdentificationCode::Swift(message_type) => {
+                write!(f, "S{:03}", message_type)
+            }
+            TransactionTypeIdentificationCode::NonSwift(transaction_type)
+            | TransactionTypeIdentificationCode::FirstAdvice(transaction_type) => {
+                write!(f, "{}{}", self.prefix(), transaction_type.code())
+            }
+        }
+    }
+}
+
+impl Display for BankTransactionCode<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}/{}", self.domain, self.family, self.sub_family)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TransactionTypeIdentificationCodeParseError {
+    InvalidLength,
+    InvalidPrefix,
+    InvalidCode,
+}
+
+impl Display for TransactionTypeIdentificationCodeParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransactionTypeIdentificationCodeParseError::InvalidLength => write!(
+                f,
+                "Transaction type identification code must be {} characters long",
+                TRANSACTION_TYPE_IDENTIFICATION_CODE_LENGTH
+            ),
+            TransactionTypeIdentificationCodeParseError::InvalidPrefix => write!(
+                f,
+                "Transaction type identification code must start with S, N or F"
+            ),
+            TransactionTypeIdentificationCodeParseError::InvalidCode => {
+                write!(f, "Invalid transaction type identification code")
+            }
+        }
+    }
+}
+
+impl Error for TransactionTypeIdentificationCodeParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_transaction_type_identification_code() {
+        let result = TransactionTypeIdentificationCode::try_from("NTR");
+        assert_eq!(
+            result,
+            Err(TransactionTypeIdentificationCodeParseError::InvalidLength)
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Transaction type identification code must be 4 characters long"
+        );
+
+        let result = TransactionTypeIdentificationCode::try_from("XTRF");
+        assert_eq!(
+            result,
+            Err(TransactionTypeIdentificationCodeParseError::InvalidPrefix)
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Transaction type identification code must start with S, N or F"
+        );
+
+        let result = TransactionTypeIdentificationCode::try_from("STRF");
+        assert_eq!(
+            result,
+            Err(TransactionTypeIdentificationCodeParseError::InvalidCode)
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Invalid transaction type identification code"
+        );
+    }
+
+    #[test]
+    fn test_valid_transaction_type_identification_code() {
+        let code = TransactionTypeIdentificationCode::try_from("NTRF").unwrap();
+        assert_eq!(
+            code,
+            TransactionTypeIdentificationCode::NonSwift(TransactionType::Transfer)
+        );
+        assert_eq!(code.description(), Some("Transfer"));
+        assert_eq!(code.to_string(), "NTRF");
+
+        let code = TransactionTypeIdentificationCode::try_from("FCHK").unwrap();
+        assert_eq!(
+            code,
+            TransactionTypeIdentificationCode::FirstAdvice(TransactionType::Cheque)
+        );
+        assert_eq!(code.to_string(), "FCHK");
+
+        let code = TransactionTypeIdentificationCode::try_from("S103").unwrap();
+        assert_eq!(code, TransactionTypeIdentificationCode::Swift(103));
+        assert_eq!(code.to_string(), "S103");
+
+        let code = TransactionTypeIdentificationCode::try_from("NXY1").unwrap();
+        assert_eq!(
+            code,
+            TransactionTypeIdentificationCode::NonSwift(TransactionType::Other("XY1".into()))
+        );
+        assert_eq!(code.description(), None);
+        assert_eq!(code.to_string(), "NXY1");
+    }
+
+    #[test]
+    fn test_transaction_types_round_trip() {
+        for transaction_type in TRANSACTION_TYPES {
+            assert_eq!(
+                TransactionType::from(transaction_type.code()),
+                transaction_type
+            );
+            assert!(transaction_type.description().is_some());
+        }
+    }
+
+    #[test]
+    fn test_bank_transaction_code() {
+        let code = TransactionTypeIdentificationCode::try_from("NTRF").unwrap();
+        let debit = code
+            .bank_transaction_code(&StatementLineMark::Debit)
+            .unwrap();
+        assert_eq!(debit, BankTransactionCode::new("PMNT", "ICDT", "DMCT"));
+        assert_eq!(debit.to_string(), "PMNT/ICDT/DMCT");
+        let credit = code
+            .bank_transaction_code(&StatementLineMark::Credit)
+            .unwrap();
+        assert_eq!(credit, BankTransactionCode::new("PMNT", "RCDT", "DMCT"));
+        let reversal = code
+            .bank_transaction_code(&StatementLineMark::ReversalOfCredit)
+            .unwrap();
+        assert_eq!(reversal, debit);
+
+        assert_eq!(
+            TransactionTypeIdentificationCode::from_bank_transaction_code(&credit),
+            code
+        );
+        assert_eq!(
+            TransactionTypeIdentificationCode::from_bank_transaction_code(
+                &BankTransactionCode::new("ACMT", "MDOP", "CHRG")
+            ),
+            TransactionTypeIdentificationCode::NonSwift(TransactionType::Charges)
+        );
+        assert_eq!(
+            TransactionTypeIdentificationCode::from_bank_transaction_code(
+                &BankTransactionCode::new("XXXX", "XXXX", "XXXX")
+            ),
+            TransactionTypeIdentificationCode::NonSwift(TransactionType::Miscellaneous)
+        );
+
+        let code = TransactionTypeIdentificationCode::try_from("NXY1").unwrap();
+        assert_eq!(code.bank_transaction_code(&StatementLineMark::Debit), None);
+    }
+}
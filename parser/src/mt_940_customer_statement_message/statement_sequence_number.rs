@@ -5,11 +5,21 @@ const SEQUENCE_NUMBER_MAX_LENGTH: usize = 5;
 const STATEMENT_NUMBER_MAX_LENGTH: usize = 5;
 
 #[derive(Debug, PartialEq)]
-pub(super) struct StatementSequenceNumber {
+pub struct StatementSequenceNumber {
     statement_number: u16,
     sequence_number: Option<u16>,
 }
 
+impl StatementSequenceNumber {
+    pub fn statement_number(&self) -> u16 {
+        self.statement_number
+    }
+
+    pub fn sequence_number(&self) -> Option<u16> {
+        self.sequence_number
+    }
+}
+
 impl TryFrom<&str> for StatementSequenceNumber {
     type Error = StatementSequenceNumberParseError;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
@@ -54,21 +64,15 @@ impl TryFrom<&str> for StatementSequenceNumber {
 
 impl Display for StatementSequenceNumber {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        if self.sequence_number.is_none() {
-            write!(f, "{}", self.statement_number)
-        } else {
-            write!(
-                f,
-                "{}/{}",
-                self.statement_number,
-                self.sequence_number.unwrap()
-            )
+        match self.sequence_number {
+            None => write!(f, "{}", self.statement_number),
+            Some(sequence_number) => write!(f, "{}/{}", self.statement_number, sequence_number),
         }
     }
 }
 
 #[derive(Debug, PartialEq)]
-pub(super) enum StatementSequenceNumberParseError {
+pub enum StatementSequenceNumberParseError {
     Empty,
     InvalidStatementSequenceNumberFormat,
     StatementNumberTooLong,
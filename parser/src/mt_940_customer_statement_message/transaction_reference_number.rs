@@ -1,41 +1,59 @@
+use std::borrow::Cow;
 use std::error::Error;
 use std::fmt::Display;
 
-const TRANSACTION_REFERENCE_NUMBER_MAX_LENGTH: usize = 16;
+pub(crate) const TRANSACTION_REFERENCE_NUMBER_MAX_LENGTH: usize = 16;
 
 #[derive(Debug, PartialEq)]
-pub(super) struct TransactionReferenceNumber(String);
+pub struct TransactionReferenceNumber<'a>(Cow<'a, str>);
 
-impl TryFrom<&str> for TransactionReferenceNumber {
-    type Error = TransactionReferenceNumberParseError;
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
+impl<'a> TransactionReferenceNumber<'a> {
+    pub(crate) fn parse(
+        value: &'a str,
+        max_length: usize,
+    ) -> Result<Self, TransactionReferenceNumberParseError> {
         let value = value.trim();
         if value.is_empty() {
             return Err(TransactionReferenceNumberParseError::Empty);
         }
 
-        if value.len() > TRANSACTION_REFERENCE_NUMBER_MAX_LENGTH {
-            return Err(TransactionReferenceNumberParseError::TooLong);
+        if value.len() > max_length {
+            return Err(TransactionReferenceNumberParseError::TooLong(max_length));
         }
 
         if value.starts_with("/") || value.ends_with("/") || value.contains("//") {
             return Err(TransactionReferenceNumberParseError::InvalidFormat);
         }
 
-        Ok(Self(value.to_string()))
+        Ok(Self(Cow::Borrowed(value)))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_owned(self) -> TransactionReferenceNumber<'static> {
+        TransactionReferenceNumber(Cow::Owned(self.0.into_owned()))
+    }
+}
+
+impl<'a> TryFrom<&'a str> for TransactionReferenceNumber<'a> {
+    type Error = TransactionReferenceNumberParseError;
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        Self::parse(value, TRANSACTION_REFERENCE_NUMBER_MAX_LENGTH)
     }
 }
 
-impl Display for TransactionReferenceNumber {
+impl Display for TransactionReferenceNumber<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
     }
 }
 
 #[derive(Debug, PartialEq)]
-pub(super) enum TransactionReferenceNumberParseError {
+pub enum TransactionReferenceNumberParseError {
     Empty,
-    TooLong,
+    TooLong(usize),
     InvalidFormat,
 }
 
@@ -45,10 +63,10 @@ impl Display for TransactionReferenceNumberParseError {
             TransactionReferenceNumberParseError::Empty => {
                 write!(f, "Transaction reference number cannot be empty")
             }
-            TransactionReferenceNumberParseError::TooLong => write!(
+            TransactionReferenceNumberParseError::TooLong(max_length) => write!(
                 f,
                 "Transaction reference number exceeds {} character length",
-                TRANSACTION_REFERENCE_NUMBER_MAX_LENGTH
+                max_length
             ),
             TransactionReferenceNumberParseError::InvalidFormat => {
                 write!(f, "Transaction reference number has invalid format")
@@ -75,11 +93,14 @@ mod tests {
 
     #[test]
     fn test_long_transaction_reference_number() {
-        let result = TransactionReferenceNumber::try_from(
-            "1".repeat(TRANSACTION_REFERENCE_NUMBER_MAX_LENGTH + 1)
-                .as_str(),
+        let value = "1".repeat(TRANSACTION_REFERENCE_NUMBER_MAX_LENGTH + 1);
+        let result = TransactionReferenceNumber::try_from(value.as_str());
+        assert_eq!(
+            result,
+            Err(TransactionReferenceNumberParseError::TooLong(
+                TRANSACTION_REFERENCE_NUMBER_MAX_LENGTH
+            ))
         );
-        assert_eq!(result, Err(TransactionReferenceNumberParseError::TooLong));
         assert_eq!(
             result.unwrap_err().to_string(),
             format!(
@@ -117,10 +138,7 @@ mod tests {
     #[test]
     fn test_valid_transaction_reference_number() {
         let result = TransactionReferenceNumber::try_from("1234567890");
-        assert_eq!(
-            result,
-            Ok(TransactionReferenceNumber("1234567890".to_string()))
-        );
+        assert_eq!(result, Ok(TransactionReferenceNumber("1234567890".into())));
         assert_eq!(result.unwrap().to_string(), "1234567890");
     }
 }
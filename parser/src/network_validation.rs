@@ -0,0 +1,388 @@
+use crate::dialect::*;
+use crate::mt_940_customer_statement_message::*;
+use std::fmt::{Display, Formatter};
+
+const CURRENCY_PREFIX_LENGTH: usize = 2;
+const STATEMENT_LINE_TAG: &str = "61";
+const INFORMATION_TAG: &str = "86";
+const INFORMATION_MAX_LINES: usize = 6;
+const INFORMATION_LINE_LENGTH: usize = 65;
+const NON_REPEATABLE_TAGS: [&str; 7] = ["20", "21", "25", "28C", "60a", "62a", "64"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NetworkErrorCode {
+    C24,
+    C27,
+    T13,
+    T30,
+    T33,
+    T43,
+    T52,
+}
+
+impl Display for NetworkErrorCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let code = match self {
+            NetworkErrorCode::C24 => "C24",
+            NetworkErrorCode::C27 => "C27",
+            NetworkErrorCode::T13 => "T13",
+            NetworkErrorCode::T30 => "T30",
+            NetworkErrorCode::T33 => "T33",
+            NetworkErrorCode::T43 => "T43",
+            NetworkErrorCode::T52 => "T52",
+        };
+        write!(f, "{}", code)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkRuleViolation {
+    code: NetworkErrorCode,
+    tag: String,
+    message: String,
+}
+
+impl NetworkRuleViolation {
+    fn new(code: NetworkErrorCode, tag: &str, message: String) -> Self {
+        Self {
+            code,
+            tag: tag.to_string(),
+            message,
+        }
+    }
+
+    pub fn code(&self) -> NetworkErrorCode {
+        self.code
+    }
+
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for NetworkRuleViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} :{}: {}", self.code, self.tag, self.message)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkValidation {
+    violations: Vec<NetworkRuleViolation>,
+}
+
+impl NetworkValidation {
+    pub fn new(message: &Mt940CustomerStatementMessage<'_>) -> Self {
+        let mut violations = Vec::new();
+        let balances = [
+            ("60a", Some(message.opening_balance())),
+            ("62a", Some(message.closing_balance())),
+            ("64", message.closing_available_balance()),
+        ]
+        .into_iter()
+        .filter_map(|(tag, balance)| balance.map(|balance| (tag, balance)))
+        .chain(
+            message
+                .forward_available_balances()
+                .iter()
+                .map(|balance| ("65", balance)),
+        );
+
+        let currency_code = message.opening_balance().currency_code().as_str();
+        for (tag, balance) in balances {
+            let code = balance.currency_code().as_str();
+            if code.get(..CURRENCY_PREFIX_LENGTH) != currency_code.get(..CURRENCY_PREFIX_LENGTH) {
+                violations.push(NetworkRuleViolation::new(
+                    NetworkErrorCode::C27,
+                    tag,
+                    format!(
+                        "Currency code {} does not start with the same {} characters as {}",
+                        code, CURRENCY_PREFIX_LENGTH, currency_code
+                    ),
+                ));
+            }
+            check_amount(
+                &mut violations,
+                tag,
+                balance.currency_code(),
+                balance.amount(),
+            );
+        }
+
+        let currency_code = message.opening_balance().currency_code();
+        if currency_code.iso_4217().is_ok() {
+            for statement_line in message.statement_lines() {
+                check_amount(
+                    &mut violations,
+                    STATEMENT_LINE_TAG,
+                    currency_code,
+                    statement_line.amount(),
+                );
+            }
+        }
+
+        Self { violations }
+    }
+
+    pub fn from_text(
+        text: &str,
+        dialect: &dyn Dialect,
+    ) -> Result<Self, Mt940CustomerStatementMessageParseError> {
+        let fields = fields(text, dialect)?.collect::<Vec<_>>();
+        let mut violations = field_violations(&fields);
+        match Mt940CustomerStatementMessage::parse_with_dialect(text, dialect) {
+            Ok(message) => violations.extend(Self::new(&message).violations),
+            Err(err) if violations.is_empty() => return Err(err),
+            Err(_) => {
+                if let Ok(recovered) =
+                    Mt940CustomerStatementMessage::parse_recovering(text, dialect)
+                {
+                    violations.extend(Self::new(recovered.message()).violations);
+                }
+            }
+        }
+        Ok(Self { violations })
+    }
+
+    pub fn violations(&self) -> &[NetworkRuleViolation] {
+        &self.violations
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+fn field_violations(fields: &[(&str, &str)]) -> Vec<NetworkRuleViolation> {
+    let mut violations = Vec::new();
+    let mut seen_tags = Vec::new();
+    for (index, (tag, value)) in fields.iter().enumerate() {
+        let tag = match &tag[..tag.len().min(2)] {
+            "60" => "60a",
+            "62" => "62a",
+            _ => tag,
+        };
+        if NON_REPEATABLE_TAGS.contains(&tag) {
+            if seen_tags.contains(&tag) {
+                violations.push(NetworkRuleViolation::new(
+                    NetworkErrorCode::T13,
+                    tag,
+                    "Field cannot be repeated".to_string(),
+                ));
+            }
+            seen_tags.push(tag);
+        }
+        if tag != INFORMATION_TAG {
+            continue;
+        }
+        if index + 1 < fields.len() && (index == 0 || fields[index - 1].0 != STATEMENT_LINE_TAG) {
+            violations.push(NetworkRuleViolation::new(
+                NetworkErrorCode::C24,
+                INFORMATION_TAG,
+                format!(
+                    "Field must follow a :{}: field or be the last field of the message",
+                    STATEMENT_LINE_TAG
+                ),
+            ));
+        }
+        let lines = value.lines().map(|line| line.trim_end_matches('\r'));
+        if lines.clone().count() > INFORMATION_MAX_LINES {
+            violations.push(NetworkRuleViolation::new(
+                NetworkErrorCode::T30,
+                INFORMATION_TAG,
+                format!(
+                    "Field cannot have more than {} lines",
+                    INFORMATION_MAX_LINES
+                ),
+            ));
+        }
+        for (line_index, line) in lines.enumerate() {
+            if line.chars().count() > INFORMATION_LINE_LENGTH {
+                violations.push(NetworkRuleViolation::new(
+                    NetworkErrorCode::T33,
+                    INFORMATION_TAG,
+                    format!(
+                        "Line {} exceeds the maximum length of {} characters",
+                        line_index + 1,
+                        INFORMATION_LINE_LENGTH
+                    ),
+                ));
+            }
+        }
+    }
+    violations
+}
+
+fn check_amount(
+    violations: &mut Vec<NetworkRuleViolation>,
+    tag: &str,
+    currency_code: &CurrencyCode<'_>,
+    amount: &Amount,
+) {
+    let violation = match currency_code.iso_4217() {
+        Ok(currency) => match amount.check_minor_units(currency) {
+            Ok(()) => return,
+            Err(err) => NetworkRuleViolation::new(NetworkErrorCode::T43, tag, err.to_string()),
+        },
+        Err(err) => NetworkRuleViolation::new(NetworkErrorCode::T52, tag, err.to_string()),
+    };
+    violations.push(violation);
+}
+
+impl Display for NetworkValidation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.violations.is_empty() {
+            return writeln!(f, "Message conforms to the network validated rules");
+        }
+        for violation in &self.violations {
+            writeln!(f, "{}", violation)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSAGE: &str = ":20:STMT
+:25:123
+:28C:1
+:60F:C230305EUR1000,00
+:61:230306D200,50NTRFINV-1001
+:86:INVOICE 1001
+:61:230306C50,NCHKNONREF
+:62F:C230306EUR849,50
+:64:C230306EUR849,50
+:65:C230307EUR849,50
+:86:END OF STATEMENT
+-";
+
+    #[test]
+    fn test_valid_message() {
+        let validation = NetworkValidation::from_text(MESSAGE, &Standard).unwrap();
+        assert!(validation.is_valid());
+        assert_eq!(
+            validation.to_string(),
+            "Message conforms to the network validated rules\n"
+        );
+    }
+
+    #[test]
+    fn test_currency_prefix_mismatch() {
+        let text = MESSAGE
+            .replace(":64:C230306EUR", ":64:C230306XEU")
+            .replace(":62F:C230306EUR", ":62F:C230306CHF");
+        let validation = NetworkValidation::from_text(&text, &Standard).unwrap();
+        let codes = validation
+            .violations()
+            .iter()
+            .map(|violation| (violation.code(), violation.tag()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            codes,
+            vec![
+                (NetworkErrorCode::C27, "62a"),
+                (NetworkErrorCode::C27, "64"),
+                (NetworkErrorCode::T52, "64"),
+            ]
+        );
+        assert_eq!(
+            validation.violations()[0].to_string(),
+            "C27 :62a: Currency code CHF does not start with the same 2 characters as EUR"
+        );
+    }
+
+    #[test]
+    fn test_too_many_decimal_places() {
+        let text = MESSAGE
+            .replace("EUR", "JPY")
+            .replace("1000,00", "1000,")
+            .replace("849,50", "850,")
+            .replace("D200,50", "D200,5");
+        let validation = NetworkValidation::from_text(&text, &Standard).unwrap();
+        assert_eq!(validation.violations().len(), 1);
+        assert_eq!(
+            validation.to_string(),
+            "T43 :61: Amount in JPY cannot have more than 0 decimal places\n"
+        );
+    }
+
+    #[test]
+    fn test_information_length() {
+        let text = MESSAGE.replace(
+            ":86:INVOICE 1001",
+            &format!(":86:{}\n2\n3\n4\n5\n6\n7", "X".repeat(66)),
+        );
+        let validation = NetworkValidation::from_text(&text, &Standard).unwrap();
+        assert_eq!(
+            validation.to_string(),
+            "T30 :86: Field cannot have more than 6 lines
+T33 :86: Line 1 exceeds the maximum length of 65 characters
+"
+        );
+    }
+
+    #[test]
+    fn test_field_repetition() {
+        let text = MESSAGE.replace(":28C:1", ":28C:1\n:28C:2");
+        let validation = NetworkValidation::from_text(&text, &Standard).unwrap();
+        assert_eq!(
+            validation.to_string(),
+            "T13 :28C: Field cannot be repeated\n"
+        );
+
+        let text = MESSAGE.replace(
+            ":64:C230306EUR849,50",
+            ":64:C230306EUR849,50\n:64:C230306EUR849,50",
+        );
+        let validation = NetworkValidation::from_text(&text, &Standard).unwrap();
+        assert_eq!(validation.violations()[0].code(), NetworkErrorCode::T13);
+        assert_eq!(validation.violations()[0].tag(), "64");
+    }
+
+    #[test]
+    fn test_all_violations_reported() {
+        let text = MESSAGE
+            .replace(":64:", ":86:MISPLACED\n:64:")
+            .replace(":62F:C230306EUR", ":62F:C230306CHF")
+            .replace("D200,50", "D200,505");
+        let validation = NetworkValidation::from_text(&text, &Standard).unwrap();
+        let codes = validation
+            .violations()
+            .iter()
+            .map(|violation| (violation.code(), violation.tag()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            codes,
+            vec![
+                (NetworkErrorCode::C24, "86"),
+                (NetworkErrorCode::C27, "62a"),
+                (NetworkErrorCode::T43, "61"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_information_placement() {
+        let text = MESSAGE.replace(
+            ":86:INVOICE 1001",
+            ":86:INVOICE 1001\n:86:SECOND INFORMATION",
+        );
+        let validation = NetworkValidation::from_text(&text, &Standard).unwrap();
+        assert_eq!(validation.violations().len(), 1);
+        assert_eq!(validation.violations()[0].code(), NetworkErrorCode::C24);
+
+        let text = MESSAGE.replace(":64:", ":86:MISPLACED\n:64:");
+        let validation = NetworkValidation::from_text(&text, &Standard).unwrap();
+        assert_eq!(validation.violations()[0].code(), NetworkErrorCode::C24);
+        assert_eq!(
+            validation.violations()[0].to_string(),
+            "C24 :86: Field must follow a :61: field or be the last field of the message"
+        );
+    }
+}
@@ -0,0 +1,545 @@
+mod open_item;
+
+use crate::money::*;
+use crate::mt_940_customer_statement_message::*;
+pub use crate::reconciliation::open_item::*;
+use crate::statement_line_deduplicator::*;
+use chrono::{Duration, NaiveDate};
+use std::cmp::Reverse;
+use std::fmt::{Display, Formatter};
+
+const DEFAULT_DATE_TOLERANCE_DAYS: i64 = 5;
+const DEFAULT_MIN_CONFIDENCE: u8 = 60;
+const AMOUNT_SCORE: u8 = 40;
+const REFERENCE_SCORE: u8 = 35;
+const COUNTERPARTY_SCORE: u8 = 15;
+const DATE_SCORE: u8 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchKind {
+    OneToOne,
+    OneToMany,
+    ManyToOne,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconciliationMatch {
+    kind: MatchKind,
+    statement_lines: Vec<StatementLineLocation>,
+    open_items: Vec<usize>,
+    confidence: u8,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconciliationReport {
+    matches: Vec<ReconciliationMatch>,
+    unmatched_statement_lines: Vec<StatementLineLocation>,
+    unmatched_open_items: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Reconciler {
+    date_tolerance: Duration,
+    min_confidence: u8,
+}
+
+struct Transaction {
+    location: StatementLineLocation,
+    amount: Money,
+    value_date: NaiveDate,
+    text: NormalizedText,
+    counterparty: Option<String>,
+}
+
+struct NormalizedText {
+    value: String,
+    boundaries: Vec<usize>,
+}
+
+impl ReconciliationMatch {
+    pub fn kind(&self) -> MatchKind {
+        self.kind
+    }
+
+    pub fn statement_lines(&self) -> &[StatementLineLocation] {
+        &self.statement_lines
+    }
+
+    pub fn open_items(&self) -> &[usize] {
+        &self.open_items
+    }
+
+    pub fn confidence(&self) -> u8 {
+        self.confidence
+    }
+}
+
+impl ReconciliationReport {
+    pub fn matches(&self) -> &[ReconciliationMatch] {
+        &self.matches
+    }
+
+    pub fn unmatched_statement_lines(&self) -> &[StatementLineLocation] {
+        &self.unmatched_statement_lines
+    }
+
+    pub fn unmatched_open_items(&self) -> &[usize] {
+        &self.unmatched_open_items
+    }
+}
+
+impl Default for Reconciler {
+    fn default() -> Self {
+        Self {
+            date_tolerance: Duration::days(DEFAULT_DATE_TOLERANCE_DAYS),
+            min_confidence: DEFAULT_MIN_CONFIDENCE,
+        }
+    }
+}
+
+impl Reconciler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_date_tolerance(self, date_tolerance: Duration) -> Self {
+        Self {
+            date_tolerance,
+            ..self
+        }
+    }
+
+    pub fn with_min_confidence(self, min_confidence: u8) -> Self {
+        Self {
+            min_confidence,
+            ..self
+        }
+    }
+
+    pub fn reconcile<'m, 'a: 'm>(
+        &self,
+        messages: impl IntoIterator<Item = &'m Mt940CustomerStatementMessage<'a>>,
+        open_items: &[OpenItem],
+    ) -> ReconciliationReport {
+        let transactions = transactions(messages);
+        let mut matched_transactions = vec![false; transactions.len()];
+        let mut matched_items = vec![false; open_items.len()];
+        let mut matches = Vec::new();
+
+        let mut candidates = Vec::new();
+        for (transaction_index, transaction) in transactions.iter().enumerate() {
+            for (item_index, item) in open_items.iter().enumerate() {
+                if &transaction.amount != item.amount() {
+                    continue;
+                }
+                let confidence = self.confidence(&[transaction], &[item]);
+                if confidence >= self.min_confidence {
+                    candidates.push((confidence, transaction_index, item_index));
+                }
+            }
+        }
+        candidates.sort_by_key(|(confidence, _, _)| Reverse(*confidence));
+        for (confidence, transaction_index, item_index) in candidates {
+            if matched_transactions[transaction_index] || matched_items[item_index] {
+                continue;
+            }
+            matched_transactions[transaction_index] = true;
+            matched_items[item_index] = true;
+            matches.push(ReconciliationMatch {
+                kind: MatchKind::OneToOne,
+                statement_lines: vec![transactions[transaction_index].location],
+                open_items: vec![item_index],
+                confidence,
+            });
+        }
+
+        for (transaction_index, transaction) in transactions.iter().enumerate() {
+            if matched_transactions[transaction_index] {
+                continue;
+            }
+            let item_indexes = (0..open_items.len())
+                .filter(|index| !matched_items[*index])
+                .filter(|index| transaction.references(&open_items[*index]))
+                .collect::<Vec<_>>();
+            let items = item_indexes
+                .iter()
+                .map(|index| &open_items[*index])
+                .collect::<Vec<_>>();
+            if items.len() < 2
+                || !sums_to(items.iter().map(|item| item.amount()), &transaction.amount)
+            {
+                continue;
+            }
+            let confidence = self.confidence(&[transaction], &items);
+            if confidence < self.min_confidence {
+                continue;
+            }
+            matched_transactions[transaction_index] = true;
+            item_indexes
+                .iter()
+                .for_each(|index| matched_items[*index] = true);
+            matches.push(ReconciliationMatch {
+                kind: MatchKind::OneToMany,
+                statement_lines: vec![transaction.location],
+                open_items: item_indexes,
+                confidence,
+            });
+        }
+
+        for (item_index, item) in open_items.iter().enumerate() {
+            if matched_items[item_index] {
+                continue;
+            }
+            let transaction_indexes = (0..transactions.len())
+                .filter(|index| !matched_transactions[*index])
+                .filter(|index| transactions[*index].references(item))
+                .collect::<Vec<_>>();
+            let matched = transaction_indexes
+                .iter()
+                .map(|index| &transactions[*index])
+                .collect::<Vec<_>>();
+            if matched.len() < 2
+                || !sums_to(
+                    matched.iter().map(|transaction| &transaction.amount),
+                    item.amount(),
+                )
+            {
+                continue;
+            }
+            let confidence = self.confidence(&matched, &[item]);
+            if confidence < self.min_confidence {
+                continue;
+            }
+            matched_items[item_index] = true;
+            transaction_indexes
+                .iter()
+                .for_each(|index| matched_transactions[*index] = true);
+            matches.push(ReconciliationMatch {
+                kind: MatchKind::ManyToOne,
+                statement_lines: matched
+                    .iter()
+                    .map(|transaction| transaction.location)
+                    .collect(),
+                open_items: vec![item_index],
+                confidence,
+            });
+        }
+
+        ReconciliationReport {
+            matches,
+            unmatched_statement_lines: transactions
+                .iter()
+                .zip(matched_transactions)
+                .filter(|(_, matched)| !matched)
+                .map(|(transaction, _)| transaction.location)
+                .collect(),
+            unmatched_open_items: (0..open_items.len())
+                .filter(|index| !matched_items[*index])
+                .collect(),
+        }
+    }
+
+    fn confidence(&self, transactions: &[&Transaction], items: &[&OpenItem]) -> u8 {
+        let pairs = || {
+            transactions
+                .iter()
+                .flat_map(|transaction| items.iter().map(move |item| (*transaction, *item)))
+        };
+        let mut confidence = AMOUNT_SCORE;
+        if transactions
+            .iter()
+            .all(|transaction| items.iter().any(|item| transaction.references(item)))
+            && items.iter().all(|item| {
+                transactions
+                    .iter()
+                    .any(|transaction| transaction.references(item))
+            })
+        {
+            confidence += REFERENCE_SCORE;
+        }
+        if pairs().all(|(transaction, item)| transaction.has_counterparty(item)) {
+            confidence += COUNTERPARTY_SCORE;
+        }
+        if pairs().all(|(transaction, item)| {
+            (transaction.value_date - item.date()).abs() <= self.date_tolerance
+        }) {
+            confidence += DATE_SCORE;
+        }
+        confidence
+    }
+}
+
+fn transactions<'m, 'a: 'm>(
+    messages: impl IntoIterator<Item = &'m Mt940CustomerStatementMessage<'a>>,
+) -> Vec<Transaction> {
+    let mut transactions = Vec::new();
+    for (message_index, message) in messages.into_iter().enumerate() {
+        let currency_code = message.opening_balance().currency_code();
+        for (statement_line_index, statement_line) in message.statement_lines().iter().enumerate() {
+            let information = statement_line.information_to_account_owner();
+            let text = NormalizedText::new(&[
+                statement_line.account_owner_ref(),
+                statement_line.bank_ref().unwrap_or_default(),
+                &information.unwrap_or_default().concat(),
+            ]);
+            transactions.push(Transaction {
+                location: StatementLineLocation::new(message_index, statement_line_index),
+                amount: Money::from_statement_line(statement_line, currency_code),
+                value_date: statement_line.value_date().value(),
+                text,
+                counterparty: information
+                    .and_then(|information| message.information_format().counterparty(information))
+                    .map(|counterparty| normalized(&counterparty)),
+            });
+        }
+    }
+    transactions
+}
+
+impl Transaction {
+    fn references(&self, item: &OpenItem) -> bool {
+        let reference = normalized(item.reference());
+        self.amount.currency_code() == item.amount().currency_code()
+            && self.text.contains(&reference)
+    }
+
+    fn has_counterparty(&self, item: &OpenItem) -> bool {
+        let Some(counterparty) = item.counterparty().map(normalized) else {
+            return false;
+        };
+        match &self.counterparty {
+            Some(name) => name == &counterparty,
+            None => self.text.contains(&counterparty),
+        }
+    }
+}
+
+impl NormalizedText {
+    fn new(parts: &[&str]) -> Self {
+        let mut value = String::new();
+        let mut boundaries = vec![0];
+        for part in parts {
+            for c in part.chars() {
+                if c.is_alphanumeric() {
+                    value.extend(c.to_uppercase());
+                } else if boundaries.last() != Some(&value.len()) {
+                    boundaries.push(value.len());
+                }
+            }
+            if boundaries.last() != Some(&value.len()) {
+                boundaries.push(value.len());
+            }
+        }
+        Self { value, boundaries }
+    }
+
+    fn contains(&self, token: &str) -> bool {
+        !token.is_empty()
+            && self.boundaries.iter().any(|start| {
+                self.value[*start..].starts_with(token)
+                    && self.boundaries.contains(&(start + token.len()))
+            })
+    }
+}
+
+fn sums_to<'m>(amounts: impl Iterator<Item = &'m Money>, total: &Money) -> bool {
+    Money::sum_in(total.currency_code().clone(), amounts).is_ok_and(|sum| &sum == total)
+}
+
+fn normalized(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+impl Display for MatchKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatchKind::OneToOne => write!(f, "one-to-one"),
+            MatchKind::OneToMany => write!(f, "one-to-many"),
+            MatchKind::ManyToOne => write!(f, "many-to-one"),
+        }
+    }
+}
+
+impl Display for ReconciliationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Matches: {}", self.matches.len())?;
+        for reconciliation_match in &self.matches {
+            let statement_lines = reconciliation_match
+                .statement_lines
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            let open_items = reconciliation_match
+                .open_items
+                .iter()
+                .map(|index| (index + 1).to_string())
+                .collect::<Vec<_>>();
+            writeln!(
+                f,
+                "- {} match of {} with open items {} ({}% confidence)",
+                reconciliation_match.kind,
+                statement_lines.join(", "),
+                open_items.join(", "),
+                reconciliation_match.confidence
+            )?;
+        }
+        writeln!(
+            f,
+            "Unmatched statement lines: {}",
+            self.unmatched_statement_lines.len()
+        )?;
+        writeln!(
+            f,
+            "Unmatched open items: {}",
+            self.unmatched_open_items.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::*;
+    use rust_decimal::Decimal;
+
+    const MESSAGE: &str = ":20:STMT
+:25:NL91ABNA0417164300
+:28C:1
+:60F:C230305EUR1000,00
+:61:230306C200,50NTRFNONREF
+:86:/NAME/ACME LTD/REMI/INVOICE INV-1001
+:61:230306C300,00NTRFNONREF
+:86:/NAME/GLOBEX/REMI/INV-2001 INV-2002
+:61:230307C60,00NTRFNONREF
+:86:/NAME/INITECH/REMI/INV-3001 PART 1
+:61:230308C40,00NTRFNONREF
+:86:/NAME/INITECH/REMI/INV-3001 PART 2
+:61:230309D75,00NTRFNONREF
+:86:/NAME/UTILITY CO/REMI/MARCH
+:61:230309C10,00NTRFNONREF
+:86:/NAME/UNKNOWN/REMI/GIFT
+:62F:C230309EUR1535,50
+-";
+
+    fn open_items() -> Vec<OpenItem> {
+        OpenItem::from_csv(
+            "reference,amount,currency,date,counterparty
+INV-1001,200.50,EUR,2023-03-05,Acme Ltd
+INV-2001,100,EUR,2023-02-28,Globex
+INV-2002,200,EUR,2023-02-28,Globex
+INV-3001,100,EUR,2023-03-07,Initech
+UTIL-03,-75,EUR,2023-03-09,Utility Co
+INV-4001,999,EUR,2023-03-09,Hooli
+",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_reconcile() {
+        let message = Mt940CustomerStatementMessage::parse_with_dialect(MESSAGE, &AbnAmro).unwrap();
+        let report = Reconciler::new().reconcile([&message], &open_items());
+        let matches = report.matches();
+        assert_eq!(matches.len(), 4);
+
+        assert_eq!(matches[0].kind(), MatchKind::OneToOne);
+        assert_eq!(matches[0].open_items(), [0]);
+        assert_eq!(matches[0].confidence(), 100);
+
+        assert_eq!(matches[1].kind(), MatchKind::OneToOne);
+        assert_eq!(matches[1].open_items(), [4]);
+        assert_eq!(matches[1].confidence(), 65);
+
+        assert_eq!(matches[2].kind(), MatchKind::OneToMany);
+        assert_eq!(
+            matches[2].statement_lines(),
+            [StatementLineLocation::new(0, 1)]
+        );
+        assert_eq!(matches[2].open_items(), [1, 2]);
+        assert_eq!(matches[2].confidence(), 90);
+
+        assert_eq!(matches[3].kind(), MatchKind::ManyToOne);
+        assert_eq!(
+            matches[3].statement_lines(),
+            [
+                StatementLineLocation::new(0, 2),
+                StatementLineLocation::new(0, 3)
+            ]
+        );
+        assert_eq!(matches[3].open_items(), [3]);
+        assert_eq!(matches[3].confidence(), 100);
+
+        assert_eq!(
+            report.unmatched_statement_lines(),
+            [StatementLineLocation::new(0, 5)]
+        );
+        assert_eq!(report.unmatched_open_items(), [5]);
+        assert_eq!(
+            report.to_string(),
+            "Matches: 4
+- one-to-one match of statement line 1 of message 1 with open items 1 (100% confidence)
+- one-to-one match of statement line 5 of message 1 with open items 5 (65% confidence)
+- one-to-many match of statement line 2 of message 1 with open items 2, 3 (90% confidence)
+- many-to-one match of statement line 3 of message 1, statement line 4 of message 1 with open items 4 (100% confidence)
+Unmatched statement lines: 1
+Unmatched open items: 1
+"
+        );
+    }
+
+    #[test]
+    fn test_reconcile_with_tolerance() {
+        let message = Mt940CustomerStatementMessage::parse_with_dialect(MESSAGE, &AbnAmro).unwrap();
+        let open_items = [OpenItem::new(
+            "",
+            Money::new(10.into(), CurrencyCode::try_from("EUR").unwrap()),
+            None,
+            NaiveDate::from_ymd_opt(2023, 3, 1).unwrap(),
+        )];
+
+        let report = Reconciler::new().reconcile([&message], &open_items);
+        assert!(report.matches().is_empty());
+
+        let report = Reconciler::new()
+            .with_date_tolerance(Duration::days(10))
+            .reconcile([&message], &open_items);
+        assert!(
+            report.matches().is_empty(),
+            "amount and date alone are not enough for a match"
+        );
+
+        let report = Reconciler::new()
+            .with_date_tolerance(Duration::days(10))
+            .with_min_confidence(50)
+            .reconcile([&message], &open_items);
+        assert_eq!(report.matches().len(), 1);
+        assert_eq!(report.matches()[0].confidence(), 50);
+    }
+
+    #[test]
+    fn test_reconcile_reference_token_boundaries() {
+        let message = Mt940CustomerStatementMessage::parse_with_dialect(MESSAGE, &AbnAmro).unwrap();
+        let open_items = |reference: &str| {
+            [OpenItem::new(
+                reference,
+                Money::new(
+                    Decimal::new(20050, 2),
+                    CurrencyCode::try_from("EUR").unwrap(),
+                ),
+                None,
+                NaiveDate::from_ymd_opt(2023, 1, 1).unwrap(),
+            )]
+        };
+
+        let report = Reconciler::new().reconcile([&message], &open_items("INV-100"));
+        assert!(report.matches().is_empty());
+
+        let report = Reconciler::new().reconcile([&message], &open_items("INV1001"));
+        assert_eq!(report.matches().len(), 1);
+        assert_eq!(report.matches()[0].confidence(), 75);
+    }
+}
@@ -0,0 +1,216 @@
+use crate::money::*;
+use crate::mt_940_customer_statement_message::*;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+const REFERENCE_COLUMN: &str = "reference";
+const AMOUNT_COLUMN: &str = "amount";
+const CURRENCY_COLUMN: &str = "currency";
+const COUNTERPARTY_COLUMN: &str = "counterparty";
+const DATE_COLUMN: &str = "date";
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenItem {
+    reference: String,
+    amount: Money,
+    counterparty: Option<String>,
+    date: NaiveDate,
+}
+
+impl OpenItem {
+    pub fn new(
+        reference: &str,
+        amount: Money,
+        counterparty: Option<&str>,
+        date: NaiveDate,
+    ) -> Self {
+        Self {
+            reference: reference.to_string(),
+            amount,
+            counterparty: counterparty.map(str::to_string),
+            date,
+        }
+    }
+
+    pub fn reference(&self) -> &str {
+        &self.reference
+    }
+
+    pub fn amount(&self) -> &Money {
+        &self.amount
+    }
+
+    pub fn counterparty(&self) -> Option<&str> {
+        self.counterparty.as_deref()
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn from_csv(text: &str) -> Result<Vec<OpenItem>, OpenItemParseError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+        let (_, header) = lines
+            .next()
+            .ok_or(OpenItemParseError::MissingColumn(REFERENCE_COLUMN))?;
+        let header = csv_fields(header)
+            .ok_or(OpenItemParseError::InvalidLine(1))?
+            .into_iter()
+            .map(|column| column.trim().to_ascii_lowercase())
+            .collect::<Vec<_>>();
+        let column = |name: &'static str| {
+            header
+                .iter()
+                .position(|column| column == name)
+                .ok_or(OpenItemParseError::MissingColumn(name))
+        };
+        let reference_column = column(REFERENCE_COLUMN)?;
+        let amount_column = column(AMOUNT_COLUMN)?;
+        let currency_column = column(CURRENCY_COLUMN)?;
+        let date_column = column(DATE_COLUMN)?;
+        let counterparty_column = column(COUNTERPARTY_COLUMN).ok();
+
+        lines
+            .map(|(index, line)| {
+                let line_number = index + 1;
+                let fields =
+                    csv_fields(line).ok_or(OpenItemParseError::InvalidLine(line_number))?;
+                let field = |column: usize| {
+                    fields
+                        .get(column)
+                        .map(|field| field.trim())
+                        .ok_or(OpenItemParseError::InvalidLine(line_number))
+                };
+                let amount = Decimal::from_str(field(amount_column)?)
+                    .map_err(|_| OpenItemParseError::InvalidAmount(line_number))?;
+                let currency_code = CurrencyCode::try_from(field(currency_column)?)
+                    .map_err(|_| OpenItemParseError::InvalidCurrency(line_number))?;
+                let date = NaiveDate::parse_from_str(field(date_column)?, DATE_FORMAT)
+                    .map_err(|_| OpenItemParseError::InvalidDate(line_number))?;
+                let counterparty = counterparty_column
+                    .map(field)
+                    .transpose()?
+                    .filter(|counterparty| !counterparty.is_empty());
+                Ok(OpenItem::new(
+                    field(reference_column)?,
+                    Money::new(amount, currency_code),
+                    counterparty,
+                    date,
+                ))
+            })
+            .collect()
+    }
+}
+
+fn csv_fields(line: &str) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.trim_end_matches('\r').chars().peekable();
+    let mut quoted = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.next_if_eq(&'"').is_some() => field.push('"'),
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    (!quoted).then_some(fields)
+}
+
+impl Display for OpenItem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.reference, self.amount, self.date)?;
+        if let Some(counterparty) = &self.counterparty {
+            write!(f, " {}", counterparty)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum OpenItemParseError {
+    MissingColumn(&'static str),
+    InvalidLine(usize),
+    InvalidAmount(usize),
+    InvalidCurrency(usize),
+    InvalidDate(usize),
+}
+
+impl Display for OpenItemParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OpenItemParseError::MissingColumn(column) => {
+                write!(f, "Open items are missing column {}", column)
+            }
+            OpenItemParseError::InvalidLine(line) => {
+                write!(f, "Open item on line {} has invalid format", line)
+            }
+            OpenItemParseError::InvalidAmount(line) => {
+                write!(f, "Open item on line {} has invalid amount", line)
+            }
+            OpenItemParseError::InvalidCurrency(line) => {
+                write!(f, "Open item on line {} has invalid currency", line)
+            }
+            OpenItemParseError::InvalidDate(line) => {
+                write!(f, "Open item on line {} has invalid date", line)
+            }
+        }
+    }
+}
+
+impl Error for OpenItemParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_items_from_csv() {
+        let items = OpenItem::from_csv(
+            "reference,amount,currency,date,counterparty
+INV-1001,200.50,EUR,2023-03-06,\"ACME, LTD\"
+INV-1002,-75,EUR,2023-03-07,
+",
+        )
+        .unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].reference(), "INV-1001");
+        assert_eq!(items[0].counterparty(), Some("ACME, LTD"));
+        assert_eq!(
+            items[0].to_string(),
+            "INV-1001 EUR 200,50 2023-03-06 ACME, LTD"
+        );
+        assert_eq!(items[1].amount().value(), Decimal::from(-75));
+        assert_eq!(items[1].counterparty(), None);
+    }
+
+    #[test]
+    fn test_invalid_open_items_csv() {
+        let result = OpenItem::from_csv("reference,amount,date\nINV-1,1,2023-03-06");
+        assert_eq!(result, Err(OpenItemParseError::MissingColumn("currency")));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Open items are missing column currency"
+        );
+
+        let result = OpenItem::from_csv("reference,amount,currency,date\nINV-1,1,EUR,06.03.2023");
+        assert_eq!(result, Err(OpenItemParseError::InvalidDate(2)));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Open item on line 2 has invalid date"
+        );
+
+        let result = OpenItem::from_csv("reference,amount,currency,date\n\"INV-1,1,EUR,2023-03-06");
+        assert_eq!(result, Err(OpenItemParseError::InvalidLine(2)));
+    }
+}
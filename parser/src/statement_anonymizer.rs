@@ -0,0 +1,518 @@
+use crate::dialect::*;
+use crate::money::*;
+use crate::mt_940_customer_statement_message::*;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+const NO_REFERENCE: &str = "NONREF";
+const INFORMATION_LINE_LENGTH: usize = 65;
+const SENSITIVE_NUMBER_MIN_DIGITS: usize = 6;
+const SLASH_CODE_ACCOUNTS: [&str; 2] = ["IBAN", "ID"];
+const SLASH_CODE_PARTIES: [&str; 6] = ["BENM", "CNTP", "NAME", "ORDP", "ULTB", "ULTD"];
+const SLASH_CODE_ADDRESSES: [&str; 1] = ["ADDR"];
+const QUESTION_MARK_ACCOUNTS: [&str; 2] = ["31", "38"];
+const QUESTION_MARK_PARTIES: [&str; 2] = ["32", "33"];
+const QUESTION_MARK_PRESERVED: [&str; 2] = ["", "00"];
+const MIN_PERTURBATION_FACTOR: i64 = 500;
+const PERTURBATION_FACTOR_RANGE: u64 = 1001;
+const PERTURBATION_FACTOR_SCALE: u32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum PseudonymKind {
+    Account,
+    Reference,
+    Party,
+    Address,
+    Text,
+}
+
+#[derive(Debug, Default)]
+pub struct StatementAnonymizer {
+    pseudonyms: HashMap<(PseudonymKind, String), String>,
+    counters: HashMap<PseudonymKind, usize>,
+    amount_seed: Option<u64>,
+    preserve_free_text: bool,
+}
+
+impl PseudonymKind {
+    fn prefix(&self) -> &'static str {
+        match self {
+            PseudonymKind::Account => "ACCOUNT",
+            PseudonymKind::Reference => "REF",
+            PseudonymKind::Party => "PARTY",
+            PseudonymKind::Address => "ADDRESS",
+            PseudonymKind::Text => "TEXT",
+        }
+    }
+}
+
+impl StatementAnonymizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_amount_perturbation(mut self, seed: u64) -> Self {
+        self.amount_seed = Some(seed);
+        self
+    }
+
+    pub fn with_free_text_preserved(mut self) -> Self {
+        self.preserve_free_text = true;
+        self
+    }
+
+    pub fn anonymize(&mut self, message: &Mt940CustomerStatementMessage<'_>) -> String {
+        let currency_code = message.opening_balance().currency_code();
+        let amounts = message
+            .statement_lines()
+            .iter()
+            .enumerate()
+            .map(|(index, statement_line)| self.amount(index, statement_line.amount().value()))
+            .collect::<Vec<_>>();
+        let shifts = message
+            .statement_lines()
+            .iter()
+            .zip(&amounts)
+            .map(|(statement_line, amount)| {
+                let shift = amount - statement_line.amount().value();
+                match statement_line.debit_credit_mark() {
+                    StatementLineMark::Credit | StatementLineMark::ReversalOfDebit => {
+                        (statement_line.value_date().value(), shift)
+                    }
+                    StatementLineMark::Debit | StatementLineMark::ReversalOfCredit => {
+                        (statement_line.value_date().value(), -shift)
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        let shift_at = |date: NaiveDate| {
+            shifts
+                .iter()
+                .filter(|(value_date, _)| *value_date <= date)
+                .map(|(_, shift)| shift)
+                .sum::<Decimal>()
+        };
+
+        let mut text = format!(
+            ":20:{}\n",
+            self.reference(message.transaction_reference_number().as_str())
+        );
+        if let Some(related_reference) = message.related_reference() {
+            let _ = writeln!(text, ":21:{}", self.reference(related_reference.as_str()));
+        }
+        let _ = writeln!(
+            text,
+            ":25:{}",
+            self.pseudonym(
+                PseudonymKind::Account,
+                message.account_identification().as_str()
+            )
+        );
+        if let Some(statement_sequence_no) = message.statement_sequence_no() {
+            let _ = writeln!(text, ":28C:{}", statement_sequence_no);
+        }
+        let _ = writeln!(
+            text,
+            ":60F:{}",
+            swift_balance(message.opening_balance(), Decimal::ZERO)
+        );
+        for (statement_line, amount) in message.statement_lines().iter().zip(amounts) {
+            let _ = write!(
+                text,
+                ":61:{}",
+                statement_line.value_date().value().format("%y%m%d")
+            );
+            if let Some(entry_date) = statement_line.entry_date() {
+                let _ = write!(text, "{}", entry_date.value().format("%m%d"));
+            }
+            let _ = write!(
+                text,
+                "{}{}{}{}{}",
+                statement_line.debit_credit_mark().code(),
+                statement_line
+                    .funds_code()
+                    .map(String::from)
+                    .unwrap_or_default(),
+                Money::new(amount, currency_code.clone()).swift_amount(),
+                statement_line.transaction_type_identification_code(),
+                self.reference(statement_line.account_owner_ref())
+            );
+            if let Some(bank_ref) = statement_line.bank_ref() {
+                let _ = write!(text, "//{}", self.reference(bank_ref));
+            }
+            text.push('\n');
+            if let Some(supplementary_details) = statement_line.supplementary_details() {
+                let _ = writeln!(text, "{}", self.free_text(supplementary_details));
+            }
+            if let Some(information) = statement_line.information_to_account_owner() {
+                let information = self.information(message.information_format(), information);
+                let _ = writeln!(text, ":86:{}", information.join("\n"));
+            }
+        }
+        let _ = writeln!(
+            text,
+            ":62F:{}",
+            swift_balance(
+                message.closing_balance(),
+                shifts.iter().map(|(_, shift)| shift).sum()
+            )
+        );
+        if let Some(balance) = message.closing_available_balance() {
+            let shift = shift_at(balance.date().value());
+            let _ = writeln!(text, ":64:{}", swift_balance(balance, shift));
+        }
+        for balance in message.forward_available_balances() {
+            let shift = shift_at(balance.date().value());
+            let _ = writeln!(text, ":65:{}", swift_balance(balance, shift));
+        }
+        if let Some(information) = message.information_to_account_owner() {
+            let information = self.information(message.information_format(), information);
+            let _ = writeln!(text, ":86:{}", information.join("\n"));
+        }
+        text.push('-');
+        text
+    }
+
+    fn pseudonym(&mut self, kind: PseudonymKind, value: &str) -> String {
+        let counters = &mut self.counters;
+        self.pseudonyms
+            .entry((kind, value.to_string()))
+            .or_insert_with(|| {
+                let counter = counters.entry(kind).or_default();
+                *counter += 1;
+                format!("{}{:04}", kind.prefix(), counter)
+            })
+            .clone()
+    }
+
+    fn reference(&mut self, value: &str) -> String {
+        if value == NO_REFERENCE {
+            return value.to_string();
+        }
+        self.pseudonym(PseudonymKind::Reference, value)
+    }
+
+    fn amount(&self, index: usize, amount: Decimal) -> Decimal {
+        let Some(seed) = self.amount_seed else {
+            return amount;
+        };
+        let random = split_mix(seed.wrapping_add(index as u64));
+        let factor = Decimal::new(
+            MIN_PERTURBATION_FACTOR + (random % PERTURBATION_FACTOR_RANGE) as i64,
+            PERTURBATION_FACTOR_SCALE,
+        );
+        let perturbed = (amount * factor).round_dp(amount.scale());
+        if perturbed.is_zero() {
+            amount
+        } else {
+            perturbed
+        }
+    }
+
+    fn information(
+        &mut self,
+        information_format: InformationFormat,
+        information: &[Cow<'_, str>],
+    ) -> Vec<String> {
+        match information_format {
+            InformationFormat::Unstructured => information
+                .iter()
+                .map(|line| self.free_text(line))
+                .collect(),
+            InformationFormat::SlashCodes => {
+                let text = information_format
+                    .subfields(information)
+                    .iter()
+                    .map(|subfield| {
+                        let value = self.subfield(
+                            subfield,
+                            &SLASH_CODE_ACCOUNTS,
+                            &SLASH_CODE_PARTIES,
+                            &SLASH_CODE_ADDRESSES,
+                            &[],
+                        );
+                        match subfield.code() {
+                            "" => value,
+                            code => format!("/{}/{}", code, value),
+                        }
+                    })
+                    .collect::<String>();
+                wrap(&text)
+            }
+            InformationFormat::QuestionMarkSubfields => {
+                let text = information_format
+                    .subfields(information)
+                    .iter()
+                    .map(|subfield| {
+                        let value = self.subfield(
+                            subfield,
+                            &QUESTION_MARK_ACCOUNTS,
+                            &QUESTION_MARK_PARTIES,
+                            &[],
+                            &QUESTION_MARK_PRESERVED,
+                        );
+                        match subfield.code() {
+                            "" => value,
+                            code => format!("?{}{}", code, value),
+                        }
+                    })
+                    .collect::<String>();
+                wrap(&text)
+            }
+        }
+    }
+
+    fn subfield(
+        &mut self,
+        subfield: &InformationSubfield,
+        accounts: &[&str],
+        parties: &[&str],
+        addresses: &[&str],
+        preserved: &[&str],
+    ) -> String {
+        let value = subfield.value();
+        if value.trim().is_empty() || preserved.contains(&subfield.code()) {
+            value.to_string()
+        } else if accounts.contains(&subfield.code()) {
+            self.pseudonym(PseudonymKind::Account, value.trim())
+        } else if parties.contains(&subfield.code()) {
+            self.pseudonym(PseudonymKind::Party, value.trim())
+        } else if addresses.contains(&subfield.code()) {
+            self.pseudonym(PseudonymKind::Address, value.trim())
+        } else {
+            self.free_text(value)
+        }
+    }
+
+    fn free_text(&mut self, text: &str) -> String {
+        if text.trim().is_empty() {
+            return text.to_string();
+        }
+        if !self.preserve_free_text {
+            return self.pseudonym(PseudonymKind::Text, text.trim());
+        }
+        let mut result = String::with_capacity(text.len());
+        let mut rest = text;
+        while !rest.is_empty() {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let (token, tail) = rest.split_at(end);
+            if token.bytes().filter(u8::is_ascii_digit).count() >= SENSITIVE_NUMBER_MIN_DIGITS {
+                result.push_str(&self.pseudonym(PseudonymKind::Account, token));
+            } else {
+                result.push_str(token);
+            }
+            let whitespace = tail
+                .find(|c: char| !c.is_whitespace())
+                .unwrap_or(tail.len());
+            result.push_str(&tail[..whitespace]);
+            rest = &tail[whitespace..];
+        }
+        result
+    }
+}
+
+fn swift_balance(balance: &Balance<'_>, shift: Decimal) -> String {
+    let money = Money::new(
+        Money::from(balance).value() + shift,
+        balance.currency_code().clone(),
+    );
+    format!(
+        "{}{}{}{}",
+        money.credit_debit_mark().code(),
+        balance.date().value().format("%y%m%d"),
+        balance.currency_code(),
+        money.swift_amount()
+    )
+}
+
+fn wrap(text: &str) -> Vec<String> {
+    let chars = text.chars().collect::<Vec<_>>();
+    chars
+        .chunks(INFORMATION_LINE_LENGTH)
+        .map(|line| line.iter().collect())
+        .collect()
+}
+
+pub(crate) fn split_mix(seed: u64) -> u64 {
+    let mut value = seed.wrapping_add(0x9e3779b97f4a7c15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94d049bb133111eb);
+    value ^ (value >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::balance_timeline::*;
+
+    const MESSAGE: &str = ":20:STMT230306
+:21:PREV230305
+:25:NL91ABNA0417164300
+:28C:45/1
+:60F:C230305EUR1000,00
+:61:2303060306D200,50NTRFINV-1001//BR-1
+:86:/NAME/ACME LTD/IBAN/DE89370400440532013000/REMI/INVOICE 1001
+:61:230306C50,NCHKNONREF
+:86:/NAME/JOHN SMITH/REMI/REFUND
+:61:230306RD12,30NTRFINV-1001
+:86:/NAME/ACME LTD/REMI/CARD 4111111111111111
+:62F:C230306EUR861,80
+:64:C230306EUR861,80
+:65:C230307EUR861,80
+:86:STATEMENT FOR NL91ABNA0417164300
+-";
+
+    #[test]
+    fn test_anonymize() {
+        let message = Mt940CustomerStatementMessage::parse_with_dialect(MESSAGE, &Ing).unwrap();
+        let mut anonymizer = StatementAnonymizer::new();
+        let text = anonymizer.anonymize(&message);
+        assert_eq!(
+            text,
+            ":20:REF0001
+:21:REF0002
+:25:ACCOUNT0001
+:28C:45/1
+:60F:C230305EUR1000,00
+:61:2303060306D200,50NTRFREF0003//REF0004
+:86:/NAME/PARTY0001/IBAN/ACCOUNT0002/REMI/TEXT0001
+:61:230306C50,NCHKNONREF
+:86:/NAME/PARTY0002/REMI/TEXT0002
+:61:230306RD12,30NTRFREF0003
+:86:/NAME/PARTY0001/REMI/TEXT0003
+:62F:C230306EUR861,80
+:64:C230306EUR861,80
+:65:C230307EUR861,80
+:86:TEXT0004
+-"
+        );
+
+        let anonymized = Mt940CustomerStatementMessage::parse_with_dialect(&text, &Ing).unwrap();
+        assert_eq!(anonymized.statement_lines().len(), 3);
+        assert_eq!(
+            anonymized.information_format().counterparty(
+                anonymized.statement_lines()[0]
+                    .information_to_account_owner()
+                    .unwrap()
+            ),
+            Some("PARTY0001".to_string())
+        );
+        assert_eq!(
+            anonymizer.anonymize(&message),
+            text,
+            "pseudonyms must be stable within a run"
+        );
+    }
+
+    #[test]
+    fn test_anonymize_with_amount_perturbation() {
+        let message = Mt940CustomerStatementMessage::parse_with_dialect(MESSAGE, &Ing).unwrap();
+        let text = StatementAnonymizer::new()
+            .with_amount_perturbation(42)
+            .anonymize(&message);
+        assert_eq!(
+            text,
+            StatementAnonymizer::new()
+                .with_amount_perturbation(42)
+                .anonymize(&message)
+        );
+
+        let anonymized = Mt940CustomerStatementMessage::parse_with_dialect(&text, &Ing).unwrap();
+        let check = |message: &Mt940CustomerStatementMessage<'_>| {
+            BalanceTimeline::new(message).unwrap().check(message)
+        };
+        assert_eq!(check(&message), Ok(()));
+        assert_eq!(check(&anonymized), Ok(()));
+        assert_eq!(anonymized.opening_balance(), message.opening_balance());
+        assert_ne!(
+            anonymized.statement_lines()[0].amount(),
+            message.statement_lines()[0].amount()
+        );
+        let shift = |balance: Option<&Balance<'_>>, original: Option<&Balance<'_>>| {
+            Money::from(balance.unwrap()).value() - Money::from(original.unwrap()).value()
+        };
+        assert_eq!(
+            shift(
+                anonymized.forward_available_balances().first(),
+                message.forward_available_balances().first()
+            ),
+            shift(
+                Some(anonymized.closing_balance()),
+                Some(message.closing_balance())
+            )
+        );
+    }
+
+    #[test]
+    fn test_anonymize_question_mark_subfields() {
+        let message = Mt940CustomerStatementMessage::parse_with_dialect(
+            ":20:STMT
+:25:DE89370400440532013000
+:28C:1
+:60F:C230305EUR100,00
+:61:230306C50,NTRFNONREF
+:86:166?00GUTSCHRIFT?20EREF+INVOICE 1001?30DEUTDEFF?31DE02120300000000
+202051?32ACME LTD
+:62F:C230306EUR150,00
+-",
+            &DeutscheBank,
+        )
+        .unwrap();
+        let text = StatementAnonymizer::new().anonymize(&message);
+        assert!(text.contains(
+            ":86:166?00GUTSCHRIFT?20TEXT0001?30TEXT0002?31ACCOUNT0002?32PARTY0001
+"
+        ));
+        let anonymized =
+            Mt940CustomerStatementMessage::parse_with_dialect(&text, &DeutscheBank).unwrap();
+        assert_eq!(
+            anonymized.information_format().counterparty(
+                anonymized.statement_lines()[0]
+                    .information_to_account_owner()
+                    .unwrap()
+            ),
+            Some("PARTY0001".to_string())
+        );
+    }
+
+    #[test]
+    fn test_anonymize_unstructured_information() {
+        let message = Mt940CustomerStatementMessage::parse_with_dialect(
+            ":20:STMT
+:25:123456789
+:28C:1
+:60F:C230305EUR100,00
+:61:230306C50,NTRFNONREF//BR-1
+JOHN SMITH PAYROLL
+:86:ACME LTD, 1 HIGH ST
+LONDON INVOICE 1001
+:62F:C230306EUR150,00
+-",
+            &Standard,
+        )
+        .unwrap();
+        let text = StatementAnonymizer::new().anonymize(&message);
+        for original in ["JOHN", "SMITH", "ACME", "HIGH ST", "LONDON", "1001"] {
+            assert!(
+                !text.contains(original),
+                "{} leaked into {}",
+                original,
+                text
+            );
+        }
+        let anonymized =
+            Mt940CustomerStatementMessage::parse_with_dialect(&text, &Standard).unwrap();
+        assert_eq!(
+            anonymized.statement_lines()[0].supplementary_details(),
+            Some("TEXT0001")
+        );
+
+        let text = StatementAnonymizer::new()
+            .with_free_text_preserved()
+            .anonymize(&message);
+        assert!(text.contains(":86:ACME LTD, 1 HIGH ST\nLONDON INVOICE 1001\n"));
+    }
+}
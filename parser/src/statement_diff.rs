@@ -0,0 +1,595 @@
+use crate::mt_940_customer_statement_message::*;
+use crate::statement_report::json_string;
+use std::cmp::Reverse;
+use std::fmt::{Display, Formatter};
+
+const NO_REFERENCE: &str = "NONREF";
+const EQUAL_SCORE: u8 = 4;
+const REFERENCE_SCORE: u8 = 2;
+const AMOUNT_SCORE: u8 = 1;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    field: &'static str,
+    old: Option<String>,
+    new: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatementLineChange<'a> {
+    Added {
+        index: usize,
+        statement_line: &'a StatementLine<'a>,
+    },
+    Removed {
+        index: usize,
+        statement_line: &'a StatementLine<'a>,
+    },
+    Modified {
+        old_index: usize,
+        new_index: usize,
+        changes: Vec<FieldChange>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatementDiff<'a> {
+    header_changes: Vec<FieldChange>,
+    balance_changes: Vec<FieldChange>,
+    statement_line_changes: Vec<StatementLineChange<'a>>,
+}
+
+impl FieldChange {
+    fn new(field: &'static str, old: Option<String>, new: Option<String>) -> Option<Self> {
+        (old != new).then_some(Self { field, old, new })
+    }
+
+    pub fn field(&self) -> &'static str {
+        self.field
+    }
+
+    pub fn old(&self) -> Option<&str> {
+        self.old.as_deref()
+    }
+
+    pub fn new_value(&self) -> Option<&str> {
+        self.new.as_deref()
+    }
+
+    fn to_json(&self) -> String {
+        format!(
+            "{{\"field\":{},\"old\":{},\"new\":{}}}",
+            json_string(self.field),
+            json_value(self.old.as_deref()),
+            json_value(self.new.as_deref())
+        )
+    }
+}
+
+impl Display for FieldChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.field,
+            self.old.as_deref().unwrap_or("none"),
+            self.new.as_deref().unwrap_or("none")
+        )
+    }
+}
+
+impl StatementLineChange<'_> {
+    fn to_json(&self) -> String {
+        match self {
+            StatementLineChange::Added {
+                index,
+                statement_line,
+            } => format!(
+                "{{\"change\":\"added\",\"index\":{},\"statement_line\":{}}}",
+                index,
+                json_string(&summary(statement_line))
+            ),
+            StatementLineChange::Removed {
+                index,
+                statement_line,
+            } => format!(
+                "{{\"change\":\"removed\",\"index\":{},\"statement_line\":{}}}",
+                index,
+                json_string(&summary(statement_line))
+            ),
+            StatementLineChange::Modified {
+                old_index,
+                new_index,
+                changes,
+            } => format!(
+                "{{\"change\":\"modified\",\"old_index\":{},\"new_index\":{},\"fields\":[{}]}}",
+                old_index,
+                new_index,
+                changes
+                    .iter()
+                    .map(FieldChange::to_json)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+        }
+    }
+}
+
+impl Display for StatementLineChange<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatementLineChange::Added {
+                index,
+                statement_line,
+            } => write!(
+                f,
+                "+ statement line {}: {}",
+                index + 1,
+                summary(statement_line)
+            ),
+            StatementLineChange::Removed {
+                index,
+                statement_line,
+            } => write!(
+                f,
+                "- statement line {}: {}",
+                index + 1,
+                summary(statement_line)
+            ),
+            StatementLineChange::Modified {
+                old_index,
+                new_index,
+                changes,
+            } => {
+                write!(f, "~ statement line {}", old_index + 1)?;
+                if old_index != new_index {
+                    write!(f, " -> {}", new_index + 1)?;
+                }
+                for change in changes {
+                    write!(f, "\n    {}", change)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<'a> StatementDiff<'a> {
+    pub fn new(
+        old: &'a Mt940CustomerStatementMessage<'a>,
+        new: &'a Mt940CustomerStatementMessage<'a>,
+    ) -> Self {
+        Self {
+            header_changes: header_changes(old, new),
+            balance_changes: balance_changes(old, new),
+            statement_line_changes: statement_line_changes(
+                old.statement_lines(),
+                new.statement_lines(),
+            ),
+        }
+    }
+
+    pub fn header_changes(&self) -> &[FieldChange] {
+        &self.header_changes
+    }
+
+    pub fn balance_changes(&self) -> &[FieldChange] {
+        &self.balance_changes
+    }
+
+    pub fn statement_line_changes(&self) -> &[StatementLineChange<'a>] {
+        &self.statement_line_changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.header_changes.is_empty()
+            && self.balance_changes.is_empty()
+            && self.statement_line_changes.is_empty()
+    }
+
+    pub fn to_json(&self) -> String {
+        let field_changes = |changes: &[FieldChange]| {
+            changes
+                .iter()
+                .map(FieldChange::to_json)
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        format!(
+            "{{\"header\":[{}],\"balances\":[{}],\"statement_lines\":[{}]}}",
+            field_changes(&self.header_changes),
+            field_changes(&self.balance_changes),
+            self.statement_line_changes
+                .iter()
+                .map(StatementLineChange::to_json)
+                .collect::<Vec<_>>()
+                .join(",")
+        )
+    }
+}
+
+fn header_changes(
+    old: &Mt940CustomerStatementMessage<'_>,
+    new: &Mt940CustomerStatementMessage<'_>,
+) -> Vec<FieldChange> {
+    let information = |message: &Mt940CustomerStatementMessage<'_>| {
+        message
+            .information_to_account_owner()
+            .map(|information| information.join("\n"))
+    };
+    [
+        FieldChange::new(
+            "transaction_reference_number",
+            Some(old.transaction_reference_number().to_string()),
+            Some(new.transaction_reference_number().to_string()),
+        ),
+        FieldChange::new(
+            "related_reference",
+            old.related_reference().map(ToString::to_string),
+            new.related_reference().map(ToString::to_string),
+        ),
+        FieldChange::new(
+            "account_identification",
+            Some(old.account_identification().as_str().to_string()),
+            Some(new.account_identification().as_str().to_string()),
+        ),
+        FieldChange::new(
+            "statement_sequence_number",
+            old.statement_sequence_no().map(ToString::to_string),
+            new.statement_sequence_no().map(ToString::to_string),
+        ),
+        FieldChange::new(
+            "information_to_account_owner",
+            information(old),
+            information(new),
+        ),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+fn balance_changes(
+    old: &Mt940CustomerStatementMessage<'_>,
+    new: &Mt940CustomerStatementMessage<'_>,
+) -> Vec<FieldChange> {
+    [
+        FieldChange::new(
+            "opening_balance",
+            Some(balance_summary(old.opening_balance())),
+            Some(balance_summary(new.opening_balance())),
+        ),
+        FieldChange::new(
+            "closing_balance",
+            Some(balance_summary(old.closing_balance())),
+            Some(balance_summary(new.closing_balance())),
+        ),
+        FieldChange::new(
+            "closing_available_balance",
+            old.closing_available_balance().map(balance_summary),
+            new.closing_available_balance().map(balance_summary),
+        ),
+        FieldChange::new(
+            "forward_available_balances",
+            balance_summaries(old.forward_available_balances()),
+            balance_summaries(new.forward_available_balances()),
+        ),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+fn statement_line_changes<'a>(
+    old: &'a [StatementLine<'a>],
+    new: &'a [StatementLine<'a>],
+) -> Vec<StatementLineChange<'a>> {
+    let mut candidates = Vec::new();
+    for (old_index, old_line) in old.iter().enumerate() {
+        for (new_index, new_line) in new.iter().enumerate() {
+            let score = alignment_score(old_line, new_line);
+            if score > 0 {
+                candidates.push((score, old_index, new_index));
+            }
+        }
+    }
+    candidates.sort_by_key(|&(score, old_index, new_index)| {
+        (
+            Reverse(score),
+            old_index.abs_diff(new_index),
+            old_index,
+            new_index,
+        )
+    });
+
+    let mut old_matches = vec![None; old.len()];
+    let mut new_matched = vec![false; new.len()];
+    for (_, old_index, new_index) in candidates {
+        if old_matches[old_index].is_none() && !new_matched[new_index] {
+            old_matches[old_index] = Some(new_index);
+            new_matched[new_index] = true;
+        }
+    }
+
+    let mut changes = Vec::new();
+    for (old_index, new_index) in old_matches.into_iter().enumerate() {
+        match new_index {
+            Some(new_index) => {
+                let line_changes = statement_line_field_changes(&old[old_index], &new[new_index]);
+                if !line_changes.is_empty() {
+                    changes.push(StatementLineChange::Modified {
+                        old_index,
+                        new_index,
+                        changes: line_changes,
+                    });
+                }
+            }
+            None => changes.push(StatementLineChange::Removed {
+                index: old_index,
+                statement_line: &old[old_index],
+            }),
+        }
+    }
+    changes.extend(
+        new.iter()
+            .enumerate()
+            .filter(|(index, _)| !new_matched[*index])
+            .map(|(index, statement_line)| StatementLineChange::Added {
+                index,
+                statement_line,
+            }),
+    );
+    changes
+}
+
+fn alignment_score(old: &StatementLine<'_>, new: &StatementLine<'_>) -> u8 {
+    if old == new {
+        return EQUAL_SCORE;
+    }
+    let mut score = 0;
+    let account_owner_ref_matches = old.account_owner_ref() != NO_REFERENCE
+        && old.account_owner_ref() == new.account_owner_ref();
+    let bank_ref_matches = old
+        .bank_ref()
+        .is_some_and(|bank_ref| bank_ref != NO_REFERENCE)
+        && old.bank_ref() == new.bank_ref();
+    if account_owner_ref_matches || bank_ref_matches {
+        score += REFERENCE_SCORE;
+    }
+    if old.debit_credit_mark() == new.debit_credit_mark()
+        && old.amount().value() == new.amount().value()
+        && old.value_date() == new.value_date()
+    {
+        score += AMOUNT_SCORE;
+    }
+    score
+}
+
+fn statement_line_field_changes(
+    old: &StatementLine<'_>,
+    new: &StatementLine<'_>,
+) -> Vec<FieldChange> {
+    let information = |statement_line: &StatementLine<'_>| {
+        statement_line
+            .information_to_account_owner()
+            .map(|information| information.join("\n"))
+    };
+    let amount = |statement_line: &StatementLine<'_>| {
+        Some(statement_line.amount().value().normalize().to_string())
+    };
+    [
+        FieldChange::new(
+            "value_date",
+            Some(old.value_date().to_string()),
+            Some(new.value_date().to_string()),
+        ),
+        FieldChange::new(
+            "entry_date",
+            old.entry_date().map(ToString::to_string),
+            new.entry_date().map(ToString::to_string),
+        ),
+        FieldChange::new(
+            "mark",
+            Some(old.debit_credit_mark().to_string()),
+            Some(new.debit_credit_mark().to_string()),
+        ),
+        FieldChange::new(
+            "funds_code",
+            old.funds_code().map(String::from),
+            new.funds_code().map(String::from),
+        ),
+        FieldChange::new("amount", amount(old), amount(new)),
+        FieldChange::new(
+            "transaction_type",
+            Some(old.transaction_type_identification_code().to_string()),
+            Some(new.transaction_type_identification_code().to_string()),
+        ),
+        FieldChange::new(
+            "account_owner_ref",
+            Some(old.account_owner_ref().to_string()),
+            Some(new.account_owner_ref().to_string()),
+        ),
+        FieldChange::new(
+            "bank_ref",
+            old.bank_ref().map(str::to_string),
+            new.bank_ref().map(str::to_string),
+        ),
+        FieldChange::new(
+            "supplementary_details",
+            old.supplementary_details().map(str::to_string),
+            new.supplementary_details().map(str::to_string),
+        ),
+        FieldChange::new(
+            "information_to_account_owner",
+            information(old),
+            information(new),
+        ),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+fn summary(statement_line: &StatementLine<'_>) -> String {
+    let mut summary = format!(
+        "{} {} {} {}",
+        statement_line.value_date(),
+        statement_line.debit_credit_mark(),
+        statement_line.amount().value().normalize(),
+        statement_line.account_owner_ref()
+    );
+    if let Some(bank_ref) = statement_line.bank_ref() {
+        summary.push_str("//");
+        summary.push_str(bank_ref);
+    }
+    summary
+}
+
+fn balance_summary(balance: &Balance<'_>) -> String {
+    format!(
+        "{} {} {} {}",
+        balance.debit_credit_mark(),
+        balance.date(),
+        balance.currency_code(),
+        balance.amount().value().normalize()
+    )
+}
+
+fn balance_summaries(balances: &[Balance<'_>]) -> Option<String> {
+    (!balances.is_empty()).then(|| {
+        balances
+            .iter()
+            .map(balance_summary)
+            .collect::<Vec<_>>()
+            .join(", ")
+    })
+}
+
+fn json_value(value: Option<&str>) -> String {
+    value.map(json_string).unwrap_or_else(|| "null".to_string())
+}
+
+impl Display for StatementDiff<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "Statements are identical");
+        }
+        if !self.header_changes.is_empty() {
+            writeln!(f, "Header:")?;
+            for change in &self.header_changes {
+                writeln!(f, "  {}", change)?;
+            }
+        }
+        if !self.balance_changes.is_empty() {
+            writeln!(f, "Balances:")?;
+            for change in &self.balance_changes {
+                writeln!(f, "  {}", change)?;
+            }
+        }
+        if !self.statement_line_changes.is_empty() {
+            writeln!(f, "Statement lines:")?;
+            for change in &self.statement_line_changes {
+                writeln!(f, "  {}", change)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORIGINAL: &str = ":20:STMT
+:25:DK5000400440116243
+:28C:1
+:60F:C230305DKK1000,00
+:61:2303060306D200,50NTRFINV-1001//BR-1
+:86:INVOICE 1001
+:61:230306C50,NCHKNONREF
+:86:REFUND
+:61:230306D75,NTRFINV-1002
+:62F:C230306DKK774,50
+-";
+    const CORRECTED: &str = ":20:STMT-CORR
+:25:DK5000400440116243
+:28C:1
+:60F:C230305DKK1000,00
+:61:230306C50,NCHKNONREF
+:86:REFUND
+:61:2303060307D210,5NTRFINV-1001//BR-1
+:86:INVOICE 1001
+:61:230306D20,NTRFINV-1003
+:62F:C230306DKK819,50
+-";
+
+    #[test]
+    fn test_diff() {
+        let original = Mt940CustomerStatementMessage::try_from(ORIGINAL).unwrap();
+        let corrected = Mt940CustomerStatementMessage::try_from(CORRECTED).unwrap();
+        let diff = StatementDiff::new(&original, &corrected);
+
+        assert_eq!(diff.header_changes().len(), 1);
+        assert_eq!(
+            diff.header_changes()[0].field(),
+            "transaction_reference_number"
+        );
+        assert_eq!(diff.header_changes()[0].old(), Some("STMT"));
+        assert_eq!(diff.header_changes()[0].new_value(), Some("STMT-CORR"));
+        assert_eq!(diff.balance_changes().len(), 1);
+        assert_eq!(
+            diff.balance_changes()[0].to_string(),
+            "closing_balance: Credit 2023-03-06 DKK 774.5 -> Credit 2023-03-06 DKK 819.5"
+        );
+        assert_eq!(diff.statement_line_changes().len(), 3);
+        assert_eq!(
+            diff.to_string(),
+            "Header:
+  transaction_reference_number: STMT -> STMT-CORR
+Balances:
+  closing_balance: Credit 2023-03-06 DKK 774.5 -> Credit 2023-03-06 DKK 819.5
+Statement lines:
+  ~ statement line 1 -> 2
+    entry_date: 2023-03-06 -> 2023-03-07
+    amount: 200.5 -> 210.5
+  - statement line 3: 2023-03-06 Debit 75 INV-1002
+  + statement line 3: 2023-03-06 Debit 20 INV-1003
+"
+        );
+    }
+
+    #[test]
+    fn test_diff_to_json() {
+        let original = Mt940CustomerStatementMessage::try_from(ORIGINAL).unwrap();
+        let text = ORIGINAL.replace(":86:REFUND", ":86:REFUND \"SHOP\"");
+        let corrected = Mt940CustomerStatementMessage::try_from(text.as_str()).unwrap();
+        let diff = StatementDiff::new(&original, &corrected);
+        assert_eq!(
+            diff.to_json(),
+            "{\"header\":[],\"balances\":[],\"statement_lines\":[{\"change\":\"modified\",\"old_index\":1,\"new_index\":1,\"fields\":[{\"field\":\"information_to_account_owner\",\"old\":\"REFUND\",\"new\":\"REFUND \\\"SHOP\\\"\"}]}]}"
+        );
+    }
+
+    #[test]
+    fn test_diff_unrelated_statement_lines() {
+        let original = Mt940CustomerStatementMessage::try_from(ORIGINAL).unwrap();
+        let text = ORIGINAL.replace(":61:230306D75,NTRFINV-1002", ":61:230309D75,NTRFINV-2002");
+        let corrected = Mt940CustomerStatementMessage::try_from(text.as_str()).unwrap();
+        let diff = StatementDiff::new(&original, &corrected);
+        assert_eq!(
+            diff.to_string(),
+            "Statement lines:
+  - statement line 3: 2023-03-06 Debit 75 INV-1002
+  + statement line 3: 2023-03-09 Debit 75 INV-2002
+"
+        );
+    }
+
+    #[test]
+    fn test_identical_statements() {
+        let original = Mt940CustomerStatementMessage::try_from(ORIGINAL).unwrap();
+        let diff = StatementDiff::new(&original, &original);
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "Statements are identical\n");
+    }
+}
@@ -0,0 +1,549 @@
+use crate::money::*;
+use crate::mt_940_customer_statement_message::*;
+use crate::statement_anonymizer::split_mix;
+use chrono::{Days, NaiveDate};
+use rust_decimal::Decimal;
+use std::fmt::Write;
+use std::io;
+use std::ops::Range;
+
+const DEFAULT_ACCOUNTS: usize = 1;
+const DEFAULT_DAYS: usize = 1;
+const DEFAULT_TRANSACTIONS_PER_DAY: usize = 10;
+const DEFAULT_CURRENCY: &str = "EUR";
+const DEFAULT_START_YEAR: i32 = 2023;
+const DEFAULT_MINOR_UNITS: u8 = 2;
+const IBAN_COUNTRY_CODE: &str = "NL";
+const IBAN_BANK_CODE: &str = "ABNA";
+const IBAN_ACCOUNT_NUMBER_MODULUS: u64 = 10_000_000_000;
+const IBAN_CHECKSUM_MODULUS: u32 = 97;
+const IBAN_CHECK_DIGITS_BASE: u32 = 98;
+const REFERENCE_NUMBER_MODULUS: u64 = 1_000_000;
+const OPENING_BALANCE_MAX: i64 = 1_000_000;
+const TEMPLATES: [Template; 8] = [
+    Template::new(
+        "NTRF",
+        Direction::Credit,
+        100,
+        25_000,
+        "SEPA CREDIT TRANSFER",
+        &CUSTOMERS,
+        "INVOICE",
+    ),
+    Template::new(
+        "NTRF",
+        Direction::Debit,
+        50,
+        15_000,
+        "SEPA CREDIT TRANSFER",
+        &SUPPLIERS,
+        "PAYMENT",
+    ),
+    Template::new(
+        "NDDT",
+        Direction::Debit,
+        20,
+        500,
+        "SEPA DIRECT DEBIT",
+        &UTILITIES,
+        "CONTRACT",
+    ),
+    Template::new(
+        "NMSC",
+        Direction::Debit,
+        5,
+        300,
+        "CARD PAYMENT",
+        &MERCHANTS,
+        "RECEIPT",
+    ),
+    Template::new(
+        "NCHK",
+        Direction::Credit,
+        50,
+        5_000,
+        "CHEQUE DEPOSIT",
+        &CUSTOMERS,
+        "CHEQUE",
+    ),
+    Template::new(
+        "NSTO",
+        Direction::Debit,
+        500,
+        3_000,
+        "STANDING ORDER",
+        &LANDLORDS,
+        "RENT",
+    ),
+    Template::new(
+        "NCOM",
+        Direction::Debit,
+        1,
+        50,
+        "BANK COMMISSION",
+        &[],
+        "FEE",
+    ),
+    Template::new(
+        "NINT",
+        Direction::Credit,
+        1,
+        200,
+        "INTEREST",
+        &[],
+        "INTEREST",
+    ),
+];
+const CUSTOMERS: [&str; 5] = [
+    "ACME LTD",
+    "GLOBEX CORP",
+    "INITECH BV",
+    "UMBRELLA GMBH",
+    "STARK INDUSTRIES",
+];
+const SUPPLIERS: [&str; 4] = [
+    "HOOLI BV",
+    "SOYLENT NV",
+    "WAYNE SUPPLIES",
+    "VANDELAY IMPORT",
+];
+const UTILITIES: [&str; 3] = ["CITY WATER", "NORTH ENERGY", "FASTNET TELECOM"];
+const MERCHANTS: [&str; 4] = [
+    "CORNER CAFE",
+    "CITY TAXI",
+    "OFFICE DEPOT",
+    "FUEL STATION 12",
+];
+const LANDLORDS: [&str; 2] = ["CENTRAL PROPERTIES", "HARBOUR REAL ESTATE"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Direction {
+    Credit,
+    Debit,
+}
+
+#[derive(Debug)]
+struct Template {
+    transaction_type: &'static str,
+    direction: Direction,
+    min_amount: i64,
+    max_amount: i64,
+    description: &'static str,
+    counterparties: &'static [&'static str],
+    remittance: &'static str,
+}
+
+#[derive(Debug, Clone)]
+pub struct StatementGenerator {
+    seed: u64,
+    accounts: usize,
+    days: usize,
+    transactions_per_day: usize,
+    start_date: NaiveDate,
+    currency_code: CurrencyCode<'static>,
+}
+
+struct Random(u64);
+
+struct AccountStatements<'g> {
+    generator: &'g StatementGenerator,
+    account: usize,
+    random: Random,
+    minor_units: u8,
+    scale: i64,
+    iban: String,
+    balance: Money,
+    days: Range<usize>,
+}
+
+impl Template {
+    const fn new(
+        transaction_type: &'static str,
+        direction: Direction,
+        min_amount: i64,
+        max_amount: i64,
+        description: &'static str,
+        counterparties: &'static [&'static str],
+        remittance: &'static str,
+    ) -> Self {
+        Self {
+            transaction_type,
+            direction,
+            min_amount,
+            max_amount,
+            description,
+            counterparties,
+            remittance,
+        }
+    }
+}
+
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.0 = split_mix(self.0);
+        self.0
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound.max(1)
+    }
+
+    fn between(&mut self, min: i64, max: i64) -> i64 {
+        min + self.below((max - min + 1) as u64) as i64
+    }
+
+    fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+}
+
+impl Default for StatementGenerator {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            accounts: DEFAULT_ACCOUNTS,
+            days: DEFAULT_DAYS,
+            transactions_per_day: DEFAULT_TRANSACTIONS_PER_DAY,
+            start_date: NaiveDate::from_ymd_opt(DEFAULT_START_YEAR, 1, 2)
+                .expect("start date must be valid"),
+            currency_code: CurrencyCode::try_from(DEFAULT_CURRENCY)
+                .expect("default currency must be valid")
+                .into_owned(),
+        }
+    }
+}
+
+impl StatementGenerator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_accounts(mut self, accounts: usize) -> Self {
+        self.accounts = accounts;
+        self
+    }
+
+    pub fn with_days(mut self, days: usize) -> Self {
+        self.days = days;
+        self
+    }
+
+    pub fn with_transactions_per_day(mut self, transactions_per_day: usize) -> Self {
+        self.transactions_per_day = transactions_per_day;
+        self
+    }
+
+    pub fn with_start_date(mut self, start_date: NaiveDate) -> Self {
+        self.start_date = start_date.clamp(Date::MIN, Date::MAX);
+        self
+    }
+
+    pub fn with_currency_code(mut self, currency_code: CurrencyCode<'_>) -> Self {
+        self.currency_code = currency_code.into_owned();
+        self
+    }
+
+    pub fn statements(&self) -> impl Iterator<Item = String> + '_ {
+        (0..self.accounts).flat_map(|account| AccountStatements::new(self, account))
+    }
+
+    pub fn generate(&self) -> Vec<String> {
+        self.statements().collect()
+    }
+
+    pub fn generate_text(&self) -> String {
+        self.generate().join("\n")
+    }
+
+    pub fn write_to(&self, mut writer: impl io::Write) -> io::Result<()> {
+        for statement in self.statements() {
+            writeln!(writer, "{}", statement)?;
+        }
+        writer.flush()
+    }
+
+    fn days(&self) -> usize {
+        let remaining = (Date::MAX - self.start_date).num_days() + 1;
+        self.days.min(usize::try_from(remaining).unwrap_or(0))
+    }
+
+    fn money(&self, value: Decimal) -> Money {
+        Money::new(value, self.currency_code.clone())
+    }
+}
+
+impl<'g> AccountStatements<'g> {
+    fn new(generator: &'g StatementGenerator, account: usize) -> Self {
+        let mut random = Random(generator.seed ^ split_mix(account as u64));
+        let minor_units = generator
+            .currency_code
+            .iso_4217()
+            .ok()
+            .and_then(|currency| currency.minor_units())
+            .unwrap_or(DEFAULT_MINOR_UNITS);
+        let scale = 10i64.pow(u32::from(minor_units));
+        let iban = iban(random.below(IBAN_ACCOUNT_NUMBER_MODULUS));
+        let balance = generator.money(Decimal::new(
+            random.between(0, OPENING_BALANCE_MAX * scale),
+            minor_units.into(),
+        ));
+        Self {
+            generator,
+            account,
+            random,
+            minor_units,
+            scale,
+            iban,
+            balance,
+            days: 0..generator.days(),
+        }
+    }
+
+    fn statement(&mut self, day: usize) -> String {
+        let generator = self.generator;
+        let random = &mut self.random;
+        let date = generator.start_date + Days::new(day as u64);
+        let mut text = format!(
+            ":20:STMT{}-{:03}\n:25:{}\n:28C:{}/1\n:60F:{}\n",
+            date.format("%y%m%d"),
+            self.account + 1,
+            self.iban,
+            day % usize::from(u16::MAX) + 1,
+            swift_balance(&self.balance, date)
+        );
+        for transaction in 0..generator.transactions_per_day {
+            let template = random.choose(&TEMPLATES);
+            let amount = generator.money(Decimal::new(
+                random.between(
+                    template.min_amount * self.scale,
+                    template.max_amount * self.scale,
+                ),
+                self.minor_units.into(),
+            ));
+            let (mark, signed) = match template.direction {
+                Direction::Credit => ("C", amount.clone()),
+                Direction::Debit => ("D", -amount.clone()),
+            };
+            self.balance = (self.balance.clone() + signed).expect("statement currency must match");
+            let reference = format!(
+                "{}{:06}",
+                &template.transaction_type[1..],
+                random.below(REFERENCE_NUMBER_MODULUS)
+            );
+            let _ = writeln!(
+                text,
+                ":61:{}{}{}{}{}//{}{:04}",
+                date.format("%y%m%d"),
+                mark,
+                amount.swift_amount(),
+                template.transaction_type,
+                reference,
+                date.format("%m%d"),
+                transaction + 1
+            );
+            let _ = write!(text, ":86:/TRTP/{}", template.description);
+            if !template.counterparties.is_empty() {
+                let _ = write!(text, "/NAME/{}", random.choose(template.counterparties));
+            }
+            let _ = writeln!(text, "\n/REMI/{} {}", template.remittance, reference);
+        }
+        let _ = writeln!(text, ":62F:{}", swift_balance(&self.balance, date));
+        let _ = writeln!(text, ":64:{}", swift_balance(&self.balance, date));
+        text.push('-');
+        text
+    }
+}
+
+impl Iterator for AccountStatements<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let day = self.days.next()?;
+        Some(self.statement(day))
+    }
+}
+
+fn swift_balance(money: &Money, date: NaiveDate) -> String {
+    format!(
+        "{}{}{}{}",
+        money.credit_debit_mark().code(),
+        date.format("%y%m%d"),
+        money.currency_code(),
+        money.swift_amount()
+    )
+}
+
+fn iban(account_number: u64) -> String {
+    let bban = format!("{}{:010}", IBAN_BANK_CODE, account_number);
+    let checksum = format!("{}{}00", bban, IBAN_COUNTRY_CODE)
+        .chars()
+        .filter_map(|c| c.to_digit(36))
+        .fold(0, |checksum, digit| {
+            let base = if digit < 10 { 10 } else { 100 };
+            (checksum * base + digit) % IBAN_CHECKSUM_MODULUS
+        });
+    format!(
+        "{}{:02}{}",
+        IBAN_COUNTRY_CODE,
+        IBAN_CHECK_DIGITS_BASE - checksum,
+        bban
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::balance_timeline::*;
+    use crate::message_reader::*;
+
+    fn messages(generator: &StatementGenerator) -> Vec<Mt940CustomerStatementMessage<'static>> {
+        MessageReader::new(generator.generate_text().as_bytes())
+            .map(|result| result.unwrap().1)
+            .collect()
+    }
+
+    #[test]
+    fn test_generate_statements() {
+        let generator = StatementGenerator::new()
+            .with_seed(7)
+            .with_accounts(2)
+            .with_days(3)
+            .with_transactions_per_day(5);
+        let messages = messages(&generator);
+        assert_eq!(messages.len(), 6);
+
+        for account in messages.chunks(3) {
+            assert!(matches!(
+                account[0].account_identification().account_number(),
+                AccountNumber::Iban(None, _)
+            ));
+            for (day, message) in account.iter().enumerate() {
+                assert_eq!(message.statement_lines().len(), 5);
+                assert_eq!(
+                    message.statement_sequence_no().unwrap().statement_number(),
+                    day as u16 + 1
+                );
+                assert_eq!(
+                    BalanceTimeline::new(message).unwrap().check(message),
+                    Ok(())
+                );
+            }
+            for pair in account.windows(2) {
+                assert_eq!(
+                    pair[0].account_identification(),
+                    pair[1].account_identification()
+                );
+                assert_eq!(
+                    Money::from(pair[0].closing_balance()),
+                    Money::from(pair[1].opening_balance())
+                );
+            }
+        }
+        assert_ne!(
+            messages[0].account_identification(),
+            messages[3].account_identification()
+        );
+    }
+
+    #[test]
+    fn test_generator_is_reproducible() {
+        let generator = StatementGenerator::new().with_seed(42).with_days(2);
+        assert_eq!(generator.generate(), generator.clone().generate());
+        assert_ne!(
+            generator.generate(),
+            StatementGenerator::new()
+                .with_seed(43)
+                .with_days(2)
+                .generate()
+        );
+    }
+
+    #[test]
+    fn test_generate_with_currency() {
+        let generator = StatementGenerator::new()
+            .with_seed(1)
+            .with_currency_code(CurrencyCode::try_from("JPY").unwrap())
+            .with_start_date(NaiveDate::from_ymd_opt(2024, 2, 28).unwrap())
+            .with_days(2);
+        let messages = messages(&generator);
+        assert_eq!(
+            messages[1].closing_balance().currency_code().as_str(),
+            "JPY"
+        );
+        assert_eq!(messages[1].closing_balance().amount().decimal_places(), 0);
+        assert_eq!(
+            messages[1].closing_balance().date().value(),
+            NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_write_statements() {
+        let generator = StatementGenerator::new()
+            .with_seed(3)
+            .with_accounts(2)
+            .with_days(2);
+        let mut output = Vec::new();
+        generator.write_to(&mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            format!("{}\n", generator.generate_text())
+        );
+    }
+
+    #[test]
+    fn test_dates_within_two_digit_years() {
+        let generator = StatementGenerator::new()
+            .with_days(usize::from(u16::MAX) + 2)
+            .with_transactions_per_day(0);
+        let dates = generator
+            .statements()
+            .map(|text| {
+                Mt940CustomerStatementMessage::try_from(text.as_str())
+                    .unwrap()
+                    .closing_balance()
+                    .date()
+                    .value()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            dates.first(),
+            Some(&NaiveDate::from_ymd_opt(2023, 1, 2).unwrap())
+        );
+        assert_eq!(
+            dates.last(),
+            Some(&NaiveDate::from_ymd_opt(2099, 12, 31).unwrap())
+        );
+        assert!(
+            dates
+                .windows(2)
+                .all(|pair| pair[1] == pair[0] + Days::new(1))
+        );
+
+        let generator = StatementGenerator::new()
+            .with_start_date(NaiveDate::from_ymd_opt(1999, 12, 30).unwrap())
+            .with_days(2)
+            .with_transactions_per_day(0);
+        let message = messages(&generator).remove(0);
+        assert_eq!(
+            message.opening_balance().date().value(),
+            NaiveDate::from_ymd_opt(2000, 1, 1).unwrap()
+        );
+
+        let generator = StatementGenerator::new()
+            .with_start_date(NaiveDate::from_ymd_opt(2100, 1, 1).unwrap())
+            .with_days(2)
+            .with_transactions_per_day(0);
+        let messages = messages(&generator);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(
+            messages[0].opening_balance().date().value(),
+            NaiveDate::from_ymd_opt(2099, 12, 31).unwrap()
+        );
+    }
+}
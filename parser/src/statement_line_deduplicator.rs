@@ -0,0 +1,396 @@
+use crate::mt_940_customer_statement_message::*;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+const FIELD_SEPARATOR: u8 = 0x1F;
+const NO_REFERENCE: &str = "NONREF";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct StatementLineFingerprint(u64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateKind {
+    Exact,
+    Probable,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchedField {
+    Account,
+    ValueDate,
+    EntryDate,
+    Mark,
+    Amount,
+    AccountOwnerReference,
+    BankReference,
+    Information,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatementLineLocation {
+    message_index: usize,
+    statement_line_index: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Duplicate {
+    kind: DuplicateKind,
+    original: StatementLineLocation,
+    duplicate: StatementLineLocation,
+    matched_fields: Vec<MatchedField>,
+}
+
+#[derive(Debug, Default)]
+pub struct StatementLineDeduplicator {
+    message_count: usize,
+    entries: HashMap<(String, &'static str, Decimal), Vec<Entry>>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    location: StatementLineLocation,
+    fingerprint: StatementLineFingerprint,
+    value_date: NaiveDate,
+    entry_date: Option<NaiveDate>,
+    account_owner_ref: String,
+    bank_ref: Option<String>,
+    information: String,
+}
+
+impl StatementLineFingerprint {
+    pub fn new(
+        message: &Mt940CustomerStatementMessage<'_>,
+        statement_line: &StatementLine<'_>,
+    ) -> Self {
+        let entry_date = statement_line
+            .entry_date()
+            .map(|date| date.to_string())
+            .unwrap_or_default();
+        let fields = [
+            message.account_identification().as_str(),
+            &statement_line.value_date().to_string(),
+            &entry_date,
+            statement_line.debit_credit_mark().code(),
+            &statement_line.amount().value().normalize().to_string(),
+            statement_line.account_owner_ref(),
+            statement_line.bank_ref().unwrap_or_default(),
+            &normalized_information(statement_line),
+        ];
+        let hash = fields.iter().fold(FNV_OFFSET_BASIS, |hash, field| {
+            field
+                .bytes()
+                .chain([FIELD_SEPARATOR])
+                .fold(hash, |hash, byte| {
+                    (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+                })
+        });
+        Self(hash)
+    }
+
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+impl Display for StatementLineFingerprint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+fn normalized_information(statement_line: &StatementLine<'_>) -> String {
+    statement_line
+        .information_to_account_owner()
+        .unwrap_or_default()
+        .iter()
+        .flat_map(|line| line.chars())
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+impl StatementLineLocation {
+    pub(crate) fn new(message_index: usize, statement_line_index: usize) -> Self {
+        Self {
+            message_index,
+            statement_line_index,
+        }
+    }
+
+    pub fn message_index(&self) -> usize {
+        self.message_index
+    }
+
+    pub fn statement_line_index(&self) -> usize {
+        self.statement_line_index
+    }
+}
+
+impl Display for StatementLineLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "statement line {} of message {}",
+            self.statement_line_index + 1,
+            self.message_index + 1
+        )
+    }
+}
+
+impl Display for MatchedField {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatchedField::Account => write!(f, "account"),
+            MatchedField::ValueDate => write!(f, "value date"),
+            MatchedField::EntryDate => write!(f, "entry date"),
+            MatchedField::Mark => write!(f, "debit/credit mark"),
+            MatchedField::Amount => write!(f, "amount"),
+            MatchedField::AccountOwnerReference => write!(f, "account owner reference"),
+            MatchedField::BankReference => write!(f, "bank reference"),
+            MatchedField::Information => write!(f, "information to account owner"),
+        }
+    }
+}
+
+impl Duplicate {
+    pub fn kind(&self) -> DuplicateKind {
+        self.kind
+    }
+
+    pub fn original(&self) -> &StatementLineLocation {
+        &self.original
+    }
+
+    pub fn duplicate(&self) -> &StatementLineLocation {
+        &self.duplicate
+    }
+
+    pub fn matched_fields(&self) -> &[MatchedField] {
+        &self.matched_fields
+    }
+}
+
+impl Display for Duplicate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.kind {
+            DuplicateKind::Exact => "an exact",
+            DuplicateKind::Probable => "a probable",
+        };
+        let matched_fields = self
+            .matched_fields
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        write!(
+            f,
+            "{} is {} duplicate of {} (matched {})",
+            self.duplicate,
+            kind,
+            self.original,
+            matched_fields.join(", ")
+        )
+    }
+}
+
+impl StatementLineDeduplicator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, message: &Mt940CustomerStatementMessage<'_>) -> Vec<Duplicate> {
+        let message_index = self.message_count;
+        self.message_count += 1;
+
+        let mut duplicates = Vec::new();
+        let mut entries = Vec::new();
+        for (statement_line_index, statement_line) in message.statement_lines().iter().enumerate() {
+            let key = (
+                message.account_identification().as_str().to_string(),
+                statement_line.debit_credit_mark().code(),
+                statement_line.amount().value().normalize(),
+            );
+            let entry = Entry {
+                location: StatementLineLocation::new(message_index, statement_line_index),
+                fingerprint: StatementLineFingerprint::new(message, statement_line),
+                value_date: statement_line.value_date().value(),
+                entry_date: statement_line.entry_date().map(Date::value),
+                account_owner_ref: statement_line.account_owner_ref().to_string(),
+                bank_ref: statement_line.bank_ref().map(str::to_string),
+                information: normalized_information(statement_line),
+            };
+            let candidates = self.entries.get(&key).into_iter().flatten();
+            duplicates.extend(candidates.filter_map(|original| original.duplicate(&entry)));
+            entries.push((key, entry));
+        }
+        for (key, entry) in entries {
+            self.entries.entry(key).or_default().push(entry);
+        }
+        duplicates
+    }
+
+    pub fn find_duplicates<'m, 'a: 'm>(
+        messages: impl IntoIterator<Item = &'m Mt940CustomerStatementMessage<'a>>,
+    ) -> Vec<Duplicate> {
+        let mut deduplicator = Self::new();
+        messages
+            .into_iter()
+            .flat_map(|message| deduplicator.add(message))
+            .collect()
+    }
+}
+
+impl Entry {
+    fn duplicate(&self, other: &Entry) -> Option<Duplicate> {
+        let mut matched_fields = vec![
+            MatchedField::Account,
+            MatchedField::Mark,
+            MatchedField::Amount,
+        ];
+        if self.value_date != other.value_date {
+            return None;
+        }
+        matched_fields.push(MatchedField::ValueDate);
+        if self.entry_date.is_some() && self.entry_date == other.entry_date {
+            matched_fields.push(MatchedField::EntryDate);
+        }
+        let is_reference = |reference: &str| reference != NO_REFERENCE;
+        let identifying_fields = [
+            (
+                MatchedField::AccountOwnerReference,
+                is_reference(&self.account_owner_ref)
+                    && self.account_owner_ref == other.account_owner_ref,
+            ),
+            (
+                MatchedField::BankReference,
+                self.bank_ref.as_deref().is_some_and(is_reference)
+                    && self.bank_ref == other.bank_ref,
+            ),
+            (
+                MatchedField::Information,
+                !self.information.is_empty() && self.information == other.information,
+            ),
+        ];
+        matched_fields.extend(
+            identifying_fields
+                .iter()
+                .filter(|(_, matched)| *matched)
+                .map(|(field, _)| *field),
+        );
+        let kind = if self.fingerprint == other.fingerprint {
+            DuplicateKind::Exact
+        } else if identifying_fields.iter().any(|(_, matched)| *matched) {
+            DuplicateKind::Probable
+        } else {
+            return None;
+        };
+        Some(Duplicate {
+            kind,
+            original: self.location,
+            duplicate: other.location,
+            matched_fields,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORIGINAL: &str = ":20:STMT
+:25:DK5000400440116243
+:28C:1
+:60F:C230305DKK1000,00
+:61:2303060306D200,50NTRFINV-1001//BR-1
+:86:INVOICE 1001
+ACME LTD
+:61:230306C50,NCHKNONREF
+:86:REFUND
+:61:230306C50,NCHKNONREF
+:86:CASH
+:62F:C230306DKK899,50
+-";
+    const CORRECTED: &str = ":20:STMT-CORR
+:25:DK5000400440116243
+:28C:1
+:60F:C230305DKK1000,00
+:61:2303060306D200,5NTRFINV-1001//BR-1
+:86:INVOICE 1001 ACME
+ LTD
+:61:230306C50,00NCHKNONREF
+:86:REFUND FROM SHOP
+:61:230306C50,NCHKNONREF
+:86:DEPOSIT
+:62F:C230306DKK899,50
+-";
+
+    fn messages() -> Vec<Mt940CustomerStatementMessage<'static>> {
+        [ORIGINAL, CORRECTED]
+            .into_iter()
+            .map(|message| Mt940CustomerStatementMessage::try_from(message).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        let messages = messages();
+        let fingerprint = |message: usize, statement_line: usize| {
+            StatementLineFingerprint::new(
+                &messages[message],
+                &messages[message].statement_lines()[statement_line],
+            )
+        };
+        assert_eq!(fingerprint(0, 0), fingerprint(1, 0));
+        assert_ne!(fingerprint(0, 1), fingerprint(1, 1));
+        assert_ne!(fingerprint(0, 1), fingerprint(0, 2));
+        assert_eq!(fingerprint(0, 0).to_string().len(), 16);
+    }
+
+    #[test]
+    fn test_find_duplicates() {
+        let messages = messages();
+        let duplicates = StatementLineDeduplicator::find_duplicates(&messages);
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].kind(), DuplicateKind::Exact);
+        assert_eq!(duplicates[0].original().statement_line_index(), 0);
+        assert_eq!(duplicates[0].duplicate().message_index(), 1);
+        assert_eq!(
+            duplicates[0].to_string(),
+            "statement line 1 of message 2 is an exact duplicate of statement line 1 of message 1 \
+(matched account, debit/credit mark, amount, value date, entry date, account owner reference, \
+bank reference, information to account owner)"
+        );
+    }
+
+    #[test]
+    fn test_probable_duplicates() {
+        let corrected = CORRECTED.replace("REFUND FROM SHOP", "REFUND");
+        let corrected = corrected.replace(":61:2303060306D200,5NTRF", ":61:2303060307D200,5NTRF");
+        let corrected = Mt940CustomerStatementMessage::try_from(corrected.as_str()).unwrap();
+        let original = Mt940CustomerStatementMessage::try_from(ORIGINAL).unwrap();
+
+        let mut deduplicator = StatementLineDeduplicator::new();
+        assert!(deduplicator.add(&original).is_empty());
+        let duplicates = deduplicator.add(&corrected);
+        assert_eq!(duplicates.len(), 2);
+        assert_eq!(duplicates[0].kind(), DuplicateKind::Probable);
+        assert_eq!(
+            duplicates[0].matched_fields(),
+            [
+                MatchedField::Account,
+                MatchedField::Mark,
+                MatchedField::Amount,
+                MatchedField::ValueDate,
+                MatchedField::AccountOwnerReference,
+                MatchedField::BankReference,
+                MatchedField::Information,
+            ]
+        );
+        assert_eq!(duplicates[1].kind(), DuplicateKind::Exact);
+        assert_eq!(duplicates[1].original().statement_line_index(), 1);
+        assert_eq!(duplicates[1].duplicate().statement_line_index(), 1);
+    }
+}
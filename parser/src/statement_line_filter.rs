@@ -0,0 +1,294 @@
+use crate::mt_940_customer_statement_message::*;
+use chrono::NaiveDate;
+use regex::Regex;
+use rust_decimal::Decimal;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::{Bound, Not, RangeBounds};
+
+#[derive(Debug)]
+pub enum StatementLineFilter {
+    ValueDate(Bound<NaiveDate>, Bound<NaiveDate>),
+    EntryDate(Bound<NaiveDate>, Bound<NaiveDate>),
+    Amount(Bound<Decimal>, Bound<Decimal>),
+    Mark(StatementLineMark),
+    TransactionType(TransactionTypeIdentificationCode<'static>),
+    FundsCode(char),
+    Currency(String),
+    Reference(TextPattern),
+    Information(TextPattern),
+    And(Vec<StatementLineFilter>),
+    Or(Vec<StatementLineFilter>),
+    Not(Box<StatementLineFilter>),
+}
+
+#[derive(Debug)]
+pub enum TextPattern {
+    Contains(String),
+    StartsWith(String),
+    Regex(Regex),
+}
+
+impl StatementLineFilter {
+    pub fn value_date(range: impl RangeBounds<NaiveDate>) -> Self {
+        StatementLineFilter::ValueDate(range.start_bound().cloned(), range.end_bound().cloned())
+    }
+
+    pub fn entry_date(range: impl RangeBounds<NaiveDate>) -> Self {
+        StatementLineFilter::EntryDate(range.start_bound().cloned(), range.end_bound().cloned())
+    }
+
+    pub fn amount(range: impl RangeBounds<Decimal>) -> Self {
+        StatementLineFilter::Amount(range.start_bound().cloned(), range.end_bound().cloned())
+    }
+
+    pub fn and(self, other: StatementLineFilter) -> Self {
+        match self {
+            StatementLineFilter::And(mut filters) => {
+                filters.push(other);
+                StatementLineFilter::And(filters)
+            }
+            filter => StatementLineFilter::And(vec![filter, other]),
+        }
+    }
+
+    pub fn or(self, other: StatementLineFilter) -> Self {
+        match self {
+            StatementLineFilter::Or(mut filters) => {
+                filters.push(other);
+                StatementLineFilter::Or(filters)
+            }
+            filter => StatementLineFilter::Or(vec![filter, other]),
+        }
+    }
+
+    pub fn matches(
+        &self,
+        message: &Mt940CustomerStatementMessage<'_>,
+        statement_line: &StatementLine<'_>,
+    ) -> bool {
+        match self {
+            StatementLineFilter::ValueDate(start, end) => {
+                (*start, *end).contains(&statement_line.value_date().value())
+            }
+            StatementLineFilter::EntryDate(start, end) => statement_line
+                .entry_date()
+                .is_some_and(|date| (*start, *end).contains(&date.value())),
+            StatementLineFilter::Amount(start, end) => {
+                (*start, *end).contains(&statement_line.amount().value())
+            }
+            StatementLineFilter::Mark(mark) => statement_line.debit_credit_mark() == mark,
+            StatementLineFilter::TransactionType(code) => {
+                statement_line.transaction_type_identification_code() == code
+            }
+            StatementLineFilter::FundsCode(funds_code) => {
+                statement_line.funds_code() == Some(*funds_code)
+            }
+            StatementLineFilter::Currency(currency_code) => {
+                message.opening_balance().currency_code().as_str() == currency_code
+            }
+            StatementLineFilter::Reference(pattern) => {
+                pattern.matches(statement_line.account_owner_ref())
+                    || statement_line
+                        .bank_ref()
+                        .is_some_and(|bank_ref| pattern.matches(bank_ref))
+            }
+            StatementLineFilter::Information(pattern) => statement_line
+                .information_to_account_owner()
+                .is_some_and(|information| pattern.matches(&information.join(" "))),
+            StatementLineFilter::And(filters) => filters
+                .iter()
+                .all(|filter| filter.matches(message, statement_line)),
+            StatementLineFilter::Or(filters) => filters
+                .iter()
+                .any(|filter| filter.matches(message, statement_line)),
+            StatementLineFilter::Not(filter) => !filter.matches(message, statement_line),
+        }
+    }
+
+    pub fn apply<'m, 'a: 'm>(
+        &'m self,
+        messages: impl IntoIterator<Item = &'m Mt940CustomerStatementMessage<'a>> + 'm,
+    ) -> impl Iterator<Item = (&'m Mt940CustomerStatementMessage<'a>, &'m StatementLine<'a>)> + 'm
+    {
+        messages.into_iter().flat_map(move |message| {
+            message
+                .statement_lines()
+                .iter()
+                .filter(move |statement_line| self.matches(message, statement_line))
+                .map(move |statement_line| (message, statement_line))
+        })
+    }
+}
+
+impl Not for StatementLineFilter {
+    type Output = StatementLineFilter;
+    fn not(self) -> Self::Output {
+        match self {
+            StatementLineFilter::Not(filter) => *filter,
+            filter => StatementLineFilter::Not(Box::new(filter)),
+        }
+    }
+}
+
+impl TextPattern {
+    pub fn contains(value: &str) -> Self {
+        TextPattern::Contains(value.to_string())
+    }
+
+    pub fn starts_with(value: &str) -> Self {
+        TextPattern::StartsWith(value.to_string())
+    }
+
+    pub fn regex(pattern: &str) -> Result<Self, StatementLineFilterError> {
+        Regex::new(pattern)
+            .map(TextPattern::Regex)
+            .map_err(StatementLineFilterError::InvalidPattern)
+    }
+
+    pub fn matches(&self, value: &str) -> bool {
+        match self {
+            TextPattern::Contains(text) => value.contains(text.as_str()),
+            TextPattern::StartsWith(text) => value.starts_with(text.as_str()),
+            TextPattern::Regex(regex) => regex.is_match(value),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum StatementLineFilterError {
+    InvalidPattern(regex::Error),
+}
+
+impl Display for StatementLineFilterError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatementLineFilterError::InvalidPattern(err) => {
+                write!(f, "Filter pattern is invalid: {}", err)
+            }
+        }
+    }
+}
+
+impl Error for StatementLineFilterError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIRST_MESSAGE: &str = ":20:STMT1
+:25:DK5000400440116243
+:28C:1
+:60F:C230301EUR50000,00
+:61:2303020302D12000,00NTRFINV-1001//BR-1
+:86:INVOICE 1001
+ACME LTD
+:61:2303030303D500,00NCHKINV-1002
+:86:INVOICE 1002
+:61:2303040304C15000,00NTRFNONREF
+:86:SALARY
+:62F:C230304EUR52500,00
+-";
+    const SECOND_MESSAGE: &str = ":20:STMT2
+:25:DK5000400440116243
+:28C:2
+:60F:C230305DKK1000,00
+:61:2303060306DF20000,00NTRFINV-2001
+:86:INVOICE 2001 GLOBEX
+:62F:D230306DKK19000,00
+-";
+
+    fn messages() -> Vec<Mt940CustomerStatementMessage<'static>> {
+        [FIRST_MESSAGE, SECOND_MESSAGE]
+            .into_iter()
+            .map(|message| Mt940CustomerStatementMessage::try_from(message).unwrap())
+            .collect()
+    }
+
+    fn references(
+        filter: &StatementLineFilter,
+        messages: &[Mt940CustomerStatementMessage<'_>],
+    ) -> Vec<String> {
+        filter
+            .apply(messages)
+            .map(|(_, statement_line)| statement_line.account_owner_ref().to_string())
+            .collect()
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2023, 3, day).unwrap()
+    }
+
+    #[test]
+    fn test_filter_debits_above_amount() {
+        let messages = messages();
+        let filter = StatementLineFilter::Mark(StatementLineMark::Debit)
+            .and(StatementLineFilter::amount(Decimal::from(10000)..))
+            .and(StatementLineFilter::Reference(TextPattern::starts_with(
+                "INV",
+            )));
+        assert_eq!(references(&filter, &messages), ["INV-1001", "INV-2001"]);
+
+        let filter = filter.and(StatementLineFilter::Currency("EUR".to_string()));
+        assert_eq!(references(&filter, &messages), ["INV-1001"]);
+    }
+
+    #[test]
+    fn test_filter_date_ranges() {
+        let messages = messages();
+        let filter = StatementLineFilter::value_date(date(3)..date(6));
+        assert_eq!(references(&filter, &messages), ["INV-1002", "NONREF"]);
+
+        let filter = StatementLineFilter::entry_date(date(3)..=date(6));
+        assert_eq!(
+            references(&filter, &messages),
+            ["INV-1002", "NONREF", "INV-2001"]
+        );
+    }
+
+    #[test]
+    fn test_filter_codes() {
+        let messages = messages();
+        let code = TransactionTypeIdentificationCode::try_from("NCHK")
+            .unwrap()
+            .into_owned();
+        let filter = StatementLineFilter::TransactionType(code);
+        assert_eq!(references(&filter, &messages), ["INV-1002"]);
+
+        let filter = StatementLineFilter::FundsCode('F');
+        assert_eq!(references(&filter, &messages), ["INV-2001"]);
+    }
+
+    #[test]
+    fn test_filter_text_patterns() {
+        let messages = messages();
+        let filter = StatementLineFilter::Information(TextPattern::contains("ACME")).or(
+            StatementLineFilter::Information(TextPattern::regex(r"^INVOICE \d+ GLOBEX$").unwrap()),
+        );
+        assert_eq!(references(&filter, &messages), ["INV-1001", "INV-2001"]);
+
+        let filter = StatementLineFilter::Reference(TextPattern::contains("BR-1"));
+        assert_eq!(references(&filter, &messages), ["INV-1001"]);
+
+        let result = TextPattern::regex("(");
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .starts_with("Filter pattern is invalid: ")
+        );
+    }
+
+    #[test]
+    fn test_filter_negation() {
+        let messages = messages();
+        let filter = !StatementLineFilter::Reference(TextPattern::starts_with("INV"));
+        assert_eq!(references(&filter, &messages), ["NONREF"]);
+
+        let filter = !filter;
+        assert_eq!(
+            references(&filter, &messages),
+            ["INV-1001", "INV-1002", "INV-2001"]
+        );
+    }
+}
@@ -0,0 +1,344 @@
+use crate::money::*;
+use crate::mt_940_customer_statement_message::*;
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Write};
+
+const CSV_HEADER: &str = "group,key,currency,credit_count,credit_total,debit_count,debit_total";
+const MONTH_FORMAT: &str = "%Y-%m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportGrouping {
+    ValueDate,
+    Month,
+    TransactionType,
+    Currency,
+    Counterparty,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportRow {
+    key: String,
+    currency_code: CurrencyCode<'static>,
+    credit_count: usize,
+    credit_total: Decimal,
+    debit_count: usize,
+    debit_total: Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatementReport {
+    grouping: ReportGrouping,
+    rows: Vec<ReportRow>,
+}
+
+impl ReportGrouping {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReportGrouping::ValueDate => "value_date",
+            ReportGrouping::Month => "month",
+            ReportGrouping::TransactionType => "transaction_type",
+            ReportGrouping::Currency => "currency",
+            ReportGrouping::Counterparty => "counterparty",
+        }
+    }
+
+    fn key(
+        &self,
+        message: &Mt940CustomerStatementMessage<'_>,
+        statement_line: &StatementLine<'_>,
+    ) -> String {
+        match self {
+            ReportGrouping::ValueDate => statement_line.value_date().to_string(),
+            ReportGrouping::Month => statement_line
+                .value_date()
+                .value()
+                .format(MONTH_FORMAT)
+                .to_string(),
+            ReportGrouping::TransactionType => statement_line
+                .transaction_type_identification_code()
+                .to_string(),
+            ReportGrouping::Currency => message.opening_balance().currency_code().to_string(),
+            ReportGrouping::Counterparty => statement_line
+                .information_to_account_owner()
+                .and_then(|information| message.information_format().counterparty(information))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl ReportRow {
+    fn new(key: String, currency_code: CurrencyCode<'static>) -> Self {
+        Self {
+            key,
+            currency_code,
+            credit_count: 0,
+            credit_total: Decimal::ZERO,
+            debit_count: 0,
+            debit_total: Decimal::ZERO,
+        }
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn currency_code(&self) -> &CurrencyCode<'static> {
+        &self.currency_code
+    }
+
+    pub fn credit_count(&self) -> usize {
+        self.credit_count
+    }
+
+    pub fn credit_total(&self) -> Decimal {
+        self.credit_total
+    }
+
+    pub fn debit_count(&self) -> usize {
+        self.debit_count
+    }
+
+    pub fn debit_total(&self) -> Decimal {
+        self.debit_total
+    }
+
+    pub fn net_total(&self) -> Decimal {
+        self.credit_total - self.debit_total
+    }
+
+    fn add(&mut self, money: &Money) {
+        match money.credit_debit_mark() {
+            CreditDebitMark::Credit => {
+                self.credit_count += 1;
+                self.credit_total += money.value();
+            }
+            CreditDebitMark::Debit => {
+                self.debit_count += 1;
+                self.debit_total -= money.value();
+            }
+        }
+    }
+}
+
+impl StatementReport {
+    pub fn new<'m, 'a: 'm>(
+        messages: impl IntoIterator<Item = &'m Mt940CustomerStatementMessage<'a>>,
+        grouping: ReportGrouping,
+    ) -> Self {
+        let mut rows = BTreeMap::new();
+        for message in messages {
+            let currency_code = message.opening_balance().currency_code();
+            for statement_line in message.statement_lines() {
+                let key = grouping.key(message, statement_line);
+                let money = Money::from_statement_line(statement_line, currency_code);
+                rows.entry((key.clone(), currency_code.as_str().to_string()))
+                    .or_insert_with(|| ReportRow::new(key, money.currency_code().clone()))
+                    .add(&money);
+            }
+        }
+        Self {
+            grouping,
+            rows: rows.into_values().collect(),
+        }
+    }
+
+    pub fn grouping(&self) -> ReportGrouping {
+        self.grouping
+    }
+
+    pub fn rows(&self) -> &[ReportRow] {
+        &self.rows
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = format!("{}\n", CSV_HEADER);
+        for row in &self.rows {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{},{}",
+                self.grouping.name(),
+                csv_field(&row.key),
+                row.currency_code,
+                row.credit_count,
+                row.credit_total,
+                row.debit_count,
+                row.debit_total
+            );
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> String {
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                format!(
+                    "{{\"key\":{},\"currency\":{},\"credit_count\":{},\"credit_total\":\"{}\",\"debit_count\":{},\"debit_total\":\"{}\"}}",
+                    json_string(&row.key),
+                    json_string(row.currency_code.as_str()),
+                    row.credit_count,
+                    row.credit_total,
+                    row.debit_count,
+                    row.debit_total
+                )
+            })
+            .collect::<Vec<_>>();
+        format!(
+            "{{\"group\":{},\"rows\":[{}]}}",
+            json_string(self.grouping.name()),
+            rows.join(",")
+        )
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub(crate) fn json_string(value: &str) -> String {
+    let mut json = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", u32::from(c));
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+impl Display for StatementReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Report by {}", self.grouping.name())?;
+        for row in &self.rows {
+            writeln!(
+                f,
+                "- {} {}: {} credits {}, {} debits {}",
+                row.key,
+                row.currency_code,
+                row.credit_count,
+                row.credit_total,
+                row.debit_count,
+                row.debit_total
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::*;
+    use std::str::FromStr;
+
+    const FIRST_MESSAGE: &str = ":20:STMT1
+:25:NL91ABNA0417164300
+:28C:1
+:60F:C230228EUR1000,00
+:61:230228D200,50NTRFINV-1001
+:86:/NAME/ACME, LTD/REMI/INVOICE 1001
+:61:230301D100,00NTRFINV-1002
+:86:/NAME/ACME, LTD/REMI/INVOICE 1002
+:61:230301C300,00NCHKNONREF
+:86:/NAME/GLOBEX/REMI/REFUND
+:62F:C230301EUR999,50
+-";
+    const SECOND_MESSAGE: &str = ":20:STMT2
+:25:NL91ABNA0417164300
+:28C:2
+:60F:C230301USD500,00
+:61:230301C50,NTRFNONREF
+:62F:C230301USD550,00
+-";
+
+    fn build_report(grouping: ReportGrouping) -> StatementReport {
+        let messages = [FIRST_MESSAGE, SECOND_MESSAGE]
+            .into_iter()
+            .map(|message| {
+                Mt940CustomerStatementMessage::parse_with_dialect(message, &AbnAmro).unwrap()
+            })
+            .collect::<Vec<_>>();
+        StatementReport::new(&messages, grouping)
+    }
+
+    fn decimal(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    #[test]
+    fn test_report_by_value_date() {
+        let report = build_report(ReportGrouping::ValueDate);
+        let rows = report.rows();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].key(), "2023-02-28");
+        assert_eq!(rows[0].debit_count(), 1);
+        assert_eq!(rows[0].debit_total(), decimal("200.50"));
+        assert_eq!(rows[1].key(), "2023-03-01");
+        assert_eq!(rows[1].currency_code().as_str(), "EUR");
+        assert_eq!(rows[1].credit_count(), 1);
+        assert_eq!(rows[1].credit_total(), decimal("300"));
+        assert_eq!(rows[1].debit_total(), decimal("100"));
+        assert_eq!(rows[1].net_total(), decimal("200"));
+        assert_eq!(rows[2].currency_code().as_str(), "USD");
+        assert_eq!(
+            report.to_string(),
+            "Report by value_date
+- 2023-02-28 EUR: 0 credits 0, 1 debits 200.50
+- 2023-03-01 EUR: 1 credits 300.00, 1 debits 100.00
+- 2023-03-01 USD: 1 credits 50, 0 debits 0
+"
+        );
+    }
+
+    #[test]
+    fn test_report_by_month_and_type() {
+        let report = build_report(ReportGrouping::Month);
+        let keys = report.rows().iter().map(ReportRow::key).collect::<Vec<_>>();
+        assert_eq!(keys, ["2023-02", "2023-03", "2023-03"]);
+
+        let report = build_report(ReportGrouping::TransactionType);
+        let keys = report.rows().iter().map(ReportRow::key).collect::<Vec<_>>();
+        assert_eq!(keys, ["NCHK", "NTRF", "NTRF"]);
+        assert_eq!(report.rows()[1].debit_count(), 2);
+        assert_eq!(report.rows()[1].debit_total(), decimal("300.50"));
+    }
+
+    #[test]
+    fn test_report_by_counterparty_to_csv() {
+        let report = build_report(ReportGrouping::Counterparty);
+        assert_eq!(
+            report.to_csv(),
+            "group,key,currency,credit_count,credit_total,debit_count,debit_total
+counterparty,,USD,1,50,0,0
+counterparty,\"ACME, LTD\",EUR,0,0,2,300.50
+counterparty,GLOBEX,EUR,1,300.00,0,0
+"
+        );
+    }
+
+    #[test]
+    fn test_report_by_currency_to_json() {
+        let report = build_report(ReportGrouping::Currency);
+        assert_eq!(
+            report.to_json(),
+            "{\"group\":\"currency\",\"rows\":[\
+{\"key\":\"EUR\",\"currency\":\"EUR\",\"credit_count\":1,\"credit_total\":\"300.00\",\"debit_count\":2,\"debit_total\":\"300.50\"},\
+{\"key\":\"USD\",\"currency\":\"USD\",\"credit_count\":1,\"credit_total\":\"50\",\"debit_count\":0,\"debit_total\":\"0\"}]}"
+        );
+        assert_eq!(json_string("A \"B\"\n"), "\"A \\\"B\\\"\\n\"");
+    }
+}
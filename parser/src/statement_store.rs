@@ -0,0 +1,592 @@
+mod migrations;
+
+use crate::dialect::*;
+use crate::mt_940_customer_statement_message::*;
+use crate::statement_store::migrations::*;
+use chrono::NaiveDate;
+use rusqlite::{Connection, OptionalExtension, Row, params};
+use std::error::Error;
+use std::fmt::{Display, Formatter, Write};
+use std::path::Path;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const SWIFT_DATE_FORMAT: &str = "%y%m%d";
+const SWIFT_ENTRY_DATE_FORMAT: &str = "%m%d";
+const STORED_REFERENCE_MAX_LENGTH: usize = 35;
+const STORED_BALANCE_MAX_LENGTH: usize = 35;
+const OPENING_BALANCE: &str = "opening";
+const CLOSING_BALANCE: &str = "closing";
+const CLOSING_AVAILABLE_BALANCE: &str = "closing_available";
+const FORWARD_AVAILABLE_BALANCE: &str = "forward_available";
+
+pub struct StatementStore {
+    connection: Connection,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportStatus {
+    Imported(i64),
+    AlreadyImported(i64),
+}
+
+struct StoredDialect(InformationFormat);
+
+impl Dialect for StoredDialect {
+    fn name(&self) -> &'static str {
+        "Stored"
+    }
+
+    fn transaction_reference_number_max_length(&self) -> usize {
+        STORED_REFERENCE_MAX_LENGTH
+    }
+
+    fn related_reference_max_length(&self) -> usize {
+        STORED_REFERENCE_MAX_LENGTH
+    }
+
+    fn balance_max_length(&self) -> usize {
+        STORED_BALANCE_MAX_LENGTH
+    }
+
+    fn information_format(&self) -> InformationFormat {
+        self.0
+    }
+
+    fn requires_statement_sequence_number(&self) -> bool {
+        false
+    }
+}
+
+impl StatementStore {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, StatementStoreError> {
+        Self::new(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, StatementStoreError> {
+        Self::new(Connection::open_in_memory()?)
+    }
+
+    pub fn new(mut connection: Connection) -> Result<Self, StatementStoreError> {
+        connection.pragma_update(None, "foreign_keys", true)?;
+        let version = schema_version(&connection)?;
+        if version > latest_version() {
+            return Err(StatementStoreError::UnsupportedSchemaVersion(version));
+        }
+        let transaction = connection.transaction()?;
+        migrate(&transaction, version)?;
+        transaction.commit()?;
+        Ok(Self { connection })
+    }
+
+    pub fn schema_version(&self) -> Result<usize, StatementStoreError> {
+        Ok(schema_version(&self.connection)?)
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    pub fn import(
+        &mut self,
+        message: &Mt940CustomerStatementMessage<'_>,
+    ) -> Result<ImportStatus, StatementStoreError> {
+        let transaction = self.connection.transaction()?;
+        let account = message.account_identification().as_str();
+        transaction.execute(
+            "INSERT INTO accounts (identification) VALUES (?1)
+            ON CONFLICT (identification) DO NOTHING",
+            [account],
+        )?;
+        let account_id: i64 = transaction.query_row(
+            "SELECT id FROM accounts WHERE identification = ?1",
+            [account],
+            |row| row.get(0),
+        )?;
+        let transaction_reference_number = message.transaction_reference_number().as_str();
+        let statement_sequence_number = message
+            .statement_sequence_no()
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        let existing = transaction
+            .query_row(
+                "SELECT id FROM statements WHERE account_id = ?1
+                AND transaction_reference_number = ?2 AND statement_sequence_number = ?3",
+                params![
+                    account_id,
+                    transaction_reference_number,
+                    statement_sequence_number
+                ],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(statement_id) = existing {
+            return Ok(ImportStatus::AlreadyImported(statement_id));
+        }
+
+        transaction.execute(
+            "INSERT INTO statements (account_id, transaction_reference_number, related_reference,
+            statement_sequence_number, information_format, information_to_account_owner)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                account_id,
+                transaction_reference_number,
+                message.related_reference().map(RelatedReference::as_str),
+                statement_sequence_number,
+                information_format_name(message.information_format()),
+                message
+                    .information_to_account_owner()
+                    .map(|information| information.join("\n")),
+            ],
+        )?;
+        let statement_id = transaction.last_insert_rowid();
+
+        let balances = [
+            (OPENING_BALANCE, Some(message.opening_balance())),
+            (CLOSING_BALANCE, Some(message.closing_balance())),
+            (
+                CLOSING_AVAILABLE_BALANCE,
+                message.closing_available_balance(),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(kind, balance)| balance.map(|balance| (kind, 0, balance)))
+        .chain(
+            message
+                .forward_available_balances()
+                .iter()
+                .enumerate()
+                .map(|(position, balance)| (FORWARD_AVAILABLE_BALANCE, position, balance)),
+        );
+        for (kind, position, balance) in balances {
+            transaction.execute(
+                "INSERT INTO balances (statement_id, kind, position, mark, date, currency, amount,
+                scale)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    statement_id,
+                    kind,
+                    position,
+                    balance.debit_credit_mark().code().to_string(),
+                    balance.date().value().format(DATE_FORMAT).to_string(),
+                    balance.currency_code().as_str(),
+                    balance.amount().value().to_string(),
+                    balance.amount().value().scale(),
+                ],
+            )?;
+        }
+
+        for (position, statement_line) in message.statement_lines().iter().enumerate() {
+            transaction.execute(
+                "INSERT INTO statement_lines (statement_id, position, value_date, entry_date, mark,
+                funds_code, amount, scale, transaction_type, account_owner_ref, bank_ref,
+                supplementary_details, information_to_account_owner)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                params![
+                    statement_id,
+                    position,
+                    statement_line
+                        .value_date()
+                        .value()
+                        .format(DATE_FORMAT)
+                        .to_string(),
+                    statement_line
+                        .entry_date()
+                        .map(|date| date.value().format(DATE_FORMAT).to_string()),
+                    statement_line.debit_credit_mark().code(),
+                    statement_line.funds_code().map(String::from),
+                    statement_line.amount().value().to_string(),
+                    statement_line.amount().value().scale(),
+                    statement_line
+                        .transaction_type_identification_code()
+                        .to_string(),
+                    statement_line.account_owner_ref(),
+                    statement_line.bank_ref(),
+                    statement_line.supplementary_details(),
+                    statement_line
+                        .information_to_account_owner()
+                        .map(|information| information.join("\n")),
+                ],
+            )?;
+        }
+
+        transaction.commit()?;
+        Ok(ImportStatus::Imported(statement_id))
+    }
+
+    pub fn find(
+        &self,
+        account: &str,
+        transaction_reference_number: &str,
+        statement_sequence_number: Option<&StatementSequenceNumber>,
+    ) -> Result<Option<Mt940CustomerStatementMessage<'static>>, StatementStoreError> {
+        let statement_id = self
+            .connection
+            .query_row(
+                "SELECT statements.id FROM statements
+                JOIN accounts ON accounts.id = statements.account_id
+                WHERE accounts.identification = ?1 AND transaction_reference_number = ?2
+                AND statement_sequence_number = ?3",
+                params![
+                    account,
+                    transaction_reference_number,
+                    statement_sequence_number
+                        .map(ToString::to_string)
+                        .unwrap_or_default()
+                ],
+                |row| row.get(0),
+            )
+            .optional()?;
+        statement_id.map(|id| self.load(id)).transpose()
+    }
+
+    pub fn load_account(
+        &self,
+        account: &str,
+    ) -> Result<Vec<Mt940CustomerStatementMessage<'static>>, StatementStoreError> {
+        let mut statement = self.connection.prepare(
+            "SELECT statements.id FROM statements
+            JOIN accounts ON accounts.id = statements.account_id
+            WHERE accounts.identification = ?1 ORDER BY statements.id",
+        )?;
+        let statement_ids = statement
+            .query_map([account], |row| row.get(0))?
+            .collect::<Result<Vec<i64>, _>>()?;
+        statement_ids.into_iter().map(|id| self.load(id)).collect()
+    }
+
+    pub fn load(
+        &self,
+        statement_id: i64,
+    ) -> Result<Mt940CustomerStatementMessage<'static>, StatementStoreError> {
+        let (
+            transaction_reference_number,
+            related_reference,
+            account,
+            statement_sequence_number,
+            information_format,
+            information,
+        ): (
+            String,
+            Option<String>,
+            String,
+            String,
+            String,
+            Option<String>,
+        ) = self.connection.query_row(
+            "SELECT transaction_reference_number, related_reference, identification,
+                statement_sequence_number, information_format, information_to_account_owner
+                FROM statements JOIN accounts ON accounts.id = statements.account_id
+                WHERE statements.id = ?1",
+            [statement_id],
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                ))
+            },
+        )?;
+        let mut text = format!(":20:{}\n", transaction_reference_number);
+        if let Some(related_reference) = related_reference {
+            let _ = writeln!(text, ":21:{}", related_reference);
+        }
+        let _ = writeln!(text, ":25:{}", account);
+        if !statement_sequence_number.is_empty() {
+            let _ = writeln!(text, ":28C:{}", statement_sequence_number);
+        }
+
+        let balances = self.balances(statement_id)?;
+        let balance = |kind: &'static str| {
+            balances
+                .iter()
+                .filter(move |(balance_kind, _)| balance_kind == kind)
+                .map(|(_, balance)| balance)
+        };
+        for balance in balance(OPENING_BALANCE) {
+            let _ = writeln!(text, ":60F:{}", balance);
+        }
+        self.write_statement_lines(statement_id, &mut text)?;
+        for balance in balance(CLOSING_BALANCE) {
+            let _ = writeln!(text, ":62F:{}", balance);
+        }
+        for balance in balance(CLOSING_AVAILABLE_BALANCE) {
+            let _ = writeln!(text, ":64:{}", balance);
+        }
+        for balance in balance(FORWARD_AVAILABLE_BALANCE) {
+            let _ = writeln!(text, ":65:{}", balance);
+        }
+        if let Some(information) = information {
+            let _ = writeln!(text, ":86:{}", information);
+        }
+        text.push('-');
+
+        let dialect = StoredDialect(information_format_from_name(&information_format));
+        Ok(Mt940CustomerStatementMessage::parse_with_dialect(&text, &dialect)?.into_owned())
+    }
+
+    fn balances(&self, statement_id: i64) -> Result<Vec<(String, String)>, StatementStoreError> {
+        let mut statement = self.connection.prepare(
+            "SELECT kind, mark, date, currency, printf('%.*f', scale, amount) FROM balances
+            WHERE statement_id = ?1 ORDER BY position, id",
+        )?;
+        let balances = statement
+            .query_map([statement_id], |row| {
+                Ok((
+                    row.get(0)?,
+                    format!(
+                        "{}{}{}{}",
+                        row.get::<_, String>(1)?,
+                        swift_date(row, 2, SWIFT_DATE_FORMAT)?,
+                        row.get::<_, String>(3)?,
+                        swift_amount(&row.get::<_, String>(4)?)
+                    ),
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(balances)
+    }
+
+    fn write_statement_lines(
+        &self,
+        statement_id: i64,
+        text: &mut String,
+    ) -> Result<(), StatementStoreError> {
+        let mut statement = self.connection.prepare(
+            "SELECT value_date, entry_date, mark, funds_code, printf('%.*f', scale, amount),
+            transaction_type,
+            account_owner_ref, bank_ref, supplementary_details, information_to_account_owner
+            FROM statement_lines WHERE statement_id = ?1 ORDER BY position",
+        )?;
+        let mut rows = statement.query([statement_id])?;
+        while let Some(row) = rows.next()? {
+            let _ = write!(
+                text,
+                ":61:{}{}{}{}{}{}{}",
+                swift_date(row, 0, SWIFT_DATE_FORMAT)?,
+                match row.get::<_, Option<String>>(1)? {
+                    Some(_) => swift_date(row, 1, SWIFT_ENTRY_DATE_FORMAT)?,
+                    None => String::new(),
+                },
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+                swift_amount(&row.get::<_, String>(4)?),
+                row.get::<_, String>(5)?,
+                row.get::<_, String>(6)?,
+            );
+            if let Some(bank_ref) = row.get::<_, Option<String>>(7)? {
+                let _ = write!(text, "//{}", bank_ref);
+            }
+            text.push('\n');
+            if let Some(supplementary_details) = row.get::<_, Option<String>>(8)? {
+                let _ = writeln!(text, "{}", supplementary_details);
+            }
+            if let Some(information) = row.get::<_, Option<String>>(9)? {
+                let _ = writeln!(text, ":86:{}", information);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn swift_date(row: &Row<'_>, index: usize, format: &str) -> rusqlite::Result<String> {
+    let value = row.get::<_, String>(index)?;
+    NaiveDate::parse_from_str(&value, DATE_FORMAT)
+        .map(|date| date.format(format).to_string())
+        .map_err(|err| {
+            rusqlite::Error::FromSqlConversionFailure(
+                index,
+                rusqlite::types::Type::Text,
+                Box::new(err),
+            )
+        })
+}
+
+fn swift_amount(value: &str) -> String {
+    match value.split_once('.') {
+        Some((integer, fraction)) => format!("{},{}", integer, fraction),
+        None => format!("{},", value),
+    }
+}
+
+fn information_format_name(information_format: InformationFormat) -> &'static str {
+    match information_format {
+        InformationFormat::Unstructured => "unstructured",
+        InformationFormat::SlashCodes => "slash_codes",
+        InformationFormat::QuestionMarkSubfields => "question_mark_subfields",
+    }
+}
+
+fn information_format_from_name(name: &str) -> InformationFormat {
+    match name {
+        "slash_codes" => InformationFormat::SlashCodes,
+        "question_mark_subfields" => InformationFormat::QuestionMarkSubfields,
+        _ => InformationFormat::Unstructured,
+    }
+}
+
+#[derive(Debug)]
+pub enum StatementStoreError {
+    Sqlite(rusqlite::Error),
+    UnsupportedSchemaVersion(usize),
+    InvalidMessage(Mt940CustomerStatementMessageParseError),
+}
+
+impl From<rusqlite::Error> for StatementStoreError {
+    fn from(err: rusqlite::Error) -> Self {
+        StatementStoreError::Sqlite(err)
+    }
+}
+
+impl From<Mt940CustomerStatementMessageParseError> for StatementStoreError {
+    fn from(err: Mt940CustomerStatementMessageParseError) -> Self {
+        StatementStoreError::InvalidMessage(err)
+    }
+}
+
+impl Display for StatementStoreError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatementStoreError::Sqlite(err) => write!(f, "Statement store error: {}", err),
+            StatementStoreError::UnsupportedSchemaVersion(version) => write!(
+                f,
+                "Statement store schema version {} is newer than supported version {}",
+                version,
+                latest_version()
+            ),
+            StatementStoreError::InvalidMessage(err) => {
+                write!(f, "Stored statement is invalid: {}", err)
+            }
+        }
+    }
+}
+
+impl Error for StatementStoreError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSAGE: &str = ":20:STMT230306
+:21:NONREF
+:25:DK5000400440116243
+:28C:45/1
+:60F:C230305DKK1000,00
+:61:2303060306D200,50NTRFINV-1001//BR-1
+PAYMENT FOR INVOICE
+:86:INVOICE 1001
+ACME LTD
+:61:230306CR50,NCHKNONREF
+:62F:C230306DKK849,50
+:64:C230306DKK849,50
+:65:C230307DKK849,50
+:65:C230308DKK849,50
+:86:END OF STATEMENT
+-";
+
+    #[test]
+    fn test_import_and_load() {
+        let message = Mt940CustomerStatementMessage::try_from(MESSAGE).unwrap();
+        let mut store = StatementStore::open_in_memory().unwrap();
+        assert_eq!(store.schema_version().unwrap(), latest_version());
+
+        let status = store.import(&message).unwrap();
+        assert_eq!(status, ImportStatus::Imported(1));
+        assert_eq!(
+            store.import(&message).unwrap(),
+            ImportStatus::AlreadyImported(1)
+        );
+        let count: i64 = store
+            .connection()
+            .query_row("SELECT COUNT(*) FROM statement_lines", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 2);
+
+        assert_eq!(store.load(1).unwrap(), message);
+        let found = store
+            .find(
+                "DK5000400440116243",
+                "STMT230306",
+                message.statement_sequence_no(),
+            )
+            .unwrap();
+        assert_eq!(found, Some(message));
+        assert_eq!(
+            store.find("DK5000400440116243", "OTHER", None).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_query_amount_range() {
+        let text = MESSAGE
+            .replace("D200,50", "D20000,50")
+            .replace("C230305DKK1000,00", "C230305DKK20849,50")
+            .replace("DKK849,50", "DKK849,5");
+        let message = Mt940CustomerStatementMessage::try_from(text.as_str()).unwrap();
+        let mut store = StatementStore::open_in_memory().unwrap();
+        store.import(&message).unwrap();
+
+        let amounts = store
+            .connection()
+            .prepare(
+                "SELECT account_owner_ref FROM statement_lines
+                WHERE amount BETWEEN 10000 AND 100000",
+            )
+            .unwrap()
+            .query_map([], |row| row.get::<_, String>(0))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(amounts, ["INV-1001"]);
+        let count: i64 = store
+            .connection()
+            .query_row(
+                "SELECT COUNT(*) FROM balances WHERE amount > 10000",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(store.load(1).unwrap(), message);
+        assert_eq!(
+            store
+                .load(1)
+                .unwrap()
+                .closing_balance()
+                .amount()
+                .to_string(),
+            message.closing_balance().amount().to_string()
+        );
+    }
+
+    #[test]
+    fn test_load_account() {
+        let text = MESSAGE.replace(":28C:45/1\n", "");
+        let first = Mt940CustomerStatementMessage::parse_with_dialect(&text, &Sberbank).unwrap();
+        let second = Mt940CustomerStatementMessage::parse_with_dialect(MESSAGE, &Ing).unwrap();
+        let mut store = StatementStore::open_in_memory().unwrap();
+        assert_eq!(store.import(&first).unwrap(), ImportStatus::Imported(1));
+        assert_eq!(store.import(&second).unwrap(), ImportStatus::Imported(2));
+
+        let messages = store.load_account("DK5000400440116243").unwrap();
+        assert_eq!(messages, [first, second]);
+        assert!(store.load_account("DK0000000000000000").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_unsupported_schema_version() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection.pragma_update(None, "user_version", 99).unwrap();
+        let result = StatementStore::new(connection);
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            format!(
+                "Statement store schema version 99 is newer than supported version {}",
+                latest_version()
+            )
+        );
+    }
+}
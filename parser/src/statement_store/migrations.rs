@@ -0,0 +1,163 @@
+use rusqlite::{Connection, Transaction};
+
+const MIGRATIONS: [&str; 3] = [
+    "CREATE TABLE accounts (
+        id INTEGER PRIMARY KEY,
+        identification TEXT NOT NULL UNIQUE
+    );
+    CREATE TABLE statements (
+        id INTEGER PRIMARY KEY,
+        account_id INTEGER NOT NULL REFERENCES accounts (id),
+        transaction_reference_number TEXT NOT NULL,
+        related_reference TEXT,
+        statement_sequence_number TEXT NOT NULL,
+        information_format TEXT NOT NULL,
+        information_to_account_owner TEXT,
+        UNIQUE (account_id, transaction_reference_number, statement_sequence_number)
+    );
+    CREATE TABLE balances (
+        id INTEGER PRIMARY KEY,
+        statement_id INTEGER NOT NULL REFERENCES statements (id) ON DELETE CASCADE,
+        kind TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        mark TEXT NOT NULL,
+        date TEXT NOT NULL,
+        currency TEXT NOT NULL,
+        amount TEXT NOT NULL
+    );
+    CREATE TABLE statement_lines (
+        id INTEGER PRIMARY KEY,
+        statement_id INTEGER NOT NULL REFERENCES statements (id) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        value_date TEXT NOT NULL,
+        entry_date TEXT,
+        mark TEXT NOT NULL,
+        funds_code TEXT,
+        amount TEXT NOT NULL,
+        transaction_type TEXT NOT NULL,
+        account_owner_ref TEXT NOT NULL,
+        bank_ref TEXT,
+        supplementary_details TEXT,
+        information_to_account_owner TEXT
+    );",
+    "CREATE INDEX balances_statement_id ON balances (statement_id);
+    CREATE INDEX statement_lines_statement_id ON statement_lines (statement_id);
+    CREATE INDEX statement_lines_value_date ON statement_lines (value_date);",
+    "CREATE TABLE numeric_balances (
+        id INTEGER PRIMARY KEY,
+        statement_id INTEGER NOT NULL REFERENCES statements (id) ON DELETE CASCADE,
+        kind TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        mark TEXT NOT NULL,
+        date TEXT NOT NULL,
+        currency TEXT NOT NULL,
+        amount NUMERIC NOT NULL,
+        scale INTEGER NOT NULL
+    );
+    INSERT INTO numeric_balances
+    SELECT id, statement_id, kind, position, mark, date, currency, CAST(amount AS NUMERIC),
+        CASE WHEN instr(amount, '.') > 0 THEN length(amount) - instr(amount, '.') ELSE 0 END
+    FROM balances;
+    DROP TABLE balances;
+    ALTER TABLE numeric_balances RENAME TO balances;
+    CREATE INDEX balances_statement_id ON balances (statement_id);
+    CREATE TABLE numeric_statement_lines (
+        id INTEGER PRIMARY KEY,
+        statement_id INTEGER NOT NULL REFERENCES statements (id) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        value_date TEXT NOT NULL,
+        entry_date TEXT,
+        mark TEXT NOT NULL,
+        funds_code TEXT,
+        amount NUMERIC NOT NULL,
+        scale INTEGER NOT NULL,
+        transaction_type TEXT NOT NULL,
+        account_owner_ref TEXT NOT NULL,
+        bank_ref TEXT,
+        supplementary_details TEXT,
+        information_to_account_owner TEXT
+    );
+    INSERT INTO numeric_statement_lines
+    SELECT id, statement_id, position, value_date, entry_date, mark, funds_code,
+        CAST(amount AS NUMERIC),
+        CASE WHEN instr(amount, '.') > 0 THEN length(amount) - instr(amount, '.') ELSE 0 END,
+        transaction_type, account_owner_ref, bank_ref, supplementary_details,
+        information_to_account_owner
+    FROM statement_lines;
+    DROP TABLE statement_lines;
+    ALTER TABLE numeric_statement_lines RENAME TO statement_lines;
+    CREATE INDEX statement_lines_statement_id ON statement_lines (statement_id);
+    CREATE INDEX statement_lines_value_date ON statement_lines (value_date);",
+];
+
+pub(crate) fn latest_version() -> usize {
+    MIGRATIONS.len()
+}
+
+pub(crate) fn schema_version(connection: &Connection) -> rusqlite::Result<usize> {
+    connection.query_row("PRAGMA user_version", [], |row| row.get(0))
+}
+
+pub(crate) fn migrate(transaction: &Transaction<'_>, version: usize) -> rusqlite::Result<()> {
+    for migration in &MIGRATIONS[version..] {
+        transaction.execute_batch(migration)?;
+    }
+    transaction.pragma_update(None, "user_version", MIGRATIONS.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_text_amounts() {
+        let mut connection = Connection::open_in_memory().unwrap();
+        connection.execute_batch(MIGRATIONS[0]).unwrap();
+        connection.execute_batch(MIGRATIONS[1]).unwrap();
+        connection
+            .execute_batch(
+                "INSERT INTO accounts VALUES (1, 'ACCOUNT');
+                INSERT INTO statements VALUES (1, 1, 'STMT', NULL, '1', 'unstructured', NULL);
+                INSERT INTO statement_lines (statement_id, position, value_date, mark, amount,
+                    transaction_type, account_owner_ref)
+                VALUES (1, 0, '2023-03-06', 'D', '9.50', 'NTRF', 'NONREF'),
+                    (1, 1, '2023-03-06', 'C', '10000', 'NTRF', 'NONREF');",
+            )
+            .unwrap();
+
+        let transaction = connection.transaction().unwrap();
+        migrate(&transaction, 2).unwrap();
+        transaction.commit().unwrap();
+        let amounts = connection
+            .prepare(
+                "SELECT printf('%.*f', scale, amount) FROM statement_lines
+                WHERE amount > 9 ORDER BY amount",
+            )
+            .unwrap()
+            .query_map([], |row| row.get::<_, String>(0))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(amounts, ["9.50", "10000"]);
+    }
+
+    #[test]
+    fn test_migrate_from_previous_version() {
+        let mut connection = Connection::open_in_memory().unwrap();
+        connection.execute_batch(MIGRATIONS[0]).unwrap();
+        connection.pragma_update(None, "user_version", 1).unwrap();
+
+        let transaction = connection.transaction().unwrap();
+        migrate(&transaction, schema_version(&transaction).unwrap()).unwrap();
+        transaction.commit().unwrap();
+        assert_eq!(schema_version(&connection).unwrap(), latest_version());
+        let indexes: i64 = connection
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type = 'index' AND name LIKE 'statement_lines_%'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(indexes, 2);
+    }
+}
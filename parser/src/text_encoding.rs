@@ -0,0 +1,402 @@
+use encoding_rs::{DecoderResult, EncoderResult, Encoding, IBM866, KOI8_R, WINDOWS_1251};
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+const EBCDIC_FIELD_TAG: &[u8] = b"\x7A\xF2\xF0\x7A";
+const EBCDIC_NEXT_LINE: u8 = 0x15;
+const EBCDIC_LINE_FEED: u8 = 0x25;
+const NON_ASCII_BYTE: u8 = 0xFF;
+const CYRILLIC_VOWELS: &str = "аеиоуыэюяАЕИОУЫЭЮЯ";
+const EBCDIC_037: [char; 256] = [
+    '\u{00}', '\u{01}', '\u{02}', '\u{03}', '\u{9c}', '\u{09}', '\u{86}', '\u{7f}', '\u{97}',
+    '\u{8d}', '\u{8e}', '\u{0b}', '\u{0c}', '\u{0d}', '\u{0e}', '\u{0f}', '\u{10}', '\u{11}',
+    '\u{12}', '\u{13}', '\u{9d}', '\u{85}', '\u{08}', '\u{87}', '\u{18}', '\u{19}', '\u{92}',
+    '\u{8f}', '\u{1c}', '\u{1d}', '\u{1e}', '\u{1f}', '\u{80}', '\u{81}', '\u{82}', '\u{83}',
+    '\u{84}', '\u{0a}', '\u{17}', '\u{1b}', '\u{88}', '\u{89}', '\u{8a}', '\u{8b}', '\u{8c}',
+    '\u{05}', '\u{06}', '\u{07}', '\u{90}', '\u{91}', '\u{16}', '\u{93}', '\u{94}', '\u{95}',
+    '\u{96}', '\u{04}', '\u{98}', '\u{99}', '\u{9a}', '\u{9b}', '\u{14}', '\u{15}', '\u{9e}',
+    '\u{1a}', ' ', '\u{a0}', '\u{e2}', '\u{e4}', '\u{e0}', '\u{e1}', '\u{e3}', '\u{e5}', '\u{e7}',
+    '\u{f1}', '\u{a2}', '.', '<', '(', '+', '|', '&', '\u{e9}', '\u{ea}', '\u{eb}', '\u{e8}',
+    '\u{ed}', '\u{ee}', '\u{ef}', '\u{ec}', '\u{df}', '!', '$', '*', ')', ';', '\u{ac}', '-', '/',
+    '\u{c2}', '\u{c4}', '\u{c0}', '\u{c1}', '\u{c3}', '\u{c5}', '\u{c7}', '\u{d1}', '\u{a6}', ',',
+    '%', '_', '>', '?', '\u{f8}', '\u{c9}', '\u{ca}', '\u{cb}', '\u{c8}', '\u{cd}', '\u{ce}',
+    '\u{cf}', '\u{cc}', '`', ':', '#', '@', '\'', '=', '"', '\u{d8}', 'a', 'b', 'c', 'd', 'e', 'f',
+    'g', 'h', 'i', '\u{ab}', '\u{bb}', '\u{f0}', '\u{fd}', '\u{fe}', '\u{b1}', '\u{b0}', 'j', 'k',
+    'l', 'm', 'n', 'o', 'p', 'q', 'r', '\u{aa}', '\u{ba}', '\u{e6}', '\u{b8}', '\u{c6}', '\u{a4}',
+    '\u{b5}', '~', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', '\u{a1}', '\u{bf}', '\u{d0}', '\u{dd}',
+    '\u{de}', '\u{ae}', '^', '\u{a3}', '\u{a5}', '\u{b7}', '\u{a9}', '\u{a7}', '\u{b6}', '\u{bc}',
+    '\u{bd}', '\u{be}', '[', ']', '\u{af}', '\u{a8}', '\u{b4}', '\u{d7}', '{', 'A', 'B', 'C', 'D',
+    'E', 'F', 'G', 'H', 'I', '\u{ad}', '\u{f4}', '\u{f6}', '\u{f2}', '\u{f3}', '\u{f5}', '}', 'J',
+    'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', '\u{b9}', '\u{fb}', '\u{fc}', '\u{f9}', '\u{fa}',
+    '\u{ff}', '\\', '\u{f7}', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '\u{b2}', '\u{d4}', '\u{d6}',
+    '\u{d2}', '\u{d3}', '\u{d5}', '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '\u{b3}',
+    '\u{db}', '\u{dc}', '\u{d9}', '\u{da}', '\u{9f}',
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+    Utf8,
+    Windows1251,
+    Koi8R,
+    Ibm866,
+    Latin1,
+    Ebcdic,
+}
+
+impl TextEncoding {
+    pub fn detect(bytes: &[u8]) -> Self {
+        if bytes.starts_with(UTF8_BOM) || std::str::from_utf8(bytes).is_ok() {
+            return TextEncoding::Utf8;
+        }
+        if bytes
+            .windows(EBCDIC_FIELD_TAG.len())
+            .any(|window| window == EBCDIC_FIELD_TAG)
+        {
+            return TextEncoding::Ebcdic;
+        }
+
+        let is_high = |byte: &u8| *byte >= 0x80;
+        let high = bytes.iter().filter(|byte| is_high(byte)).count();
+        let adjacent = bytes
+            .windows(2)
+            .filter(|pair| is_high(&pair[0]) && is_high(&pair[1]))
+            .count();
+        if adjacent * 2 < high {
+            return TextEncoding::Latin1;
+        }
+
+        [
+            (TextEncoding::Windows1251, WINDOWS_1251),
+            (TextEncoding::Koi8R, KOI8_R),
+            (TextEncoding::Ibm866, IBM866),
+        ]
+        .into_iter()
+        .map(|(text_encoding, encoding)| (text_encoding, vowel_count(encoding, bytes)))
+        .reduce(|best, candidate| {
+            if candidate.1 > best.1 {
+                candidate
+            } else {
+                best
+            }
+        })
+        .map_or(TextEncoding::Windows1251, |(text_encoding, _)| {
+            text_encoding
+        })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TextEncoding::Utf8 => "UTF-8",
+            TextEncoding::Windows1251 => "Windows-1251",
+            TextEncoding::Koi8R => "KOI8-R",
+            TextEncoding::Ibm866 => "IBM866",
+            TextEncoding::Latin1 => "ISO-8859-1",
+            TextEncoding::Ebcdic => "EBCDIC 037",
+        }
+    }
+
+    pub fn decode<'a>(&self, bytes: &'a [u8]) -> Result<Cow<'a, str>, TextEncodingError> {
+        match self {
+            TextEncoding::Utf8 => decode_utf8(bytes),
+            TextEncoding::Windows1251 => decode_with(WINDOWS_1251, bytes).map(Cow::Owned),
+            TextEncoding::Koi8R => decode_with(KOI8_R, bytes).map(Cow::Owned),
+            TextEncoding::Ibm866 => decode_with(IBM866, bytes).map(Cow::Owned),
+            TextEncoding::Latin1 => Ok(Cow::Owned(
+                bytes.iter().map(|byte| char::from(*byte)).collect(),
+            )),
+            TextEncoding::Ebcdic => Ok(Cow::Owned(
+                bytes.iter().map(|byte| ebcdic_char(*byte)).collect(),
+            )),
+        }
+    }
+
+    pub fn encode<'a>(&self, text: &'a str) -> Result<Cow<'a, [u8]>, TextEncodingError> {
+        match self {
+            TextEncoding::Utf8 => Ok(Cow::Borrowed(text.as_bytes())),
+            TextEncoding::Windows1251 => encode_with(WINDOWS_1251, text).map(Cow::Owned),
+            TextEncoding::Koi8R => encode_with(KOI8_R, text).map(Cow::Owned),
+            TextEncoding::Ibm866 => encode_with(IBM866, text).map(Cow::Owned),
+            TextEncoding::Latin1 => encode_chars(text, |c| u8::try_from(c).ok()),
+            TextEncoding::Ebcdic => {
+                encode_chars(text, |c| (0..=u8::MAX).find(|byte| ebcdic_char(*byte) == c))
+            }
+        }
+        .map_err(|offset| TextEncodingError::UnencodableCharacter(self.name(), offset))
+    }
+
+    pub(crate) fn ascii_byte(&self, byte: u8) -> u8 {
+        match self {
+            TextEncoding::Ebcdic => {
+                let c = ebcdic_char(byte);
+                if c.is_ascii() {
+                    c as u8
+                } else {
+                    NON_ASCII_BYTE
+                }
+            }
+            _ => byte,
+        }
+    }
+}
+
+fn ebcdic_char(byte: u8) -> char {
+    match byte {
+        EBCDIC_NEXT_LINE => '\n',
+        EBCDIC_LINE_FEED => EBCDIC_037[usize::from(EBCDIC_NEXT_LINE)],
+        byte => EBCDIC_037[usize::from(byte)],
+    }
+}
+
+fn vowel_count(encoding: &'static Encoding, bytes: &[u8]) -> usize {
+    let (text, _) = encoding.decode_without_bom_handling(bytes);
+    text.chars()
+        .filter(|c| CYRILLIC_VOWELS.contains(*c))
+        .count()
+}
+
+fn decode_utf8(bytes: &[u8]) -> Result<Cow<'_, str>, TextEncodingError> {
+    let start = if bytes.starts_with(UTF8_BOM) {
+        UTF8_BOM.len()
+    } else {
+        0
+    };
+    if let Ok(text) = std::str::from_utf8(&bytes[start..]) {
+        return Ok(Cow::Borrowed(text));
+    }
+    let mut offsets = Vec::new();
+    let mut position = start;
+    while let Err(err) = std::str::from_utf8(&bytes[position..]) {
+        position += err.valid_up_to();
+        offsets.push(position);
+        position += err.error_len().unwrap_or(bytes.len() - position);
+    }
+    Err(TextEncodingError::UndecodableBytes(offsets))
+}
+
+fn decode_with(encoding: &'static Encoding, bytes: &[u8]) -> Result<String, TextEncodingError> {
+    let mut decoder = encoding.new_decoder_without_bom_handling();
+    let mut text = String::with_capacity(bytes.len() * 2);
+    let mut offsets = Vec::new();
+    let mut position = 0;
+    loop {
+        let (result, read) =
+            decoder.decode_to_string_without_replacement(&bytes[position..], &mut text, true);
+        position += read;
+        match result {
+            DecoderResult::InputEmpty => break,
+            DecoderResult::OutputFull => text.reserve(
+                decoder
+                    .max_utf8_buffer_length_without_replacement(bytes.len() - position)
+                    .unwrap_or(bytes.len()),
+            ),
+            DecoderResult::Malformed(length, consumed) => {
+                offsets.push(position - usize::from(consumed) - usize::from(length));
+            }
+        }
+    }
+    if offsets.is_empty() {
+        Ok(text)
+    } else {
+        Err(TextEncodingError::UndecodableBytes(offsets))
+    }
+}
+
+fn encode_with(encoding: &'static Encoding, text: &str) -> Result<Vec<u8>, usize> {
+    let mut encoder = encoding.new_encoder();
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut position = 0;
+    loop {
+        let (result, read) = encoder.encode_from_utf8_to_vec_without_replacement(
+            &text[position..],
+            &mut bytes,
+            true,
+        );
+        position += read;
+        match result {
+            EncoderResult::InputEmpty => return Ok(bytes),
+            EncoderResult::OutputFull => bytes.reserve(
+                encoder
+                    .max_buffer_length_from_utf8_without_replacement(text.len() - position)
+                    .unwrap_or(text.len()),
+            ),
+            EncoderResult::Unmappable(c) => return Err(position - c.len_utf8()),
+        }
+    }
+}
+
+fn encode_chars(text: &str, encode: impl Fn(char) -> Option<u8>) -> Result<Cow<'_, [u8]>, usize> {
+    text.char_indices()
+        .map(|(offset, c)| encode(c).ok_or(offset))
+        .collect::<Result<Vec<_>, _>>()
+        .map(Cow::Owned)
+}
+
+impl TryFrom<&str> for TextEncoding {
+    type Error = TextEncodingError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "utf-8" | "utf8" => Ok(TextEncoding::Utf8),
+            "windows-1251" | "cp1251" => Ok(TextEncoding::Windows1251),
+            "koi8-r" | "koi8r" => Ok(TextEncoding::Koi8R),
+            "ibm866" | "cp866" => Ok(TextEncoding::Ibm866),
+            "iso-8859-1" | "latin1" => Ok(TextEncoding::Latin1),
+            "ebcdic" | "cp037" | "ibm037" => Ok(TextEncoding::Ebcdic),
+            _ => Err(TextEncodingError::UnknownEncoding(value.to_string())),
+        }
+    }
+}
+
+impl Display for TextEncoding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TextEncodingError {
+    UnknownEncoding(String),
+    UndecodableBytes(Vec<usize>),
+    UnencodableCharacter(&'static str, usize),
+}
+
+impl Display for TextEncodingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextEncodingError::UnknownEncoding(name) => write!(f, "Unknown encoding {}", name),
+            TextEncodingError::UndecodableBytes(offsets) => {
+                let offsets = offsets.iter().map(ToString::to_string).collect::<Vec<_>>();
+                write!(f, "Undecodable bytes at offsets {}", offsets.join(", "))
+            }
+            TextEncodingError::UnencodableCharacter(encoding, offset) => write!(
+                f,
+                "Character at offset {} cannot be encoded in {}",
+                offset, encoding
+            ),
+        }
+    }
+}
+
+impl Error for TextEncodingError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = ":86:Оплата по счету 1001 от поставщика\n";
+
+    #[test]
+    fn test_detect_encoding() {
+        assert_eq!(TextEncoding::detect(TEXT.as_bytes()), TextEncoding::Utf8);
+
+        let (bytes, _, _) = WINDOWS_1251.encode(TEXT);
+        assert_eq!(TextEncoding::detect(&bytes), TextEncoding::Windows1251);
+
+        let (bytes, _, _) = KOI8_R.encode(TEXT);
+        assert_eq!(TextEncoding::detect(&bytes), TextEncoding::Koi8R);
+
+        let (bytes, _, _) = WINDOWS_1251.encode("ООО РОМАШКА");
+        assert_eq!(TextEncoding::detect(&bytes), TextEncoding::Windows1251);
+
+        let (bytes, _, _) = KOI8_R.encode("ООО РОМАШКА");
+        assert_eq!(TextEncoding::detect(&bytes), TextEncoding::Koi8R);
+
+        let (bytes, _, _) = IBM866.encode(TEXT);
+        assert_eq!(TextEncoding::detect(&bytes), TextEncoding::Ibm866);
+
+        let (bytes, _, _) = IBM866.encode("ООО РОМАШКА");
+        assert_eq!(TextEncoding::detect(&bytes), TextEncoding::Ibm866);
+
+        let bytes = b":86:M\xFCller Stra\xDFe Z\xFCrich\n";
+        assert_eq!(TextEncoding::detect(bytes), TextEncoding::Latin1);
+
+        let bytes = TextEncoding::Ebcdic.encode(":20:STMT\n").unwrap();
+        assert_eq!(TextEncoding::detect(&bytes), TextEncoding::Ebcdic);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for encoding in [
+            TextEncoding::Utf8,
+            TextEncoding::Windows1251,
+            TextEncoding::Koi8R,
+            TextEncoding::Ibm866,
+        ] {
+            let bytes = encoding.encode(TEXT).unwrap();
+            assert_eq!(encoding.decode(&bytes).unwrap(), TEXT);
+        }
+
+        let text = ":86:Müller Straße\n:20:STMT\n";
+        for encoding in [TextEncoding::Latin1, TextEncoding::Ebcdic] {
+            let bytes = encoding.encode(text).unwrap();
+            assert_eq!(encoding.decode(&bytes).unwrap(), text);
+        }
+        assert_eq!(
+            TextEncoding::Ebcdic
+                .decode(b"\x7A\xF2\xF0\x7A\xC1\x15")
+                .unwrap(),
+            ":20:A\n"
+        );
+        assert_eq!(
+            TextEncoding::Ebcdic.encode(":20:A\n").unwrap(),
+            &b"\x7A\xF2\xF0\x7A\xC1\x15"[..]
+        );
+        let bytes = (0..=u8::MAX).collect::<Vec<_>>();
+        let text = TextEncoding::Ebcdic.decode(&bytes).unwrap();
+        assert_eq!(TextEncoding::Ebcdic.encode(&text).unwrap(), &bytes[..]);
+
+        let bytes = [0xFF, 0xFF];
+        let encoding = TextEncoding::detect(&bytes);
+        let text = encoding.decode(&bytes).unwrap();
+        assert_eq!(encoding.encode(&text).unwrap(), &bytes[..]);
+    }
+
+    #[test]
+    fn test_undecodable_bytes() {
+        let result = TextEncoding::Utf8.decode(b":20:\xFFOK\xC3");
+        assert_eq!(result, Err(TextEncodingError::UndecodableBytes(vec![4, 7])));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Undecodable bytes at offsets 4, 7"
+        );
+
+        let result = TextEncoding::Utf8.decode(b"\xEF\xBB\xBF:20:\xFF");
+        assert_eq!(result, Err(TextEncodingError::UndecodableBytes(vec![7])));
+    }
+
+    #[test]
+    fn test_unencodable_character() {
+        let result = TextEncoding::Windows1251.encode(":20:€✓");
+        assert_eq!(
+            result,
+            Err(TextEncodingError::UnencodableCharacter("Windows-1251", 7))
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Character at offset 7 cannot be encoded in Windows-1251"
+        );
+
+        let result = TextEncoding::Latin1.encode("Ж");
+        assert_eq!(
+            result,
+            Err(TextEncodingError::UnencodableCharacter("ISO-8859-1", 0))
+        );
+    }
+
+    #[test]
+    fn test_encoding_from_label() {
+        assert_eq!(
+            TextEncoding::try_from("CP1251"),
+            Ok(TextEncoding::Windows1251)
+        );
+        assert_eq!(TextEncoding::try_from("koi8-r"), Ok(TextEncoding::Koi8R));
+        assert_eq!(TextEncoding::try_from("CP866"), Ok(TextEncoding::Ibm866));
+        let result = TextEncoding::try_from("utf-16");
+        assert_eq!(
+            result,
+            Err(TextEncodingError::UnknownEncoding("utf-16".to_string()))
+        );
+        assert_eq!(result.unwrap_err().to_string(), "Unknown encoding utf-16");
+    }
+}